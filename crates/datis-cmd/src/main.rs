@@ -1,10 +1,16 @@
+use std::path::PathBuf;
 use std::str::FromStr;
 
 use clap::{App, Arg};
 use datis_core::Datis;
-use datis_core::config::{AwsConfig, AzureConfig, Config, GcloudConfig};
-use datis_core::station::{Airfield, MissionInterface, Position, Station, Transmitter};
+use datis_core::config::{AwsConfig, AzureConfig, Config, GcloudConfig, LocalConfig};
+use datis_core::locale::Locale;
+use datis_core::station::{
+    Airfield, Coalition, MissionInterface, Position, Runway, RunwayRules, Station, Transmitter,
+};
 use datis_core::tts::TextToSpeechProvider;
+use datis_core::tts::prosody::Prosody;
+use datis_core::units::UnitProfile;
 use dotenv::dotenv;
 
 pub fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -70,6 +76,20 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .env("AZURE_REGION")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("piper")
+                .long("piper")
+                .env("PIPER_PATH")
+                .help("Sets the path to the piper executable (for PIPER:{model} voices)")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("espeak")
+                .long("espeak")
+                .env("ESPEAK_PATH")
+                .help("Sets the path to the espeak-ng executable (for LOCAL voices)")
+                .takes_value(true),
+        )
         .get_matches();
 
     let freq = matches.value_of("frequency").unwrap();
@@ -93,18 +113,32 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
         name: String::from("Test Station"),
         freq,
         tts,
+        fallback_tts: Vec::new(),
+        prosody: Prosody::default(),
         transmitter: Transmitter::Airfield(Airfield {
             name: String::from("Test"),
+            icao: None,
             position: Position::default(),
-            runways: vec![String::from("09"), String::from("26")],
+            runways: vec![Runway::new("09", "26")],
             traffic_freq: None,
             info_ltr_offset: 0,
             info_ltr_override: None,
             active_rwy_override: None,
             no_hpa: false,
             no_qfe: false,
+            dew_point: false,
+            density_alt: false,
+            transition_alt: None,
+            runway_rules: RunwayRules::default(),
+            locale: Locale::default(),
+            units: UnitProfile::default(),
+            crosswind_limit: None,
+            tailwind_limit: None,
         }),
         ipc: MissionInterface::Static,
+        coalitions: vec![Coalition::Blue],
+        template: None,
+        radio_effects: None,
     };
     let mut config = Config::default();
 
@@ -136,6 +170,11 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
         })
     }
 
+    config.local = LocalConfig {
+        piper: matches.value_of("piper").map(PathBuf::from),
+        espeak: matches.value_of("espeak").map(PathBuf::from),
+    };
+
     let mut datis = Datis::new(vec![station], config)?;
     datis.start()?;
 
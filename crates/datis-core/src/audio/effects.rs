@@ -0,0 +1,265 @@
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use super::SAMPLE_RATE;
+
+/// Effects that make the synthesized speech sound like it has been transmitted over the radio.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RadioEffects {
+    /// Whether the effects are applied to all stations (can be overridden per station).
+    pub enabled: bool,
+    /// Limit the speech to the frequency band of a radio (300Hz - 3kHz).
+    pub band_pass: bool,
+    /// Level of the static/hiss relative to full scale (`0.0` disables it).
+    pub noise: f64,
+    /// Reduce the dynamic range of the speech.
+    pub compression: bool,
+    /// Frequency (in Hz) of the short squelch-break tone at the start of the transmission, and
+    /// the squelch tail at its end (`0.0` disables both).
+    pub squelch_tone: f64,
+}
+
+impl Default for RadioEffects {
+    fn default() -> Self {
+        RadioEffects {
+            enabled: false,
+            band_pass: true,
+            noise: 0.02,
+            compression: true,
+            squelch_tone: 1_000.0,
+        }
+    }
+}
+
+const BAND_PASS_LOW: f64 = 300.0;
+const BAND_PASS_HIGH: f64 = 3_000.0;
+const SQUELCH_TONE_MS: usize = 80;
+const SQUELCH_TAIL_MS: usize = 150;
+
+impl RadioEffects {
+    /// Apply the effects to 16kHz mono speech.
+    pub fn apply(&self, samples: &[i16]) -> Vec<i16> {
+        let mut signal: Vec<f64> = samples.iter().map(|s| *s as f64 / 32768.0).collect();
+
+        if self.band_pass {
+            // two cascaded filters each for a steeper roll-off
+            for mut filter in [
+                Biquad::high_pass(BAND_PASS_LOW),
+                Biquad::high_pass(BAND_PASS_LOW),
+                Biquad::low_pass(BAND_PASS_HIGH),
+                Biquad::low_pass(BAND_PASS_HIGH),
+            ] {
+                filter.process(&mut signal);
+            }
+        }
+
+        if self.compression {
+            compress(&mut signal);
+        }
+
+        if self.squelch_tone > 0.0 {
+            let mut tone = tone(self.squelch_tone, ms(SQUELCH_TONE_MS), 0.3);
+            tone.append(&mut signal);
+            signal = tone;
+        }
+
+        let mut noise = Noise::default();
+        if self.noise > 0.0 {
+            for s in &mut signal {
+                *s += noise.next() * self.noise;
+            }
+        }
+
+        if self.squelch_tone > 0.0 {
+            // a short burst of loud static when the carrier drops
+            signal.extend((0..ms(SQUELCH_TAIL_MS)).map(|_| noise.next() * 0.3));
+        }
+
+        signal
+            .into_iter()
+            .map(|s| {
+                (s * 32768.0)
+                    .round()
+                    .clamp(i16::MIN as f64, i16::MAX as f64) as i16
+            })
+            .collect()
+    }
+}
+
+fn ms(ms: usize) -> usize {
+    SAMPLE_RATE as usize * ms / 1000
+}
+
+fn tone(freq: f64, len: usize, level: f64) -> Vec<f64> {
+    (0..len)
+        .map(|i| (2.0 * PI * freq * i as f64 / SAMPLE_RATE as f64).sin() * level)
+        .collect()
+}
+
+/// Simple feed-forward compressor (-20 dBFS threshold, 4:1 ratio) with make-up gain.
+fn compress(signal: &mut [f64]) {
+    const THRESHOLD_DB: f64 = -20.0;
+    const RATIO: f64 = 4.0;
+    const MAKEUP_DB: f64 = 6.0;
+
+    let attack = (-1.0 / (0.005 * SAMPLE_RATE as f64)).exp();
+    let release = (-1.0 / (0.050 * SAMPLE_RATE as f64)).exp();
+    let mut envelope = 0.0;
+
+    for s in signal.iter_mut() {
+        let level = s.abs();
+        let coeff = if level > envelope { attack } else { release };
+        envelope = coeff * envelope + (1.0 - coeff) * level;
+
+        let level_db = 20.0 * envelope.max(1e-9).log10();
+        let gain_db = if level_db > THRESHOLD_DB {
+            (THRESHOLD_DB - level_db) * (1.0 - 1.0 / RATIO)
+        } else {
+            0.0
+        };
+        *s *= 10f64.powf((gain_db + MAKEUP_DB) / 20.0);
+    }
+}
+
+/// Second order IIR filter (see https://www.w3.org/TR/audio-eq-cookbook/).
+struct Biquad {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+}
+
+impl Biquad {
+    fn high_pass(freq: f64) -> Self {
+        let (cos, alpha) = Self::params(freq);
+        Self::normalized(
+            (1.0 + cos) / 2.0,
+            -(1.0 + cos),
+            (1.0 + cos) / 2.0,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        )
+    }
+
+    fn low_pass(freq: f64) -> Self {
+        let (cos, alpha) = Self::params(freq);
+        Self::normalized(
+            (1.0 - cos) / 2.0,
+            1.0 - cos,
+            (1.0 - cos) / 2.0,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        )
+    }
+
+    fn params(freq: f64) -> (f64, f64) {
+        let w0 = 2.0 * PI * freq / SAMPLE_RATE as f64;
+        // Q of 1/sqrt(2) for a flat pass band
+        (w0.cos(), w0.sin() / (2.0 * std::f64::consts::FRAC_1_SQRT_2))
+    }
+
+    fn normalized(b0: f64, b1: f64, b2: f64, a0: f64, a1: f64, a2: f64) -> Self {
+        Biquad {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: a1 / a0,
+            a2: a2 / a0,
+        }
+    }
+
+    fn process(&mut self, signal: &mut [f64]) {
+        let (mut x1, mut x2, mut y1, mut y2) = (0.0, 0.0, 0.0, 0.0);
+        for s in signal.iter_mut() {
+            let x = *s;
+            let y = self.b0 * x + self.b1 * x1 + self.b2 * x2 - self.a1 * y1 - self.a2 * y2;
+            x2 = x1;
+            x1 = x;
+            y2 = y1;
+            y1 = y;
+            *s = y;
+        }
+    }
+}
+
+/// Deterministic white noise in the range of -1.0 to 1.0 (xorshift).
+struct Noise(u32);
+
+impl Default for Noise {
+    fn default() -> Self {
+        Noise(0x2545_f491)
+    }
+}
+
+impl Noise {
+    fn next(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0 as f64 / u32::MAX as f64 * 2.0 - 1.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn rms(signal: &[f64]) -> f64 {
+        (signal.iter().map(|s| s * s).sum::<f64>() / signal.len() as f64).sqrt()
+    }
+
+    #[test]
+    fn test_band_pass() {
+        let len = SAMPLE_RATE as usize;
+        let filter = |freq: f64| {
+            let mut signal = tone(freq, len, 0.5);
+            for mut filter in [
+                Biquad::high_pass(BAND_PASS_LOW),
+                Biquad::low_pass(BAND_PASS_HIGH),
+            ] {
+                filter.process(&mut signal);
+            }
+            // ignore the settling of the filter
+            rms(&signal[len / 2..]) / rms(&tone(freq, len, 0.5))
+        };
+
+        assert!(filter(1_000.0) > 0.9);
+        assert!(filter(50.0) < 0.1);
+        assert!(filter(7_000.0) < 0.2);
+    }
+
+    #[test]
+    fn test_squelch() {
+        let effects = RadioEffects {
+            enabled: true,
+            band_pass: false,
+            noise: 0.0,
+            compression: false,
+            squelch_tone: 1_000.0,
+        };
+        let out = effects.apply(&[0; 320]);
+        assert_eq!(out.len(), ms(SQUELCH_TONE_MS) + 320 + ms(SQUELCH_TAIL_MS));
+        // the speech itself is left untouched
+        let speech = &out[ms(SQUELCH_TONE_MS)..ms(SQUELCH_TONE_MS) + 320];
+        assert_eq!(speech, &[0; 320][..]);
+    }
+
+    #[test]
+    fn test_disabled_effects_are_noop() {
+        let effects = RadioEffects {
+            enabled: true,
+            band_pass: false,
+            noise: 0.0,
+            compression: false,
+            squelch_tone: 0.0,
+        };
+        assert_eq!(effects.apply(&[1, -2, 3]), vec![1, -2, 3]);
+    }
+}
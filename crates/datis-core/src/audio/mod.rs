@@ -0,0 +1,466 @@
+use std::io::Cursor;
+
+use audiopus::coder::{Decoder, Encoder};
+use audiopus::{Application, Channels, SampleRate};
+use ogg::reading::PacketReader;
+
+pub use self::effects::RadioEffects;
+
+mod effects;
+
+/// The sample rate of the audio sent to SRS.
+pub const SAMPLE_RATE: u32 = 16_000;
+
+/// Samples per 20ms Opus frame (mono).
+const MONO_20MS: usize = SAMPLE_RATE as usize * 20 / 1000;
+
+/// Speech is normalized to this RMS level (-20 dBFS) ...
+const TARGET_RMS: f64 = 0.1 * i16::MAX as f64;
+/// ... without exceeding this peak level (-1 dBFS) ...
+const MAX_PEAK: f64 = 0.89 * i16::MAX as f64;
+/// ... and without amplifying (mostly silent) audio by more than this factor.
+const MAX_GAIN: f64 = 10.0;
+
+/// The format of the audio returned by a TTS provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    /// Raw signed 16 bit little endian PCM (interleaved if more than one channel).
+    Pcm {
+        sample_rate: u32,
+        channels: u16,
+    },
+    /// A WAV file containing 16 bit PCM.
+    Wav,
+    Mp3,
+    /// Opus packets in an OGG container.
+    OggOpus,
+}
+
+/// Decoded 16 bit PCM audio (interleaved if more than one channel).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pcm {
+    pub samples: Vec<i16>,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+/// Decode the audio `data` returned by a TTS provider, resample it to 16kHz mono and normalize
+/// its loudness.
+pub async fn prepare(data: Vec<u8>, format: AudioFormat) -> Result<Vec<i16>, anyhow::Error> {
+    tokio::task::spawn_blocking(move || {
+        let pcm = decode(&data, format)?.into_mono().resample(SAMPLE_RATE);
+        let mut samples = pcm.samples;
+        normalize(&mut samples);
+        Ok(samples)
+    })
+    .await?
+}
+
+/// Apply the radio `effects` (if any) to the prepared 16kHz mono speech and encode it into the
+/// 20ms Opus frames sent to SRS.
+pub async fn encode(
+    mut samples: Vec<i16>,
+    effects: Option<RadioEffects>,
+) -> Result<Vec<Vec<u8>>, anyhow::Error> {
+    tokio::task::spawn_blocking(move || {
+        if let Some(effects) = effects {
+            samples = effects.apply(&samples);
+        }
+        encode_opus(&samples)
+    })
+    .await?
+}
+
+pub fn decode(data: &[u8], format: AudioFormat) -> Result<Pcm, anyhow::Error> {
+    match format {
+        AudioFormat::Pcm {
+            sample_rate,
+            channels,
+        } => Ok(Pcm {
+            samples: vector_i16(data),
+            sample_rate,
+            channels,
+        }),
+        AudioFormat::Wav => decode_wav(data),
+        AudioFormat::Mp3 => decode_mp3(data),
+        AudioFormat::OggOpus => decode_ogg_opus(data),
+    }
+}
+
+impl Pcm {
+    /// Mix all channels down to a single one.
+    pub fn into_mono(self) -> Pcm {
+        if self.channels <= 1 {
+            return self;
+        }
+
+        let channels = self.channels as usize;
+        Pcm {
+            samples: self
+                .samples
+                .chunks_exact(channels)
+                .map(|frame| {
+                    (frame.iter().map(|s| *s as i32).sum::<i32>() / channels as i32) as i16
+                })
+                .collect(),
+            sample_rate: self.sample_rate,
+            channels: 1,
+        }
+    }
+
+    /// Linear resampling of mono audio.
+    pub fn resample(self, sample_rate: u32) -> Pcm {
+        debug_assert_eq!(self.channels, 1);
+        if self.sample_rate == sample_rate || self.samples.is_empty() {
+            return Pcm {
+                sample_rate,
+                ..self
+            };
+        }
+
+        let samples = &self.samples;
+        let len = (samples.len() as u64 * sample_rate as u64 / self.sample_rate as u64) as usize;
+        let step = self.sample_rate as f64 / sample_rate as f64;
+        Pcm {
+            samples: (0..len)
+                .map(|i| {
+                    let pos = i as f64 * step;
+                    let ix = pos as usize;
+                    let a = samples[ix] as f64;
+                    let b = samples.get(ix + 1).copied().unwrap_or(samples[ix]) as f64;
+                    (a + (b - a) * pos.fract()).round() as i16
+                })
+                .collect(),
+            sample_rate,
+            channels: 1,
+        }
+    }
+}
+
+/// Adjust the gain of the given mono 16kHz speech to get a consistent loudness across the
+/// different TTS providers and voices.
+pub fn normalize(samples: &mut [i16]) {
+    // only consider the parts that actually contain speech (above -50 dBFS) to not let pauses
+    // drag down the measured loudness
+    let gate = 0.003 * i16::MAX as f64;
+    let (sum, count) = samples
+        .chunks(MONO_20MS)
+        .map(|window| window.iter().map(|s| (*s as f64).powi(2)).sum::<f64>() / window.len() as f64)
+        .filter(|mean_square| mean_square.sqrt() > gate)
+        .fold((0.0, 0), |(sum, count), ms| (sum + ms, count + 1));
+    if count == 0 {
+        return;
+    }
+
+    let rms = (sum / count as f64).sqrt();
+    let peak = samples
+        .iter()
+        .map(|s| (*s as i32).unsigned_abs())
+        .max()
+        .unwrap_or(0) as f64;
+    let gain = (TARGET_RMS / rms).min(MAX_PEAK / peak).min(MAX_GAIN);
+
+    for sample in samples.iter_mut() {
+        *sample = (*sample as f64 * gain)
+            .round()
+            .clamp(i16::MIN as f64, i16::MAX as f64) as i16;
+    }
+}
+
+/// Change the volume of the given samples by `db` (clipping at full scale).
+pub fn amplify(samples: &mut [i16], db: f64) {
+    let gain = 10f64.powf(db / 20.0);
+    for sample in samples.iter_mut() {
+        *sample = (*sample as f64 * gain)
+            .round()
+            .clamp(i16::MIN as f64, i16::MAX as f64) as i16;
+    }
+}
+
+/// Encode mono 16kHz PCM into 20ms Opus frames.
+pub fn encode_opus(samples: &[i16]) -> Result<Vec<Vec<u8>>, anyhow::Error> {
+    let enc = Encoder::new(SampleRate::Hz16000, Channels::Mono, Application::Voip)?;
+    let mut output = [0; 256];
+    let mut frames = Vec::with_capacity(samples.len() / MONO_20MS + 1);
+
+    for chunk in samples.chunks(MONO_20MS) {
+        let len = if chunk.len() == MONO_20MS {
+            enc.encode(chunk, &mut output)?
+        } else {
+            // pad the last frame with silence
+            let mut frame = [0; MONO_20MS];
+            frame[..chunk.len()].copy_from_slice(chunk);
+            enc.encode(&frame, &mut output)?
+        };
+        frames.push(output[..len].to_vec());
+    }
+
+    Ok(frames)
+}
+
+fn vector_i16(byte_stream: &[u8]) -> Vec<i16> {
+    byte_stream
+        .chunks_exact(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]))
+        .collect()
+}
+
+fn decode_wav(wav: &[u8]) -> Result<Pcm, anyhow::Error> {
+    if wav.len() < 12 || &wav[0..4] != b"RIFF" || &wav[8..12] != b"WAVE" {
+        return Err(anyhow!("audio is not a WAV file"));
+    }
+
+    let mut format = None;
+    let mut pos = 12;
+    while pos + 8 <= wav.len() {
+        let id = &wav[pos..pos + 4];
+        let size = u32::from_le_bytes([wav[pos + 4], wav[pos + 5], wav[pos + 6], wav[pos + 7]]);
+        let body = pos + 8;
+
+        match id {
+            b"fmt " if body + 16 <= wav.len() => {
+                let channels = u16::from_le_bytes([wav[body + 2], wav[body + 3]]);
+                let sample_rate = u32::from_le_bytes([
+                    wav[body + 4],
+                    wav[body + 5],
+                    wav[body + 6],
+                    wav[body + 7],
+                ]);
+                let bits = u16::from_le_bytes([wav[body + 14], wav[body + 15]]);
+                if bits != 16 {
+                    return Err(anyhow!("unsupported WAV format ({} bits)", bits));
+                }
+                format = Some((sample_rate, channels));
+            }
+            b"data" => {
+                let (sample_rate, channels) =
+                    format.ok_or_else(|| anyhow!("WAV file is missing its format"))?;
+                // when streamed, the size of the data is not known upfront and usually set to its
+                // max value
+                let end = body.saturating_add(size as usize).min(wav.len());
+                return Ok(Pcm {
+                    samples: vector_i16(&wav[body..end]),
+                    sample_rate,
+                    channels,
+                });
+            }
+            _ => {}
+        }
+
+        // chunks are padded to an even size
+        pos = body.saturating_add(size as usize + (size as usize & 1));
+    }
+
+    Err(anyhow!("WAV file does not contain any data"))
+}
+
+fn decode_mp3(data: &[u8]) -> Result<Pcm, anyhow::Error> {
+    let mut decoder = minimp3::Decoder::new(Cursor::new(data));
+    let mut pcm = Pcm {
+        samples: Vec::new(),
+        sample_rate: 0,
+        channels: 0,
+    };
+
+    loop {
+        match decoder.next_frame() {
+            Ok(frame) => {
+                pcm.sample_rate = frame.sample_rate as u32;
+                pcm.channels = frame.channels as u16;
+                pcm.samples.extend_from_slice(&frame.data);
+            }
+            Err(minimp3::Error::Eof) => break,
+            Err(minimp3::Error::SkippedData) => continue,
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    if pcm.channels == 0 {
+        return Err(anyhow!("MP3 does not contain any audio"));
+    }
+
+    Ok(pcm)
+}
+
+fn decode_ogg_opus(data: &[u8]) -> Result<Pcm, anyhow::Error> {
+    let mut packets = PacketReader::new(Cursor::new(data));
+
+    // the first packet is the Opus identification header
+    let head = packets
+        .read_packet()?
+        .ok_or_else(|| anyhow!("OGG stream is empty"))?;
+    if head.data.len() < 19 || &head.data[0..8] != b"OpusHead" {
+        return Err(anyhow!("OGG stream does not contain Opus audio"));
+    }
+    let (channels, opus_channels) = match head.data[9] {
+        1 => (1, Channels::Mono),
+        2 => (2, Channels::Stereo),
+        n => return Err(anyhow!("unsupported number of Opus channels ({})", n)),
+    };
+    // the number of samples (at 48kHz) to discard from the decoder output
+    let pre_skip = u16::from_le_bytes([head.data[10], head.data[11]]) as usize;
+
+    // Opus can decode to any of its supported sample rates, regardless of the one it has been
+    // encoded with
+    let mut decoder = Decoder::new(SampleRate::Hz16000, opus_channels)?;
+    // max. Opus frame duration is 120ms
+    let mut output = vec![0i16; SAMPLE_RATE as usize * 120 / 1000 * channels as usize];
+    let mut samples = Vec::new();
+
+    while let Some(packet) = packets.read_packet()? {
+        // skip the comment header
+        if packet.data.starts_with(b"OpusTags") {
+            continue;
+        }
+        let len = decoder.decode(Some(packet.data.as_slice()), output.as_mut_slice(), false)?;
+        samples.extend_from_slice(&output[..len * channels as usize]);
+    }
+
+    let skip = (pre_skip * SAMPLE_RATE as usize / 48_000 * channels as usize).min(samples.len());
+    samples.drain(..skip);
+
+    Ok(Pcm {
+        samples,
+        sample_rate: SAMPLE_RATE,
+        channels: channels as u16,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn sine(len: usize, amplitude: f64) -> Vec<i16> {
+        (0..len)
+            .map(|i| {
+                let t = i as f64 / SAMPLE_RATE as f64;
+                ((t * 440.0 * 2.0 * std::f64::consts::PI).sin() * amplitude) as i16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_decode_wav() {
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&u32::MAX.to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&2u16.to_le_bytes()); // stereo
+        wav.extend_from_slice(&22050u32.to_le_bytes());
+        wav.extend_from_slice(&88200u32.to_le_bytes());
+        wav.extend_from_slice(&4u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&u32::MAX.to_le_bytes());
+        wav.extend_from_slice(&[1, 0, 3, 0]);
+
+        let pcm = decode(&wav, AudioFormat::Wav).unwrap();
+        assert_eq!(
+            pcm,
+            Pcm {
+                samples: vec![1, 3],
+                sample_rate: 22050,
+                channels: 2,
+            }
+        );
+        assert_eq!(pcm.into_mono().samples, vec![2]);
+    }
+
+    #[test]
+    fn test_resample() {
+        let pcm = |samples: Vec<i16>, sample_rate| Pcm {
+            samples,
+            sample_rate,
+            channels: 1,
+        };
+        assert_eq!(
+            pcm(vec![0, 100, 200, 300], 32_000).resample(16_000),
+            pcm(vec![0, 200], 16_000)
+        );
+        assert_eq!(
+            pcm(vec![0, 100], 8_000).resample(16_000),
+            pcm(vec![0, 50, 100, 100], 16_000)
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut quiet = sine(SAMPLE_RATE as usize, 1000.0);
+        let mut loud = sine(SAMPLE_RATE as usize, 30000.0);
+        normalize(&mut quiet);
+        normalize(&mut loud);
+
+        let peak = |s: &[i16]| s.iter().map(|s| s.unsigned_abs()).max().unwrap();
+        // a sine with an RMS of -20 dBFS has a peak of about 4634
+        assert!((4500..4700).contains(&peak(&quiet)), "{}", peak(&quiet));
+        assert!((4500..4700).contains(&peak(&loud)), "{}", peak(&loud));
+
+        // silence stays silent
+        let mut silence = vec![0; 320];
+        normalize(&mut silence);
+        assert_eq!(silence, vec![0; 320]);
+    }
+
+    #[test]
+    fn test_amplify() {
+        let mut samples = vec![1000, -1000, 30000];
+        amplify(&mut samples, 6.0);
+        assert_eq!(samples, vec![1995, -1995, i16::MAX]);
+
+        amplify(&mut samples, -6.0);
+        assert_eq!(samples, vec![1000, -1000, 16422]);
+    }
+
+    #[test]
+    fn test_ogg_opus_roundtrip() {
+        use ogg::writing::{PacketWriteEndInfo, PacketWriter};
+
+        let samples = sine(MONO_20MS * 10, 10000.0);
+        let frames = encode_opus(&samples).unwrap();
+        assert_eq!(frames.len(), 10);
+
+        let mut head = b"OpusHead".to_vec();
+        head.push(1); // version
+        head.push(1); // channels
+        head.extend_from_slice(&0u16.to_le_bytes()); // pre-skip
+        head.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+        head.extend_from_slice(&0u16.to_le_bytes()); // output gain
+        head.push(0); // channel mapping
+
+        let mut ogg = Vec::new();
+        let mut writer = PacketWriter::new(&mut ogg);
+        writer
+            .write_packet(head, 1, PacketWriteEndInfo::EndPage, 0)
+            .unwrap();
+        writer
+            .write_packet(b"OpusTags".to_vec(), 1, PacketWriteEndInfo::EndPage, 0)
+            .unwrap();
+        let count = frames.len();
+        for (i, frame) in frames.into_iter().enumerate() {
+            let end = if i + 1 == count {
+                PacketWriteEndInfo::EndStream
+            } else {
+                PacketWriteEndInfo::NormalPacket
+            };
+            writer
+                .write_packet(frame, 1, end, (i as u64 + 1) * 960)
+                .unwrap();
+        }
+        drop(writer);
+
+        let pcm = decode(&ogg, AudioFormat::OggOpus).unwrap();
+        assert_eq!(pcm.sample_rate, SAMPLE_RATE);
+        assert_eq!(pcm.channels, 1);
+        assert_eq!(pcm.samples.len(), samples.len());
+    }
+
+    #[test]
+    fn test_encode_pads_last_frame() {
+        let frames = encode_opus(&sine(MONO_20MS * 2 + 1, 10000.0)).unwrap();
+        assert_eq!(frames.len(), 3);
+    }
+}
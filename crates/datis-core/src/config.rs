@@ -1,47 +1,92 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
 
+use crate::audio::RadioEffects;
 use crate::tts::TextToSpeechProvider;
+use crate::tts::prosody::Prosody;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     #[serde(default)]
     pub default_voice: TextToSpeechProvider,
+    /// Voices tried in order when the voice of a station fails (after the station's own fallback
+    /// voices).
+    #[serde(default)]
+    pub fallback_voices: Vec<TextToSpeechProvider>,
+    /// Time (in seconds) a failed voice is skipped in favor of the fallback voices.
+    #[serde(default = "default_tts_cooldown")]
+    pub tts_cooldown: u64,
+    /// Default speaking rate, pitch and volume for stations that do not set their own.
+    #[serde(default)]
+    pub prosody: Prosody,
     pub gcloud: Option<GcloudConfig>,
     pub aws: Option<AwsConfig>,
     pub azure: Option<AzureConfig>,
+    /// Paths of the offline TTS engines (defaults to looking them up in `PATH`).
+    #[serde(default)]
+    pub local: LocalConfig,
     #[serde(default = "default_srs_port")]
     pub srs_port: u16,
     #[serde(default)]
     pub debug: bool,
+    /// Whether to keep a history of all issued reports (`atis-reports-history.jsonl`).
+    #[serde(default)]
+    pub export_history: bool,
+    /// Max size (in MB) of the on-disk cache of synthesized reports, `0` disables the cache.
+    #[serde(default = "default_tts_cache_size")]
+    pub tts_cache_size: u64,
+    /// Address of the optional status HTTP server, e.g. `127.0.0.1:8080` (requires the `http`
+    /// feature).
+    #[serde(default)]
+    pub status_addr: Option<SocketAddr>,
+    /// Radio effects applied to the audio of all stations that do not override it.
+    #[serde(default)]
+    pub radio_effects: RadioEffects,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GcloudConfig {
     pub key: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AwsConfig {
     pub key: String,
     pub secret: String,
     pub region: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AzureConfig {
     pub key: String,
     pub region: String,
 }
 
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct LocalConfig {
+    pub piper: Option<PathBuf>,
+    pub espeak: Option<PathBuf>,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config {
             default_voice: TextToSpeechProvider::default(),
+            fallback_voices: Vec::new(),
+            tts_cooldown: default_tts_cooldown(),
+            prosody: Prosody::default(),
             gcloud: None,
             aws: None,
             azure: None,
+            local: LocalConfig::default(),
             srs_port: default_srs_port(),
             debug: false,
+            export_history: false,
+            tts_cache_size: default_tts_cache_size(),
+            status_addr: None,
+            radio_effects: RadioEffects::default(),
         }
     }
 }
@@ -49,3 +94,11 @@ impl Default for Config {
 fn default_srs_port() -> u16 {
     5002
 }
+
+fn default_tts_cooldown() -> u64 {
+    300
+}
+
+fn default_tts_cache_size() -> u64 {
+    100
+}
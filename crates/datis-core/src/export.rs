@@ -1,36 +1,265 @@
 use std::collections::HashMap;
-use std::fs::File;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 use std::{error, fmt};
 
 use futures::lock::Mutex;
+use serde::Serialize;
+use uom::si::angle::degree;
+use uom::si::length::{foot, meter};
+use uom::si::pressure::{hectopascal, inch_of_mercury, millimeter_of_mercury};
+use uom::si::thermodynamic_temperature::degree_celsius;
+use uom::si::velocity::knot;
+
+use crate::station::{LatLngPosition, Report, Station, Transmitter, runway_heading};
+use crate::units::UnitProfile;
+
+/// Once the history file exceeds this size, it is moved to `*.1.jsonl` and a new one is started.
+const MAX_HISTORY_SIZE: u64 = 10 * 1024 * 1024; // 10MB
 
 #[derive(Clone)]
-pub struct ReportExporter(Arc<Mutex<ReportExporterInner>>);
+pub struct ReportExporter {
+    inner: Arc<Mutex<ReportExporterInner>>,
+    /// Held while writing the files (on a blocking thread) and contains the version of the reports
+    /// last written, so an older snapshot of the reports never overwrites a newer one.
+    written: Arc<std::sync::Mutex<u64>>,
+}
 
 pub struct ReportExporterInner {
     path: PathBuf,
-    reports: HashMap<String, String>,
+    history_path: Option<PathBuf>,
+    reports: HashMap<String, ExportedReport>,
+    /// Incremented on every change of `reports`.
+    version: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedReport {
+    pub name: String,
+    pub freq: u64,
+    pub transmitter: &'static str,
+    /// The unit profile the textual report is given in (`mixed`, `faa`, `icao` or `soviet`).
+    pub units: &'static str,
+    pub information_letter: Option<&'static str>,
+    pub active_runway: Option<String>,
+    pub wind_dir_deg: Option<f64>,
+    pub wind_speed_kt: Option<f64>,
+    pub wind_gust_kt: Option<f64>,
+    pub temperature_celsius: Option<f64>,
+    pub dew_point_celsius: Option<f64>,
+    pub qnh_hpa: Option<f64>,
+    pub qnh_inhg: Option<f64>,
+    pub qfe_hpa: Option<f64>,
+    pub qfe_inhg: Option<f64>,
+    pub qnh_mmhg: Option<f64>,
+    pub qfe_mmhg: Option<f64>,
+    pub ceiling_ft: Option<i32>,
+    pub ceiling_coverage: Option<String>,
+    pub visibility_m: Option<i32>,
+    pub pressure_altitude_ft: Option<i32>,
+    pub density_altitude_ft: Option<i32>,
+    pub winds_aloft: Vec<ExportedWindAloft>,
+    /// The wind components for each runway of an airfield.
+    pub runway_winds: Vec<ExportedRunwayWind>,
+    pub position: LatLngPosition,
+    /// Unix timestamp (in seconds) of when the report has been generated.
+    pub timestamp: u64,
+    pub report: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedWindAloft {
+    pub alt_ft: f64,
+    pub wind_dir_deg: f64,
+    pub wind_speed_kt: f64,
+    pub temperature_celsius: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedRunwayWind {
+    pub runway: String,
+    /// Negative for a tailwind.
+    pub headwind_kt: f64,
+    /// Positive if coming from the right.
+    pub crosswind_kt: f64,
 }
 
 impl ReportExporter {
-    pub fn new(path: impl AsRef<Path>) -> Self {
-        ReportExporter(Arc::new(Mutex::new(ReportExporterInner {
-            path: path.as_ref().to_path_buf(),
-            reports: HashMap::new(),
-        })))
+    /// Exports the latest report of each station to `path`. If `history_path` is set, every
+    /// newly issued report is additionally appended as a JSON line to the file at `history_path`.
+    pub fn new(path: impl AsRef<Path>, history_path: Option<PathBuf>) -> Self {
+        ReportExporter {
+            inner: Arc::new(Mutex::new(ReportExporterInner {
+                path: path.as_ref().to_path_buf(),
+                history_path,
+                reports: HashMap::new(),
+                version: 0,
+            })),
+            written: Arc::new(std::sync::Mutex::new(0)),
+        }
+    }
+
+    pub async fn export(
+        &self,
+        station: &Station,
+        report: &Report,
+    ) -> Result<(), ReportExporterError> {
+        let exported = ExportedReport::new(station, report);
+
+        // only hold the lock to update the reports, the files are written on a blocking thread
+        let (path, history, reports, version) = {
+            let mut inner = self.inner.lock().await;
+            let is_new = inner
+                .reports
+                .get(&station.name)
+                .map(|previous| previous.report != exported.report)
+                .unwrap_or(true);
+            let history = inner
+                .history_path
+                .clone()
+                .filter(|_| is_new)
+                .map(|path| (path, exported.clone()));
+
+            inner.reports.insert(station.name.clone(), exported);
+            inner.version += 1;
+            (
+                inner.path.clone(),
+                history,
+                inner.reports.clone(),
+                inner.version,
+            )
+        };
+
+        let written = self.written.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut written = written.lock().unwrap_or_else(|err| err.into_inner());
+            if let Some((history_path, exported)) = history {
+                append_history(&history_path, &exported)?;
+            }
+            if version < *written {
+                // a newer version has already been written
+                return Ok(());
+            }
+
+            // write to a temporary file first to never expose a partially written file to readers
+            let tmp_path = path.with_extension("json.tmp");
+            let mut file = File::create(&tmp_path)?;
+            serde_json::to_writer_pretty(&mut file, &reports)?;
+            drop(file);
+            fs::rename(&tmp_path, &path)?;
+            *written = version;
+
+            Ok(())
+        })
+        .await
+        .map_err(std::io::Error::other)?
     }
+}
 
-    pub async fn export(&self, name: &str, report: String) -> Result<(), ReportExporterError> {
-        let mut inner = self.0.lock().await;
-        inner.reports.insert(name.to_string(), report);
+impl ExportedReport {
+    pub fn new(station: &Station, report: &Report) -> Self {
+        let details = report.details.as_ref();
+        let weather = details.map(|d| &d.weather);
 
-        let mut file = File::create(&inner.path)?;
-        serde_json::to_writer_pretty(&mut file, &inner.reports)?;
+        let ceiling = details.and_then(|d| d.weather.get_ceiling(d.alt));
+        let qnh = details.map(|d| d.weather.get_qnh(d.alt));
+        let qfe = weather.map(|w| w.get_qfe());
+
+        ExportedReport {
+            name: station.name.clone(),
+            freq: station.freq,
+            transmitter: match station.transmitter {
+                Transmitter::Airfield(_) => "airfield",
+                Transmitter::Carrier(_) => "carrier",
+                Transmitter::Custom(_) => "custom",
+                Transmitter::Weather(_) => "weather",
+            },
+            units: match &station.transmitter {
+                Transmitter::Airfield(airfield) => airfield.units,
+                Transmitter::Weather(weather) => weather.units,
+                Transmitter::Carrier(_) | Transmitter::Custom(_) => UnitProfile::Mixed,
+            }
+            .as_str(),
+            information_letter: details.and_then(|d| d.information_letter),
+            active_runway: details.and_then(|d| d.active_runway.clone()),
+            wind_dir_deg: weather.map(|w| w.wind_dir.get::<degree>().round()),
+            wind_speed_kt: weather.map(|w| w.wind_speed.get::<knot>().round()),
+            wind_gust_kt: weather
+                .and_then(|w| w.get_wind_gust())
+                .map(|v| v.get::<knot>().round()),
+            temperature_celsius: weather.map(|w| w.temperature.get::<degree_celsius>()),
+            dew_point_celsius: details.map(|d| {
+                d.weather
+                    .get_dew_point(d.alt)
+                    .get::<degree_celsius>()
+                    .round()
+            }),
+            qnh_hpa: qnh.map(|p| p.get::<hectopascal>().round()),
+            qnh_inhg: qnh.map(|p| (p.get::<inch_of_mercury>() * 100.0).round() / 100.0),
+            qfe_hpa: qfe.map(|p| p.get::<hectopascal>().round()),
+            qfe_inhg: qfe.map(|p| (p.get::<inch_of_mercury>() * 100.0).round() / 100.0),
+            qnh_mmhg: qnh.map(|p| p.get::<millimeter_of_mercury>().round()),
+            qfe_mmhg: qfe.map(|p| p.get::<millimeter_of_mercury>().round()),
+            ceiling_ft: ceiling.as_ref().map(|c| c.alt.get::<foot>()),
+            ceiling_coverage: ceiling.as_ref().map(|c| c.coverage.to_string()),
+            visibility_m: details
+                .and_then(|d| d.weather.get_visibility(d.alt))
+                .map(|v| v.get::<meter>()),
+            pressure_altitude_ft: weather.map(|w| w.get_pressure_altitude().get::<foot>()),
+            density_altitude_ft: weather.map(|w| w.get_density_altitude().get::<foot>()),
+            winds_aloft: weather
+                .map(|w| {
+                    w.winds_aloft
+                        .iter()
+                        .map(|wind| ExportedWindAloft {
+                            alt_ft: wind.alt.get::<foot>().round(),
+                            wind_dir_deg: wind.wind_dir.get::<degree>().round(),
+                            wind_speed_kt: wind.wind_speed.get::<knot>().round(),
+                            temperature_celsius: wind.temperature.get::<degree_celsius>(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            runway_winds: match (&station.transmitter, weather) {
+                (Transmitter::Airfield(airfield), Some(weather)) => airfield
+                    .runways
+                    .iter()
+                    .flat_map(|rwy| rwy.designators())
+                    .filter_map(|rwy| {
+                        let (headwind, crosswind) =
+                            weather.get_wind_components(runway_heading(rwy)?);
+                        Some(ExportedRunwayWind {
+                            runway: rwy.to_string(),
+                            headwind_kt: headwind.get::<knot>().round(),
+                            crosswind_kt: crosswind.get::<knot>().round(),
+                        })
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            },
+            position: report.position.clone(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            report: report.textual.clone(),
+        }
+    }
+}
 
-        Ok(())
+fn append_history(path: &Path, report: &ExportedReport) -> Result<(), ReportExporterError> {
+    if fs::metadata(path).map(|m| m.len()).unwrap_or(0) > MAX_HISTORY_SIZE {
+        fs::rename(path, path.with_extension("1.jsonl"))?;
     }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    serde_json::to_writer(&mut file, report)?;
+    file.write_all(b"\n")?;
+
+    Ok(())
 }
 
 #[derive(Debug)]
@@ -72,3 +301,94 @@ impl From<serde_json::Error> for ReportExporterError {
         ReportExporterError::Json(err)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::locale::Locale;
+    use crate::station::{Airfield, Coalition, MissionInterface, Position, Runway, RunwayRules};
+    use crate::tts::TextToSpeechProvider;
+    use crate::tts::prosody::Prosody;
+
+    #[tokio::test]
+    async fn test_exported_airfield_report() {
+        let station = Station {
+            name: String::from("Kutaisi"),
+            freq: 251_000_000,
+            tts: TextToSpeechProvider::default(),
+            fallback_tts: Vec::new(),
+            prosody: Prosody::default(),
+            transmitter: Transmitter::Airfield(Airfield {
+                name: String::from("Kutaisi"),
+                icao: None,
+                position: Position::default(),
+                runways: vec![Runway::new("04", "22")],
+                traffic_freq: None,
+                info_ltr_offset: 0,
+                info_ltr_override: None,
+                active_rwy_override: None,
+                no_hpa: false,
+                no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+            }),
+            ipc: MissionInterface::Static,
+            coalitions: vec![Coalition::Blue],
+            template: None,
+            radio_effects: None,
+        };
+
+        let report = station
+            .generate_report(0, None, None)
+            .await
+            .unwrap()
+            .unwrap();
+        let exported = ExportedReport::new(&station, &report);
+
+        assert_eq!(exported.transmitter, "airfield");
+        assert_eq!(exported.units, "mixed");
+        assert_eq!(exported.information_letter, Some("Alpha"));
+        assert_eq!(exported.active_runway.as_deref(), Some("04"));
+        assert_eq!(exported.wind_dir_deg, Some(330.0));
+        assert_eq!(exported.wind_speed_kt, Some(5.0));
+        assert_eq!(exported.wind_gust_kt, None);
+        assert_eq!(
+            exported
+                .runway_winds
+                .iter()
+                .map(|w| (w.runway.as_str(), w.headwind_kt, w.crosswind_kt))
+                .collect::<Vec<_>>(),
+            vec![("04", 2.0, -5.0), ("22", -2.0, 5.0)]
+        );
+        assert_eq!(exported.qnh_hpa, Some(1015.0));
+        assert_eq!(exported.qnh_inhg, Some(29.97));
+        assert_eq!(exported.qnh_mmhg, Some(761.0));
+        assert_eq!(exported.ceiling_ft, None);
+        assert_eq!(exported.visibility_m, None);
+        assert_eq!(exported.dew_point_celsius, Some(8.0));
+        assert_eq!(exported.density_altitude_ft, Some(774));
+        assert_eq!(exported.report, report.textual);
+
+        let dir = std::env::temp_dir().join(format!("datis-export-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let exporter =
+            ReportExporter::new(dir.join("reports.json"), Some(dir.join("history.jsonl")));
+        exporter.export(&station, &report).await.unwrap();
+        exporter.export(&station, &report).await.unwrap();
+        let reports: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.join("reports.json")).unwrap()).unwrap();
+        assert_eq!(reports["Kutaisi"]["report"], report.textual.as_str());
+        // unchanged reports are only added to the history once
+        let history = fs::read_to_string(dir.join("history.jsonl")).unwrap();
+        assert_eq!(history.lines().count(), 1);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -2,19 +2,48 @@ use std::collections::HashMap;
 use std::str::FromStr;
 
 use regex::{Regex, RegexBuilder};
+use uom::si::f64::Length;
+use uom::si::length::foot;
 
+use crate::locale::Locale;
+use crate::station::{Coalition, RunwayRules, all_coalitions};
 use crate::tts::TextToSpeechProvider;
+use crate::tts::prosody::Prosody;
+use crate::units::UnitProfile;
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct StationConfig {
     pub name: String,
+    /// The ICAO code of the station (see `ICAO {IDENT}`).
+    pub icao: Option<String>,
     pub atis: u64,
     pub traffic: Option<u64>,
     pub tts: Option<TextToSpeechProvider>,
+    /// Voices tried in order when the voice fails.
+    pub fallback_tts: Vec<TextToSpeechProvider>,
+    /// Speaking rate, pitch and volume of the voice.
+    pub prosody: Prosody,
     pub info_ltr_override: Option<char>,
     pub active_rwy_override: Option<String>,
     pub no_hpa: bool,
     pub no_qfe: bool,
+    pub dew_point: bool,
+    pub density_alt: bool,
+    pub transition_level: bool,
+    /// Transition altitude in feet (defaults to the one of the map).
+    pub transition_alt: Option<u32>,
+    pub runway_rules: RunwayRules,
+    /// The language of the report.
+    pub locale: Locale,
+    /// The units of the report.
+    pub units: UnitProfile,
+    pub crosswind_limit: Option<u32>,
+    pub tailwind_limit: Option<u32>,
+    /// Coalitions set with `COALITION` (`ALL` sets both coalitions).
+    pub coalition: Option<Vec<Coalition>>,
+    pub radio_effects: Option<bool>,
+    /// Name of the template used for the report (see `Template::load`).
+    pub template: Option<String>,
 }
 
 pub fn extract_station_config_from_mission_description(
@@ -65,6 +94,21 @@ pub fn extract_atis_station_config(config: &str) -> Option<StationConfig> {
     let mut active_rwy_override = None;
     let mut no_hpa = false;
     let mut no_qfe = false;
+    let mut dew_point = false;
+    let mut density_alt = false;
+    let mut runway_rules = RunwayRules::default();
+    let mut locale = Locale::default();
+    let mut units = UnitProfile::default();
+    let mut transition_level = false;
+    let mut transition_alt = None;
+    let mut crosswind_limit = None;
+    let mut template = None;
+    let mut tailwind_limit = None;
+    let mut coalition = None;
+    let mut radio_effects = None;
+    let mut fallback_tts = Vec::new();
+    let mut prosody = Prosody::default();
+    let mut icao = None;
 
     for (option_key, option_value) in options.split(',').filter_map(|t| {
         let t = t.trim();
@@ -83,11 +127,16 @@ pub fn extract_atis_station_config(config: &str) -> Option<StationConfig> {
                     );
                 }
             }
-            "VOICE" => {
-                if let Ok(tts_provider) = TextToSpeechProvider::from_str(option_value) {
-                    tts = Some(tts_provider);
-                } else {
-                    log::warn!("Unable to extract Voice from {}", option_value);
+            "VOICE" => match TextToSpeechProvider::from_str(option_value) {
+                Ok(tts_provider) => tts = Some(tts_provider),
+                Err(err) => log::warn!("Unable to extract Voice from {}: {}", option_value, err),
+            },
+            "FALLBACK" => {
+                fallback_tts = voice_list(option_value);
+            }
+            "RATE" | "PITCH" | "VOLUME" => {
+                if !prosody_option(&mut prosody, option_key, option_value) {
+                    log::warn!("Unsupported ATIS {} option {}", option_key, option_value);
                 }
             }
             "INFO" => {
@@ -107,6 +156,139 @@ pub fn extract_atis_station_config(config: &str) -> Option<StationConfig> {
                     log::warn!("Unsupported ATIS NO option {}", option_value);
                 }
             },
+            "COALITION" => match option_value.to_uppercase().as_str() {
+                "BLUE" => {
+                    coalition = Some(vec![Coalition::Blue]);
+                }
+                "RED" => {
+                    coalition = Some(vec![Coalition::Red]);
+                }
+                "ALL" | "NEUTRAL" => {
+                    coalition = Some(all_coalitions());
+                }
+                _ => {
+                    log::warn!("Unsupported ATIS COALITION option {}", option_value);
+                }
+            },
+            "EFFECTS" => match option_value.to_uppercase().as_str() {
+                "ON" => {
+                    radio_effects = Some(true);
+                }
+                "OFF" => {
+                    radio_effects = Some(false);
+                }
+                _ => {
+                    log::warn!("Unsupported ATIS EFFECTS option {}", option_value);
+                }
+            },
+            "DEWPOINT" => match option_value.to_uppercase().as_str() {
+                "ON" => {
+                    dew_point = true;
+                }
+                "OFF" => {
+                    dew_point = false;
+                }
+                _ => {
+                    log::warn!("Unsupported ATIS DEWPOINT option {}", option_value);
+                }
+            },
+            "DA" => match option_value.to_uppercase().as_str() {
+                "ON" => {
+                    density_alt = true;
+                }
+                "OFF" => {
+                    density_alt = false;
+                }
+                _ => {
+                    log::warn!("Unsupported ATIS DA option {}", option_value);
+                }
+            },
+            "TL" => match option_value.to_uppercase().as_str() {
+                "ON" => {
+                    transition_level = true;
+                }
+                "OFF" => {
+                    transition_level = false;
+                }
+                // e.g. `TL 5000` for a transition altitude of 5000ft
+                value => {
+                    if let Ok(alt) = value.parse::<u32>() {
+                        transition_level = true;
+                        transition_alt = Some(alt);
+                    } else {
+                        log::warn!("Unsupported ATIS TL option {}", option_value);
+                    }
+                }
+            },
+            "CALM" => {
+                // e.g. `CALM 25` or `CALM 25 8` to use runway 25 for winds below 8 knots
+                let mut parts = option_value.split_whitespace();
+                runway_rules.calm_rwy = parts.next().map(str::to_uppercase);
+                if let Some(limit) = parts.next() {
+                    if let Ok(limit) = limit.parse::<u32>() {
+                        runway_rules.calm_wind_limit = Some(limit);
+                    } else {
+                        log::warn!("Unsupported ATIS CALM option {}", option_value);
+                    }
+                }
+            }
+            "HYSTERESIS" => {
+                if let Ok(hysteresis) = option_value.parse::<u32>() {
+                    runway_rules.hysteresis = Some(hysteresis);
+                } else {
+                    log::warn!("Unsupported ATIS HYSTERESIS option {}", option_value);
+                }
+            }
+            "CLOSED" => {
+                runway_rules.closed = runway_list(option_value);
+            }
+            "ARRIVAL" => {
+                runway_rules.arrival = runway_list(option_value);
+            }
+            "DEPARTURE" => {
+                runway_rules.departure = runway_list(option_value);
+            }
+            "LANG" => match Locale::from_str(option_value) {
+                Ok(lang) => {
+                    locale = lang;
+                }
+                Err(_) => {
+                    log::warn!("Unsupported ATIS LANG option {}", option_value);
+                }
+            },
+            "UNITS" => match UnitProfile::from_str(option_value) {
+                Ok(profile) => {
+                    units = profile;
+                }
+                Err(_) => {
+                    log::warn!("Unsupported ATIS UNITS option {}", option_value);
+                }
+            },
+            "TEMPLATE" => {
+                template = Some(option_value.to_string());
+            }
+            "ICAO" => match icao_ident(option_value) {
+                Some(ident) => {
+                    icao = Some(ident);
+                }
+                None => {
+                    log::warn!("Unsupported ATIS ICAO option {}", option_value);
+                }
+            },
+            "CROSSWIND" => {
+                if let Ok(limit) = option_value.parse::<u32>() {
+                    crosswind_limit = Some(limit);
+                } else {
+                    log::warn!("Unsupported ATIS CROSSWIND option {}", option_value);
+                }
+            }
+            "TAILWIND" => {
+                if let Ok(limit) = option_value.parse::<u32>() {
+                    tailwind_limit = Some(limit);
+                } else {
+                    log::warn!("Unsupported ATIS TAILWIND option {}", option_value);
+                }
+            }
             _ => {
                 log::warn!("Unsupported ATIS station option {}", option_key);
             }
@@ -115,18 +297,95 @@ pub fn extract_atis_station_config(config: &str) -> Option<StationConfig> {
 
     let result = StationConfig {
         name,
+        icao,
         atis: atis_freq,
         traffic: traffic_freq,
         tts,
+        fallback_tts,
+        prosody,
         info_ltr_override,
         active_rwy_override,
         no_hpa,
         no_qfe,
+        dew_point,
+        density_alt,
+        transition_level,
+        transition_alt,
+        runway_rules,
+        locale,
+        units,
+        crosswind_limit,
+        tailwind_limit,
+        coalition,
+        radio_effects,
+        template,
     };
 
     Some(result)
 }
 
+/// Parse an ICAO location indicator, e.g. `UGKO`.
+fn icao_ident(value: &str) -> Option<String> {
+    (value.len() == 4 && value.chars().all(|c| c.is_ascii_alphanumeric()))
+        .then(|| value.to_uppercase())
+}
+
+/// Parse a list of voices separated by spaces, e.g. `AWS:Matthew WIN`.
+fn voice_list(value: &str) -> Vec<TextToSpeechProvider> {
+    value
+        .split_whitespace()
+        .filter_map(|voice| match TextToSpeechProvider::from_str(voice) {
+            Ok(tts) => Some(tts),
+            Err(err) => {
+                log::warn!("Unable to extract fallback Voice from {}: {}", voice, err);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Parse the `RATE` (in percent), `PITCH` (in semitones) and `VOLUME` (in dB) options into
+/// `prosody`, e.g. `RATE 110%`, `PITCH -2st` or `VOLUME +3dB` (the units are optional). Returns
+/// `false` if the value is invalid or out of range.
+fn prosody_option(prosody: &mut Prosody, key: &str, value: &str) -> bool {
+    let value = value.trim();
+    match key.to_uppercase().as_str() {
+        "RATE" => match value.trim_end_matches('%').trim().parse::<u32>() {
+            Ok(rate) if (50..=200).contains(&rate) => prosody.rate = Some(rate),
+            _ => return false,
+        },
+        "PITCH" => {
+            let value = value.strip_suffix("st").unwrap_or(value).trim();
+            match value.parse::<i32>() {
+                Ok(pitch) if (-12..=12).contains(&pitch) => prosody.pitch = Some(pitch),
+                _ => return false,
+            }
+        }
+        "VOLUME" => {
+            let value = value
+                .strip_suffix("dB")
+                .or_else(|| value.strip_suffix("db"))
+                .unwrap_or(value)
+                .trim();
+            match value.parse::<i32>() {
+                Ok(volume) if (-20..=20).contains(&volume) => prosody.volume = Some(volume),
+                _ => return false,
+            }
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// Parse a list of runways separated by `/`, e.g. `04/22`.
+fn runway_list(value: &str) -> Vec<String> {
+    value
+        .split('/')
+        .map(|rwy| rwy.trim().to_uppercase())
+        .filter(|rwy| !rwy.is_empty())
+        .collect()
+}
+
 pub fn extract_carrier_station_config(config: &str) -> Option<StationConfig> {
     let re = RegexBuilder::new(r"^CARRIER ([a-zA-Z- ]+) ([1-3]\d{2}(\.\d{1,3})?)(, (.+))?$")
         .case_insensitive(true)
@@ -140,7 +399,10 @@ pub fn extract_carrier_station_config(config: &str) -> Option<StationConfig> {
     let options = caps.get(5).map(|m| m.as_str()).unwrap_or_default();
 
     let mut tts: Option<TextToSpeechProvider> = None;
+    let mut fallback_tts = Vec::new();
+    let mut prosody = Prosody::default();
     let mut info_ltr_override = None;
+    let mut template = None;
 
     for (option_key, option_value) in options.split(',').filter_map(|t| {
         let t = t.trim();
@@ -149,16 +411,24 @@ pub fn extract_carrier_station_config(config: &str) -> Option<StationConfig> {
             .map(|(k, v)| (k, &v[1..]))
     }) {
         match option_key {
-            "VOICE" => {
-                if let Ok(tts_provider) = TextToSpeechProvider::from_str(option_value) {
-                    tts = Some(tts_provider);
-                } else {
-                    log::warn!("Unable to extract Voice from {}", option_value);
+            "VOICE" => match TextToSpeechProvider::from_str(option_value) {
+                Ok(tts_provider) => tts = Some(tts_provider),
+                Err(err) => log::warn!("Unable to extract Voice from {}: {}", option_value, err),
+            },
+            "FALLBACK" => {
+                fallback_tts = voice_list(option_value);
+            }
+            "RATE" | "PITCH" | "VOLUME" => {
+                if !prosody_option(&mut prosody, option_key, option_value) {
+                    log::warn!("Unsupported CARRIER {} option {}", option_key, option_value);
                 }
             }
             "INFO" => {
                 info_ltr_override = option_value.chars().next().map(|c| c.to_ascii_uppercase());
             }
+            "TEMPLATE" => {
+                template = Some(option_value.to_string());
+            }
             _ => {
                 log::warn!("Unsupported CARRIER station option {}", option_key);
             }
@@ -167,13 +437,28 @@ pub fn extract_carrier_station_config(config: &str) -> Option<StationConfig> {
 
     let result = StationConfig {
         name,
+        icao: None,
         atis: atis_freq,
         traffic: None,
         tts,
+        fallback_tts,
+        prosody,
         info_ltr_override,
         active_rwy_override: None,
         no_hpa: false,
         no_qfe: false,
+        dew_point: false,
+        density_alt: false,
+        transition_level: false,
+        transition_alt: None,
+        runway_rules: RunwayRules::default(),
+        locale: Locale::default(),
+        units: UnitProfile::default(),
+        crosswind_limit: None,
+        tailwind_limit: None,
+        coalition: None,
+        radio_effects: None,
+        template,
     };
 
     Some(result)
@@ -187,36 +472,42 @@ pub struct BroadcastConfig {
 }
 
 pub fn extract_custom_broadcast_config(config: &str) -> Option<BroadcastConfig> {
-    let re = RegexBuilder::new(r"^BROADCAST ([1-3]\d{2}(\.\d{1,3})?)(.*): ([^:]+)$")
+    let re = RegexBuilder::new(r"^BROADCAST ([1-3]\d{2}(\.\d{1,3})?)(.*)$")
         .case_insensitive(true)
         .build()
         .unwrap();
 
     let caps = re.captures(config)?;
     let freq = caps.get(1).unwrap();
-    let options = caps.get(3);
     let freq = (f64::from_str(freq.as_str()).unwrap() * 1_000_000.0) as u64;
-    let message = caps.get(4).unwrap().as_str().to_string();
+
+    // the options end at the first `: ` outside of quotes, so the message as well as voice names
+    // (e.g. Piper model paths like `C:\voices\...`) can contain colons
+    let rest = caps.get(3).unwrap().as_str();
+    let separator = find_unquoted(rest, ": ")?;
+    let (options, message) = (&rest[..separator], rest[separator + 2..].trim());
+    if message.is_empty() {
+        return None;
+    }
+    let message = message.to_string();
 
     let mut tts: Option<TextToSpeechProvider> = None;
-    if let Some(options) = options {
-        for token in options.as_str().split(',').skip(1) {
-            let token = token.trim();
-            let (option_key, option_value) = token.split_at(token.find(' ').unwrap_or(token.len()));
-            let option_key = option_key.trim();
-            let option_value = option_value.trim();
-
-            match option_key {
-                "VOICE" => {
-                    if let Ok(tts_provider) = TextToSpeechProvider::from_str(option_value) {
-                        tts = Some(tts_provider);
-                    } else {
-                        log::warn!("Unable to extract Voice from {}", option_value);
-                    }
-                }
-                _ => {
-                    log::warn!("Unsupported BROADCAST station option {}", option_key);
+    for token in options.split(',').skip(1) {
+        let token = token.trim();
+        let (option_key, option_value) = token.split_at(token.find(' ').unwrap_or(token.len()));
+        let option_key = option_key.trim();
+        let option_value = option_value.trim().replace('"', "");
+        let option_value = option_value.as_str();
+
+        match option_key {
+            "VOICE" => match TextToSpeechProvider::from_str(option_value) {
+                Ok(tts_provider) => tts = Some(tts_provider),
+                Err(err) => {
+                    log::warn!("Unable to extract Voice from {}: {}", option_value, err)
                 }
+            },
+            _ => {
+                log::warn!("Unsupported BROADCAST station option {}", option_key);
             }
         }
     }
@@ -226,13 +517,43 @@ pub fn extract_custom_broadcast_config(config: &str) -> Option<BroadcastConfig>
     Some(result)
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// The byte index of the first occurrence of `pattern` in `s` that is not within double quotes.
+fn find_unquoted(s: &str, pattern: &str) -> Option<usize> {
+    let mut quoted = false;
+    for (i, c) in s.char_indices() {
+        if c == '"' {
+            quoted = !quoted;
+        } else if !quoted && s[i..].starts_with(pattern) {
+            return Some(i);
+        }
+    }
+    None
+}
+
+#[derive(Debug, PartialEq)]
 pub struct WetherStationConfig {
     pub name: String,
+    /// The ICAO code of the station (see `ICAO {IDENT}`).
+    pub icao: Option<String>,
     pub freq: u64,
     pub tts: Option<TextToSpeechProvider>,
+    /// Voices tried in order when the voice fails.
+    pub fallback_tts: Vec<TextToSpeechProvider>,
+    /// Speaking rate, pitch and volume of the voice.
+    pub prosody: Prosody,
+    /// Altitudes (MSL) to report the winds aloft for.
+    pub winds_aloft: Vec<Length>,
+    /// The language of the report.
+    pub locale: Locale,
+    /// The units of the report.
+    pub units: UnitProfile,
+    /// Name of the template used for the report (see `Template::load`).
+    pub template: Option<String>,
 }
 
+/// The altitudes (in ft MSL) the winds aloft are reported for when enabled via `WINDS ON`.
+pub const DEFAULT_WINDS_ALOFT: [f64; 3] = [2_000.0, 8_000.0, 26_000.0];
+
 pub fn extract_weather_station_config(config: &str) -> Option<WetherStationConfig> {
     let re = RegexBuilder::new(r"^WEATHER ([a-zA-Z- ]+) ([1-3]\d{2}(\.\d{1,3})?)")
         .case_insensitive(true)
@@ -245,6 +566,13 @@ pub fn extract_weather_station_config(config: &str) -> Option<WetherStationConfi
     let station_freq = (f64::from_str(station_freq.as_str()).unwrap() * 1_000_000.0) as u64;
 
     let mut tts: Option<TextToSpeechProvider> = None;
+    let mut winds_aloft = Vec::new();
+    let mut locale = Locale::default();
+    let mut units = UnitProfile::default();
+    let mut template = None;
+    let mut fallback_tts = Vec::new();
+    let mut prosody = Prosody::default();
+    let mut icao = None;
 
     for token in config.split(',').skip(1) {
         let token = token.trim();
@@ -253,13 +581,68 @@ pub fn extract_weather_station_config(config: &str) -> Option<WetherStationConfi
         let option_value = option_value.trim();
 
         match option_key {
-            "VOICE" => {
-                if let Ok(tts_provider) = TextToSpeechProvider::from_str(option_value) {
-                    tts = Some(tts_provider);
-                } else {
-                    log::warn!("Unable to extract Voice from {}", option_value);
+            "VOICE" => match TextToSpeechProvider::from_str(option_value) {
+                Ok(tts_provider) => tts = Some(tts_provider),
+                Err(err) => log::warn!("Unable to extract Voice from {}: {}", option_value, err),
+            },
+            "FALLBACK" => {
+                fallback_tts = voice_list(option_value);
+            }
+            "RATE" | "PITCH" | "VOLUME" => {
+                if !prosody_option(&mut prosody, option_key, option_value) {
+                    log::warn!("Unsupported WEATHER {} option {}", option_key, option_value);
                 }
             }
+            "WINDS" => match option_value.to_uppercase().as_str() {
+                "ON" => {
+                    winds_aloft = DEFAULT_WINDS_ALOFT
+                        .iter()
+                        .map(|alt| Length::new::<foot>(*alt))
+                        .collect();
+                }
+                "OFF" => {
+                    winds_aloft.clear();
+                }
+                alts => match alts
+                    .split('/')
+                    .map(|alt| alt.trim().parse::<f64>().map(Length::new::<foot>))
+                    .collect::<Result<Vec<_>, _>>()
+                {
+                    Ok(alts) => {
+                        winds_aloft = alts;
+                    }
+                    Err(_) => {
+                        log::warn!("Unsupported WEATHER WINDS option {}", option_value);
+                    }
+                },
+            },
+            "LANG" => match Locale::from_str(option_value) {
+                Ok(lang) => {
+                    locale = lang;
+                }
+                Err(_) => {
+                    log::warn!("Unsupported WEATHER LANG option {}", option_value);
+                }
+            },
+            "UNITS" => match UnitProfile::from_str(option_value) {
+                Ok(profile) => {
+                    units = profile;
+                }
+                Err(_) => {
+                    log::warn!("Unsupported WEATHER UNITS option {}", option_value);
+                }
+            },
+            "TEMPLATE" => {
+                template = Some(option_value.to_string());
+            }
+            "ICAO" => match icao_ident(option_value) {
+                Some(ident) => {
+                    icao = Some(ident);
+                }
+                None => {
+                    log::warn!("Unsupported WEATHER ICAO option {}", option_value);
+                }
+            },
             _ => {
                 log::warn!("Unsupported WEATHER station option {}", option_key);
             }
@@ -268,8 +651,15 @@ pub fn extract_weather_station_config(config: &str) -> Option<WetherStationConfi
 
     let result = WetherStationConfig {
         name,
+        icao,
         freq: station_freq,
         tts,
+        fallback_tts,
+        prosody,
+        winds_aloft,
+        locale,
+        units,
+        template,
     };
 
     Some(result)
@@ -278,7 +668,7 @@ pub fn extract_weather_station_config(config: &str) -> Option<WetherStationConfi
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::tts::{TextToSpeechProvider, aws, gcloud};
+    use crate::tts::{TextToSpeechProvider, aws, gcloud, local};
 
     #[test]
     fn test_mission_descriptiopn_extraction() {
@@ -299,39 +689,84 @@ mod test {
                     "Mineralnye Vody".to_string(),
                     StationConfig {
                         name: "Mineralnye Vody".to_string(),
+                        icao: None,
                         atis: 251_000_000,
                         traffic: None,
                         tts: None,
+                        fallback_tts: Vec::new(),
+                        prosody: Prosody::default(),
                         info_ltr_override: None,
                         active_rwy_override: None,
                         no_hpa: false,
                         no_qfe: false,
+                        dew_point: false,
+                        density_alt: false,
+                        transition_level: false,
+                        transition_alt: None,
+                        runway_rules: RunwayRules::default(),
+                        locale: Locale::default(),
+                        units: UnitProfile::default(),
+                        crosswind_limit: None,
+                        tailwind_limit: None,
+                        coalition: None,
+                        radio_effects: None,
+                        template: None,
                     }
                 ),
                 (
                     "Batumi".to_string(),
                     StationConfig {
                         name: "Batumi".to_string(),
+                        icao: None,
                         atis: 131_500_000,
                         traffic: Some(255_000_000),
                         tts: None,
+                        fallback_tts: Vec::new(),
+                        prosody: Prosody::default(),
                         info_ltr_override: None,
                         active_rwy_override: None,
                         no_hpa: false,
                         no_qfe: false,
+                        dew_point: false,
+                        density_alt: false,
+                        transition_level: false,
+                        transition_alt: None,
+                        runway_rules: RunwayRules::default(),
+                        locale: Locale::default(),
+                        units: UnitProfile::default(),
+                        crosswind_limit: None,
+                        tailwind_limit: None,
+                        coalition: None,
+                        radio_effects: None,
+                        template: None,
                     }
                 ),
                 (
                     "Senaki-Kolkhi".to_string(),
                     StationConfig {
                         name: "Senaki-Kolkhi".to_string(),
+                        icao: None,
                         atis: 145_000_000,
                         traffic: None,
                         tts: None,
+                        fallback_tts: Vec::new(),
+                        prosody: Prosody::default(),
                         info_ltr_override: None,
                         active_rwy_override: None,
                         no_hpa: false,
                         no_qfe: false,
+                        dew_point: false,
+                        density_alt: false,
+                        transition_level: false,
+                        transition_alt: None,
+                        runway_rules: RunwayRules::default(),
+                        locale: Locale::default(),
+                        units: UnitProfile::default(),
+                        crosswind_limit: None,
+                        tailwind_limit: None,
+                        coalition: None,
+                        radio_effects: None,
+                        template: None,
                     }
                 )
             ]
@@ -354,13 +789,28 @@ mod test {
                 "H4".to_string(),
                 StationConfig {
                     name: "H4".to_string(),
+                    icao: None,
                     atis: 251_000_000,
                     traffic: None,
                     tts: None,
+                    fallback_tts: Vec::new(),
+                    prosody: Prosody::default(),
                     info_ltr_override: None,
                     active_rwy_override: None,
                     no_hpa: false,
                     no_qfe: false,
+                    dew_point: false,
+                    density_alt: false,
+                    transition_level: false,
+                    transition_alt: None,
+                    runway_rules: RunwayRules::default(),
+                    locale: Locale::default(),
+                    units: UnitProfile::default(),
+                    crosswind_limit: None,
+                    tailwind_limit: None,
+                    coalition: None,
+                    radio_effects: None,
+                    template: None,
                 }
             ),]
             .into_iter()
@@ -382,13 +832,28 @@ mod test {
                 "Antonio B. Won Pat Intl".to_string(),
                 StationConfig {
                     name: "Antonio B. Won Pat Intl".to_string(),
+                    icao: None,
                     atis: 251_000_000,
                     traffic: None,
                     tts: None,
+                    fallback_tts: Vec::new(),
+                    prosody: Prosody::default(),
                     info_ltr_override: None,
                     active_rwy_override: None,
                     no_hpa: false,
                     no_qfe: false,
+                    dew_point: false,
+                    density_alt: false,
+                    transition_level: false,
+                    transition_alt: None,
+                    runway_rules: RunwayRules::default(),
+                    locale: Locale::default(),
+                    units: UnitProfile::default(),
+                    crosswind_limit: None,
+                    tailwind_limit: None,
+                    coalition: None,
+                    radio_effects: None,
+                    template: None,
                 }
             ),]
             .into_iter()
@@ -413,13 +878,28 @@ mod test {
                 "Batumi".to_string(),
                 StationConfig {
                     name: "Batumi".to_string(),
+                    icao: None,
                     atis: 131_500_000,
                     traffic: None,
                     tts: None,
+                    fallback_tts: Vec::new(),
+                    prosody: Prosody::default(),
                     info_ltr_override: Some('T'),
                     active_rwy_override: Some("12/30".to_string()),
                     no_hpa: false,
                     no_qfe: false,
+                    dew_point: false,
+                    density_alt: false,
+                    transition_level: false,
+                    transition_alt: None,
+                    runway_rules: RunwayRules::default(),
+                    locale: Locale::default(),
+                    units: UnitProfile::default(),
+                    crosswind_limit: None,
+                    tailwind_limit: None,
+                    coalition: None,
+                    radio_effects: None,
+                    template: None,
                 }
             )]
             .into_iter()
@@ -433,13 +913,28 @@ mod test {
             extract_atis_station_config("ATIS Kutaisi 251"),
             Some(StationConfig {
                 name: "Kutaisi".to_string(),
+                icao: None,
                 atis: 251_000_000,
                 traffic: None,
                 tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
 
@@ -447,13 +942,28 @@ mod test {
             extract_atis_station_config("ATIS Mineralnye Vody 251"),
             Some(StationConfig {
                 name: "Mineralnye Vody".to_string(),
+                icao: None,
                 atis: 251_000_000,
                 traffic: None,
                 tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
 
@@ -461,13 +971,28 @@ mod test {
             extract_atis_station_config("ATIS Senaki-Kolkhi 251"),
             Some(StationConfig {
                 name: "Senaki-Kolkhi".to_string(),
+                icao: None,
                 atis: 251_000_000,
                 traffic: None,
                 tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
 
@@ -475,13 +1000,28 @@ mod test {
             extract_atis_station_config("ATIS Kutaisi 251.000, TRAFFIC 123.45"),
             Some(StationConfig {
                 name: "Kutaisi".to_string(),
+                icao: None,
                 atis: 251_000_000,
                 traffic: Some(123_450_000),
                 tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
 
@@ -491,15 +1031,30 @@ mod test {
             ),
             Some(StationConfig {
                 name: "Kutaisi".to_string(),
+                icao: None,
                 atis: 251_000_000,
                 traffic: Some(123_450_000),
                 tts: Some(TextToSpeechProvider::GoogleCloud {
-                    voice: gcloud::VoiceKind::EnUsStandardE
+                    voice: gcloud::VoiceKind::from_str("en-US-Standard-E").unwrap()
                 }),
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: Some('Q'),
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
 
@@ -509,15 +1064,30 @@ mod test {
             ),
             Some(StationConfig {
                 name: "Kutaisi".to_string(),
+                icao: None,
                 atis: 251_000_000,
                 traffic: Some(123_450_000),
                 tts: Some(TextToSpeechProvider::GoogleCloud {
-                    voice: gcloud::VoiceKind::EnUsStandardE
+                    voice: gcloud::VoiceKind::from_str("en-US-Standard-E").unwrap()
                 }),
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
 
@@ -525,15 +1095,30 @@ mod test {
             extract_atis_station_config("ATIS Kutaisi 251.000, VOICE en-US-Standard-E"),
             Some(StationConfig {
                 name: "Kutaisi".to_string(),
+                icao: None,
                 atis: 251_000_000,
                 traffic: None,
                 tts: Some(TextToSpeechProvider::GoogleCloud {
-                    voice: gcloud::VoiceKind::EnUsStandardE
+                    voice: gcloud::VoiceKind::from_str("en-US-Standard-E").unwrap()
                 }),
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
 
@@ -541,13 +1126,28 @@ mod test {
             extract_atis_station_config("ATIS Kutaisi 131.400"),
             Some(StationConfig {
                 name: "Kutaisi".to_string(),
+                icao: None,
                 atis: 131_400_000,
                 traffic: None,
                 tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
 
@@ -556,13 +1156,28 @@ mod test {
             extract_atis_station_config("ATIS Kutaisi 131.400, TRAFFIC Potatoe"),
             Some(StationConfig {
                 name: "Kutaisi".to_string(),
+                icao: None,
                 atis: 131_400_000,
                 traffic: None,
                 tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
 
@@ -571,13 +1186,28 @@ mod test {
             extract_atis_station_config("ATIS Kutaisi 131.400, GRAVITY 7"),
             Some(StationConfig {
                 name: "Kutaisi".to_string(),
+                icao: None,
                 atis: 131_400_000,
                 traffic: None,
                 tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
     }
@@ -588,13 +1218,28 @@ mod test {
             extract_carrier_station_config("CARRIER Mother 251"),
             Some(StationConfig {
                 name: "Mother".to_string(),
+                icao: None,
                 atis: 251_000_000,
                 traffic: None,
                 tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
 
@@ -602,13 +1247,28 @@ mod test {
             extract_carrier_station_config("CARRIER Mother 131.400"),
             Some(StationConfig {
                 name: "Mother".to_string(),
+                icao: None,
                 atis: 131_400_000,
                 traffic: None,
                 tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
 
@@ -616,15 +1276,30 @@ mod test {
             extract_carrier_station_config("CARRIER Mother 251.000, VOICE en-US-Standard-E"),
             Some(StationConfig {
                 name: "Mother".to_string(),
+                icao: None,
                 atis: 251_000_000,
                 traffic: None,
                 tts: Some(TextToSpeechProvider::GoogleCloud {
-                    voice: gcloud::VoiceKind::EnUsStandardE
+                    voice: gcloud::VoiceKind::from_str("en-US-Standard-E").unwrap()
                 }),
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
     }
@@ -635,15 +1310,30 @@ mod test {
             extract_atis_station_config("ATIS Kutaisi 131.400, VOICE GC:en-US-Standard-D"),
             Some(StationConfig {
                 name: "Kutaisi".to_string(),
+                icao: None,
                 atis: 131_400_000,
                 traffic: None,
                 tts: Some(TextToSpeechProvider::GoogleCloud {
-                    voice: gcloud::VoiceKind::EnUsStandardD
+                    voice: gcloud::VoiceKind::from_str("en-US-Standard-D").unwrap()
                 }),
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
 
@@ -651,15 +1341,30 @@ mod test {
             extract_atis_station_config("ATIS Kutaisi 131.400, VOICE AWS:Brian"),
             Some(StationConfig {
                 name: "Kutaisi".to_string(),
+                icao: None,
                 atis: 131_400_000,
                 traffic: None,
                 tts: Some(TextToSpeechProvider::AmazonWebServices {
-                    voice: aws::VoiceKind::Brian
+                    voice: aws::VoiceKind::from_str("Brian").unwrap()
                 }),
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
     }
@@ -673,15 +1378,30 @@ mod test {
             ),
             Some(StationConfig {
                 name: "Kutaisi".to_string(),
+                icao: None,
                 atis: 251_000_000,
                 traffic: Some(123_450_000),
                 tts: Some(TextToSpeechProvider::GoogleCloud {
-                    voice: gcloud::VoiceKind::EnUsStandardE
+                    voice: gcloud::VoiceKind::from_str("en-US-Standard-E").unwrap()
                 }),
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
     }
@@ -715,13 +1435,28 @@ mod test {
             extract_atis_station_config("ATIS Kutaisi 131.400, ACTIVE 21L"),
             Some(StationConfig {
                 name: "Kutaisi".to_string(),
+                icao: None,
                 atis: 131_400_000,
                 traffic: None,
                 tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: Some("21L".to_string()),
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
     }
@@ -732,13 +1467,28 @@ mod test {
             extract_atis_station_config("ATIS Kutaisi 131.400, NO HPA, NO QFE"),
             Some(StationConfig {
                 name: "Kutaisi".to_string(),
+                icao: None,
                 atis: 131_400_000,
                 traffic: None,
                 tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: true,
                 no_qfe: true,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
 
@@ -746,13 +1496,28 @@ mod test {
             extract_atis_station_config("ATIS Kutaisi 131.400, no hpa, no qfe"),
             Some(StationConfig {
                 name: "Kutaisi".to_string(),
+                icao: None,
                 atis: 131_400_000,
                 traffic: None,
                 tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: true,
                 no_qfe: true,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
 
@@ -760,13 +1525,28 @@ mod test {
             extract_atis_station_config("ATIS Kutaisi 131.400, NO HPA"),
             Some(StationConfig {
                 name: "Kutaisi".to_string(),
+                icao: None,
                 atis: 131_400_000,
                 traffic: None,
                 tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: true,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
             })
         );
 
@@ -774,13 +1554,325 @@ mod test {
             extract_atis_station_config("ATIS Kutaisi 131.400, NO QFE"),
             Some(StationConfig {
                 name: "Kutaisi".to_string(),
+                icao: None,
                 atis: 131_400_000,
                 traffic: None,
                 tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: true,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_coalition_override() {
+        assert_eq!(
+            extract_atis_station_config("ATIS Kutaisi 131.400, COALITION red"),
+            Some(StationConfig {
+                name: "Kutaisi".to_string(),
+                icao: None,
+                atis: 131_400_000,
+                traffic: None,
+                tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
+                info_ltr_override: None,
+                active_rwy_override: None,
+                no_hpa: false,
+                no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: Some(vec![Coalition::Red]),
+                radio_effects: None,
+                template: None,
+            })
+        );
+
+        assert_eq!(
+            extract_atis_station_config("ATIS Kutaisi 131.400, COALITION ALL"),
+            Some(StationConfig {
+                name: "Kutaisi".to_string(),
+                icao: None,
+                atis: 131_400_000,
+                traffic: None,
+                tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
+                info_ltr_override: None,
+                active_rwy_override: None,
+                no_hpa: false,
+                no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: Some(vec![Coalition::Blue, Coalition::Red]),
+                radio_effects: None,
+                template: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_radio_effects_override() {
+        assert_eq!(
+            extract_atis_station_config("ATIS Kutaisi 131.400, EFFECTS off"),
+            Some(StationConfig {
+                name: "Kutaisi".to_string(),
+                icao: None,
+                atis: 131_400_000,
+                traffic: None,
+                tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
+                info_ltr_override: None,
+                active_rwy_override: None,
+                no_hpa: false,
+                no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: Some(false),
+                template: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_dew_point_and_density_alt() {
+        assert_eq!(
+            extract_atis_station_config("ATIS Kutaisi 131.400, DEWPOINT ON, DA on"),
+            Some(StationConfig {
+                name: "Kutaisi".to_string(),
+                icao: None,
+                atis: 131_400_000,
+                traffic: None,
+                tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
+                info_ltr_override: None,
+                active_rwy_override: None,
+                no_hpa: false,
+                no_qfe: false,
+                dew_point: true,
+                density_alt: true,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+                coalition: None,
+                radio_effects: None,
+                template: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_transition_level() {
+        let config = extract_atis_station_config("ATIS Kutaisi 131.400, TL ON").unwrap();
+        assert!(config.transition_level);
+        assert_eq!(config.transition_alt, None);
+
+        let config = extract_atis_station_config("ATIS Kutaisi 131.400, TL 5000").unwrap();
+        assert!(config.transition_level);
+        assert_eq!(config.transition_alt, Some(5000));
+
+        let config = extract_atis_station_config("ATIS Kutaisi 131.400, TL OFF").unwrap();
+        assert!(!config.transition_level);
+    }
+
+    #[test]
+    fn test_lang() {
+        let config = extract_atis_station_config("ATIS Kutaisi 131.400, LANG DE").unwrap();
+        assert_eq!(config.locale, Locale::De);
+
+        let config = extract_atis_station_config("ATIS Kutaisi 131.400, LANG XX").unwrap();
+        assert_eq!(config.locale, Locale::En);
+
+        let config = extract_weather_station_config("WEATHER Shali 251.000, LANG ru").unwrap();
+        assert_eq!(config.locale, Locale::Ru);
+    }
+
+    #[test]
+    fn test_units() {
+        let config = extract_atis_station_config("ATIS Kutaisi 131.400, UNITS SOVIET").unwrap();
+        assert_eq!(config.units, UnitProfile::Soviet);
+
+        let config = extract_atis_station_config("ATIS Kutaisi 131.400, UNITS icao").unwrap();
+        assert_eq!(config.units, UnitProfile::Icao);
+
+        let config = extract_weather_station_config("WEATHER Shali 251.000, UNITS FAA").unwrap();
+        assert_eq!(config.units, UnitProfile::Faa);
+    }
+
+    #[test]
+    fn test_icao() {
+        let config = extract_atis_station_config("ATIS Kutaisi 131.400, ICAO ugko").unwrap();
+        assert_eq!(config.icao.as_deref(), Some("UGKO"));
+
+        let config = extract_atis_station_config("ATIS Kutaisi 131.400, ICAO Kutaisi").unwrap();
+        assert_eq!(config.icao, None);
+
+        let config = extract_weather_station_config("WEATHER Shali 251.000, ICAO URMG").unwrap();
+        assert_eq!(config.icao.as_deref(), Some("URMG"));
+    }
+
+    #[test]
+    fn test_fallback_voices() {
+        let config = extract_atis_station_config(
+            "ATIS Kutaisi 131.400, VOICE GC:en-US-Wavenet-B, FALLBACK AWS:Matthew WIN",
+        )
+        .unwrap();
+        assert_eq!(
+            config.fallback_tts,
+            vec![
+                TextToSpeechProvider::AmazonWebServices {
+                    voice: aws::VoiceKind::from_str("Matthew").unwrap()
+                },
+                TextToSpeechProvider::Windows { voice: None },
+            ]
+        );
+
+        let config =
+            extract_carrier_station_config("CARRIER Mother 251.000, FALLBACK UNK:Mathew WIN")
+                .unwrap();
+        assert_eq!(
+            config.fallback_tts,
+            vec![TextToSpeechProvider::Windows { voice: None }]
+        );
+
+        let config =
+            extract_weather_station_config("WEATHER Shali 251.000, FALLBACK LOCAL").unwrap();
+        assert_eq!(
+            config.fallback_tts,
+            vec![TextToSpeechProvider::Local {
+                voice: local::VoiceKind::Espeak { voice: None }
+            }]
+        );
+    }
+
+    #[test]
+    fn test_prosody_options() {
+        let config =
+            extract_atis_station_config("ATIS Kutaisi 131.400, RATE 110%, PITCH -2st, VOLUME +3dB")
+                .unwrap();
+        assert_eq!(
+            config.prosody,
+            Prosody {
+                rate: Some(110),
+                pitch: Some(-2),
+                volume: Some(3),
+            }
+        );
+
+        let config =
+            extract_carrier_station_config("CARRIER Mother 251.000, RATE 90, PITCH 13").unwrap();
+        assert_eq!(
+            config.prosody,
+            Prosody {
+                rate: Some(90),
+                ..Default::default()
+            }
+        );
+
+        let config =
+            extract_weather_station_config("WEATHER Shali 251.000, VOLUME -6, RATE 10%").unwrap();
+        assert_eq!(
+            config.prosody,
+            Prosody {
+                volume: Some(-6),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_runway_wind_options() {
+        assert_eq!(
+            extract_atis_station_config("ATIS Kutaisi 131.400, CALM 25, CROSSWIND 10, TAILWIND 3"),
+            Some(StationConfig {
+                name: "Kutaisi".to_string(),
+                icao: None,
+                atis: 131_400_000,
+                traffic: None,
+                tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
+                info_ltr_override: None,
+                active_rwy_override: None,
+                no_hpa: false,
+                no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_level: false,
+                transition_alt: None,
+                runway_rules: RunwayRules {
+                    calm_rwy: Some("25".to_string()),
+                    ..Default::default()
+                },
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: Some(10),
+                tailwind_limit: Some(3),
+                coalition: None,
+                radio_effects: None,
+                template: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_runway_rules() {
+        assert_eq!(
+            extract_atis_station_config(
+                "ATIS Kutaisi 131.400, CALM 25 8, HYSTERESIS 4, CLOSED 07, ARRIVAL 25/07, DEPARTURE 25r"
+            )
+            .map(|c| c.runway_rules),
+            Some(RunwayRules {
+                calm_rwy: Some("25".to_string()),
+                calm_wind_limit: Some(8),
+                hysteresis: Some(4),
+                closed: vec!["07".to_string()],
+                arrival: vec!["25".to_string(), "07".to_string()],
+                departure: vec!["25R".to_string()],
             })
         );
     }
@@ -802,10 +1894,37 @@ mod test {
                 freq: 251_500_000,
                 message: "Bla bla".to_string(),
                 tts: Some(TextToSpeechProvider::AmazonWebServices {
-                    voice: aws::VoiceKind::Brian
+                    voice: aws::VoiceKind::from_str("Brian").unwrap()
                 }),
             })
         );
+
+        let piper = Some(TextToSpeechProvider::Local {
+            voice: local::VoiceKind::Piper {
+                model: "C:\\voices\\en_US-ryan-medium.onnx".to_string(),
+            },
+        });
+        assert_eq!(
+            extract_custom_broadcast_config(
+                "BROADCAST 251, VOICE PIPER:C:\\voices\\en_US-ryan-medium.onnx: Range hot: stay clear"
+            ),
+            Some(BroadcastConfig {
+                freq: 251_000_000,
+                message: "Range hot: stay clear".to_string(),
+                tts: piper.clone(),
+            })
+        );
+        assert_eq!(
+            extract_custom_broadcast_config(
+                "BROADCAST 251, VOICE \"PIPER:C:\\voices\\en_US-ryan-medium.onnx\": Bla bla"
+            ),
+            Some(BroadcastConfig {
+                freq: 251_000_000,
+                message: "Bla bla".to_string(),
+                tts: piper,
+            })
+        );
+        assert_eq!(extract_custom_broadcast_config("BROADCAST 251: "), None);
     }
 
     #[test]
@@ -814,8 +1933,15 @@ mod test {
             extract_weather_station_config("WEATHER Shooting Range 251"),
             Some(WetherStationConfig {
                 name: "Shooting Range".to_string(),
+                icao: None,
                 freq: 251_000_000,
                 tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
+                winds_aloft: Vec::new(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                template: None,
             })
         );
 
@@ -823,8 +1949,15 @@ mod test {
             extract_weather_station_config("WEATHER Coast 131.400"),
             Some(WetherStationConfig {
                 name: "Coast".to_string(),
+                icao: None,
                 freq: 131_400_000,
                 tts: None,
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
+                winds_aloft: Vec::new(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                template: None,
             })
         );
 
@@ -834,11 +1967,36 @@ mod test {
             ),
             Some(WetherStationConfig {
                 name: "Mountain Range".to_string(),
+                icao: None,
                 freq: 251_000_000,
                 tts: Some(TextToSpeechProvider::GoogleCloud {
-                    voice: gcloud::VoiceKind::EnUsStandardE
+                    voice: gcloud::VoiceKind::from_str("en-US-Standard-E").unwrap()
                 }),
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
+                winds_aloft: Vec::new(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                template: None,
             })
         );
+
+        assert_eq!(
+            extract_weather_station_config("WEATHER Coast 131.400, WINDS ON")
+                .map(|c| c.winds_aloft),
+            Some(vec![
+                Length::new::<foot>(2_000.0),
+                Length::new::<foot>(8_000.0),
+                Length::new::<foot>(26_000.0)
+            ])
+        );
+        assert_eq!(
+            extract_weather_station_config("WEATHER Coast 131.400, WINDS 5000/10000")
+                .map(|c| c.winds_aloft),
+            Some(vec![
+                Length::new::<foot>(5_000.0),
+                Length::new::<foot>(10_000.0)
+            ])
+        );
     }
 }
@@ -0,0 +1,136 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+use bytes::Bytes;
+use futures::FutureExt;
+use futures::select;
+use http_body_util::Full;
+use hyper::body::Incoming;
+use hyper::header::CONTENT_TYPE;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+use crate::status::StatusRegistry;
+
+/// Serves the live state of all stations:
+///
+/// - `GET /stations`: JSON list of all stations
+/// - `GET /stations/{name}`: JSON of a single station
+/// - `GET /metar`: the METAR of each station, one per line
+pub async fn serve(
+    addr: SocketAddr,
+    status: StatusRegistry,
+    shutdown_signal: oneshot::Receiver<()>,
+) -> Result<(), anyhow::Error> {
+    let listener = TcpListener::bind(addr).await?;
+    log::info!("Status server listening on http://{}", addr);
+
+    let mut shutdown_signal = shutdown_signal.fuse();
+    loop {
+        let (stream, _) = select! {
+            result = listener.accept().fuse() => result?,
+            _ = shutdown_signal => break,
+        };
+
+        let status = status.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |req| {
+                let status = status.clone();
+                async move { Ok::<_, Infallible>(handle(req, &status)) }
+            });
+            if let Err(err) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                log::debug!("Status server connection error: {}", err);
+            }
+        });
+    }
+
+    log::debug!("Status server successfully shut down");
+
+    Ok(())
+}
+
+fn handle(req: Request<Incoming>, status: &StatusRegistry) -> Response<Full<Bytes>> {
+    if req.method() != Method::GET {
+        return text(StatusCode::METHOD_NOT_ALLOWED, "Method not allowed");
+    }
+
+    let path = req.uri().path().trim_end_matches('/');
+    match path {
+        "/stations" => json(&status.all()),
+        "/metar" => {
+            let metars = status
+                .all()
+                .into_iter()
+                .filter_map(|s| s.metar)
+                .collect::<Vec<_>>();
+            text(StatusCode::OK, &metars.join("\n"))
+        }
+        _ => match path.strip_prefix("/stations/") {
+            Some(name) => match status.get(&percent_decode(name)) {
+                Some(station) => json(&station),
+                None => text(StatusCode::NOT_FOUND, "Station not found"),
+            },
+            None => text(StatusCode::NOT_FOUND, "Not found"),
+        },
+    }
+}
+
+fn json(value: &impl serde::Serialize) -> Response<Full<Bytes>> {
+    match serde_json::to_vec(value) {
+        Ok(body) => Response::builder()
+            .header(CONTENT_TYPE, "application/json")
+            .body(Full::new(Bytes::from(body)))
+            .unwrap(),
+        Err(err) => text(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string()),
+    }
+}
+
+fn text(status: StatusCode, body: &str) -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "text/plain; charset=utf-8")
+        .body(Full::new(Bytes::from(body.to_string())))
+        .unwrap()
+}
+
+/// Decode percent-encoded path segments, e.g. `Senaki%20Kolkhi`.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if let Some(b) = s
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::percent_decode;
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("Kutaisi"), "Kutaisi");
+        assert_eq!(percent_decode("Mineralnye%20Vody"), "Mineralnye Vody");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+}
@@ -6,11 +6,34 @@ use serde::Deserialize;
 use serde_json::json;
 use tokio::sync::Mutex;
 use uom::si::angle::degree;
-use uom::si::f64::{Angle, Pressure, ThermodynamicTemperature as Temperature, Velocity};
+use uom::si::f64::{
+    Angle, Length as LengthF64, Pressure, ThermodynamicTemperature as Temperature, Velocity,
+};
 use uom::si::i32::Length;
+use uom::si::length::meter;
 
 use crate::station::{LatLngPosition, Position};
-use crate::weather::{Clouds, WeatherInfo};
+use crate::weather::{Clouds, WeatherInfo, WindAloft};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WeatherData {
+    #[serde(deserialize_with = "crate::de::from_meter_per_second")]
+    wind_speed: Velocity,
+    #[serde(deserialize_with = "crate::de::from_radian")]
+    wind_dir: Angle,
+    #[serde(deserialize_with = "crate::de::from_meter_per_second")]
+    turbulence: Velocity,
+    #[serde(deserialize_with = "crate::de::from_degree_celcius")]
+    temp: Temperature,
+    #[serde(deserialize_with = "crate::de::from_pascal")]
+    pressure: Pressure,
+    #[serde(deserialize_with = "crate::de::from_meter")]
+    fog_thickness: Length,
+    #[serde(deserialize_with = "crate::de::from_meter")]
+    fog_visibility: Length,
+    dust_density: i32,
+}
 
 pub struct MissionRpcInner {
     ipc: dcs_module_ipc::IPC<()>,
@@ -22,53 +45,13 @@ pub struct MissionRpc(Arc<MissionRpcInner>);
 
 impl MissionRpc {
     pub async fn get_weather_at(&self, pos: &Position) -> Result<WeatherInfo, Error> {
-        #[derive(Debug, Deserialize)]
-        #[serde(rename_all = "camelCase")]
-        struct Data {
-            #[serde(deserialize_with = "crate::de::from_meter_per_second")]
-            wind_speed: Velocity,
-            #[serde(deserialize_with = "crate::de::from_radian")]
-            wind_dir: Angle,
-            #[serde(deserialize_with = "crate::de::from_degree_celcius")]
-            temp: Temperature,
-            #[serde(deserialize_with = "crate::de::from_pascal")]
-            pressure: Pressure,
-            #[serde(deserialize_with = "crate::de::from_meter")]
-            fog_thickness: Length,
-            #[serde(deserialize_with = "crate::de::from_meter")]
-            fog_visibility: Length,
-            dust_density: i32,
-        }
-
         // first, get weather at sea level
-        let data: Data = self
-            .0
-            .ipc
-            .request(
-                "get_weather",
-                Some(json!({ "x": pos.x, "y": pos.y, "alt": 0})),
-            )
-            .await?;
+        let data = self.get_weather_data(pos, 0.0).await?;
         // ... to retrieve the QNH
         let pressure_sealevel = data.pressure;
 
         // then get weather at actual altitude
-        let data: Data = self
-            .0
-            .ipc
-            .request(
-                "get_weather",
-                Some(json!({ "x": pos.x, "y": pos.y, "alt": pos.alt})),
-            )
-            .await?;
-
-        // convert to degrees and rotate wind direction
-        let mut wind_dir = data.wind_dir - Angle::new::<degree>(180.0);
-
-        // normalize wind direction
-        while wind_dir < Angle::new::<degree>(0.0) {
-            wind_dir += Angle::new::<degree>(360.0);
-        }
+        let data = self.get_weather_data(pos, pos.alt).await?;
 
         let clouds = {
             let mut clouds = self.0.clouds.lock().await;
@@ -81,7 +64,8 @@ impl MissionRpc {
         Ok(WeatherInfo {
             clouds: Some(clouds),
             wind_speed: data.wind_speed,
-            wind_dir,
+            wind_dir: data.wind_dir,
+            turbulence: data.turbulence,
             temperature: data.temp,
             pressure_sealevel,
             pressure_groundlevel: data.pressure,
@@ -89,9 +73,50 @@ impl MissionRpc {
             fog_visibility: data.fog_visibility,
             dust_density: data.dust_density,
             position: pos.clone(),
+            winds_aloft: Vec::new(),
         })
     }
 
+    /// Sample the wind at each of the given altitudes (MSL) above `pos`.
+    pub async fn get_winds_aloft(
+        &self,
+        pos: &Position,
+        alts: &[LengthF64],
+    ) -> Result<Vec<WindAloft>, Error> {
+        let mut winds = Vec::with_capacity(alts.len());
+        for alt in alts {
+            let data = self.get_weather_data(pos, alt.get::<meter>()).await?;
+            winds.push(WindAloft {
+                alt: *alt,
+                wind_speed: data.wind_speed,
+                wind_dir: data.wind_dir,
+                temperature: data.temp,
+            });
+        }
+        Ok(winds)
+    }
+
+    async fn get_weather_data(&self, pos: &Position, alt: f64) -> Result<WeatherData, Error> {
+        let mut data: WeatherData = self
+            .0
+            .ipc
+            .request(
+                "get_weather",
+                Some(json!({ "x": pos.x, "y": pos.y, "alt": alt})),
+            )
+            .await?;
+
+        // convert to degrees and rotate wind direction
+        data.wind_dir -= Angle::new::<degree>(180.0);
+
+        // normalize wind direction
+        while data.wind_dir < Angle::new::<degree>(0.0) {
+            data.wind_dir += Angle::new::<degree>(360.0);
+        }
+
+        Ok(data)
+    }
+
     pub async fn get_clouds(&self) -> Result<Clouds, Error> {
         let clouds: Clouds = self.0.ipc.request("get_clouds", None::<()>).await?;
 
@@ -134,6 +159,15 @@ impl MissionRpc {
         })
     }
 
+    /// The current date and time of the mission.
+    pub async fn get_mission_time(&self) -> Result<time::PrimitiveDateTime, Error> {
+        let date = self.get_mission_start_date().await?;
+        // the absolute time is the number of seconds since midnight of the start date
+        let time = self.get_abs_time().await?;
+        Ok(time::PrimitiveDateTime::new(date, time::Time::MIDNIGHT)
+            + time::Duration::seconds_f64(time.max(0.0)))
+    }
+
     pub async fn get_mission_hour(&self) -> Result<u16, Error> {
         let mut time = self.get_abs_time().await?;
         let mut h = 0;
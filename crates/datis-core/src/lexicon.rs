@@ -0,0 +1,278 @@
+//! Pronunciation lexicon for the spoken reports.
+//!
+//! Every line of a lexicon is an entry of the form `{WORDS} = {PRONUNCIATION}`. A pronunciation
+//! enclosed in slashes is IPA (e.g. `Kutaisi = /kʊˈtaɪsi/`), anything else is spoken instead of
+//! the words (e.g. `Lympne = Lim`). Words are matched as whole words and case-insensitive, longer
+//! entries take precedence over shorter ones. Empty lines and lines starting with `#` are ignored.
+
+use std::path::Path;
+use std::str::FromStr;
+
+/// The built-in pronunciations of airfield names (of all DCS maps) and terms.
+pub const DEFAULT_LEXICON: &str = include_str!("lexicon.txt");
+
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Lexicon {
+    /// Sorted by the length of the words, longest first.
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct Entry {
+    words: String,
+    pronunciation: Pronunciation,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Pronunciation {
+    Ipa(String),
+    /// Text spoken instead of the words.
+    Alias(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LexiconError {
+    #[error("Expected `{{WORDS}} = {{PRONUNCIATION}}` in line {0}")]
+    Syntax(usize),
+    #[error("Failed to read lexicon {0}")]
+    Read(String, #[source] std::io::Error),
+}
+
+impl Lexicon {
+    /// The lexicon with the built-in pronunciations (see `DEFAULT_LEXICON`).
+    pub fn builtin() -> Self {
+        DEFAULT_LEXICON.parse().expect("valid built-in lexicon")
+    }
+
+    /// Load the lexicon at `path`, returns `None` if it does not exist.
+    pub fn load(path: &Path) -> Result<Option<Self>, LexiconError> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(path)
+            .map_err(|err| LexiconError::Read(path.display().to_string(), err))?;
+        content.parse().map(Some)
+    }
+
+    /// Add the entries of `other`, replacing existing entries for the same words.
+    pub fn extend(&mut self, other: Lexicon) {
+        for entry in other.entries {
+            self.entries
+                .retain(|e| !e.words.eq_ignore_ascii_case(&entry.words));
+            self.entries.push(entry);
+        }
+        self.sort();
+    }
+
+    pub fn get(&self, words: &str) -> Option<&Pronunciation> {
+        self.entries
+            .iter()
+            .find(|e| e.words.eq_ignore_ascii_case(words))
+            .map(|e| &e.pronunciation)
+    }
+
+    /// Apply the lexicon to the text of the given SSML. Markup and the content of elements that
+    /// already define a pronunciation (`<phoneme>`, `<sub>` and `<say-as>`) are kept as is.
+    pub fn apply(&self, ssml: &str) -> String {
+        if self.entries.is_empty() {
+            return ssml.to_string();
+        }
+
+        let mut result = String::with_capacity(ssml.len());
+        let mut rest = ssml;
+        // nesting depth of elements whose content must not be changed
+        let mut skip = 0usize;
+        while !rest.is_empty() {
+            if rest.starts_with('<') {
+                let end = rest.find('>').map(|i| i + 1).unwrap_or(rest.len());
+                let tag = &rest[..end];
+                if let Some(name) = tag.strip_prefix("</") {
+                    if defines_pronunciation(name) {
+                        skip = skip.saturating_sub(1);
+                    }
+                } else if !tag.ends_with("/>") && defines_pronunciation(&tag[1..]) {
+                    skip += 1;
+                }
+                result += tag;
+                rest = &rest[end..];
+            } else {
+                let end = rest.find('<').unwrap_or(rest.len());
+                let text = &rest[..end];
+                if skip == 0 {
+                    self.apply_text(text, &mut result);
+                } else {
+                    result += text;
+                }
+                rest = &rest[end..];
+            }
+        }
+        result
+    }
+
+    fn apply_text(&self, text: &str, result: &mut String) {
+        let mut pos = 0;
+        let mut prev = None;
+        while let Some(c) = text[pos..].chars().next() {
+            if !prev.is_some_and(is_word_char) && is_word_char(c) {
+                if let Some(entry) = self.entries.iter().find(|e| e.matches(&text[pos..])) {
+                    let words = &text[pos..pos + entry.words.len()];
+                    match &entry.pronunciation {
+                        Pronunciation::Ipa(ipa) => {
+                            *result += &format!(
+                                r#"<phoneme alphabet="ipa" ph="{}">{}</phoneme>"#,
+                                escape_attr(ipa),
+                                words
+                            );
+                        }
+                        Pronunciation::Alias(alias) => {
+                            *result +=
+                                &format!(r#"<sub alias="{}">{}</sub>"#, escape_attr(alias), words);
+                        }
+                    }
+                    pos += words.len();
+                    prev = words.chars().next_back();
+                    continue;
+                }
+            }
+
+            result.push(c);
+            pos += c.len_utf8();
+            prev = Some(c);
+        }
+    }
+
+    fn sort(&mut self) {
+        self.entries
+            .sort_by_key(|e| std::cmp::Reverse(e.words.len()));
+    }
+}
+
+impl Entry {
+    /// Whether `text` starts with the words of the entry, followed by a word boundary.
+    fn matches(&self, text: &str) -> bool {
+        text.get(..self.words.len())
+            .is_some_and(|words| words.eq_ignore_ascii_case(&self.words))
+            && !text[self.words.len()..]
+                .chars()
+                .next()
+                .is_some_and(is_word_char)
+    }
+}
+
+impl FromStr for Lexicon {
+    type Err = LexiconError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lexicon = Lexicon::default();
+        for (nr, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (words, pronunciation) = line
+                .split_once('=')
+                .map(|(words, pronunciation)| (words.trim(), pronunciation.trim()))
+                .filter(|(words, pronunciation)| !words.is_empty() && !pronunciation.is_empty())
+                .ok_or(LexiconError::Syntax(nr + 1))?;
+            let pronunciation = match pronunciation
+                .strip_prefix('/')
+                .and_then(|ipa| ipa.strip_suffix('/'))
+            {
+                Some(ipa) => Pronunciation::Ipa(ipa.to_string()),
+                None => Pronunciation::Alias(pronunciation.to_string()),
+            };
+
+            // later entries replace earlier ones for the same words
+            lexicon
+                .entries
+                .retain(|e| !e.words.eq_ignore_ascii_case(words));
+            lexicon.entries.push(Entry {
+                words: words.to_string(),
+                pronunciation,
+            });
+        }
+        lexicon.sort();
+
+        Ok(lexicon)
+    }
+}
+
+fn defines_pronunciation(tag: &str) -> bool {
+    ["phoneme", "sub", "say-as"].iter().any(|name| {
+        tag.strip_prefix(name)
+            .is_some_and(|rest| rest.starts_with([' ', '>', '/']))
+    })
+}
+
+/// Letters and digits, and `&` to not match the names of XML entities (e.g. `&amp;`).
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '&'
+}
+
+fn escape_attr(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let lexicon =
+            Lexicon::from_str("# comment\nKutaisi = /kʊˈtaɪsi/\n\nLympne = Lim\nlympne = Limm")
+                .unwrap();
+        assert_eq!(
+            lexicon.get("KUTAISI"),
+            Some(&Pronunciation::Ipa("kʊˈtaɪsi".to_string()))
+        );
+        assert_eq!(
+            lexicon.get("Lympne"),
+            Some(&Pronunciation::Alias("Limm".to_string()))
+        );
+
+        assert!(matches!(
+            Lexicon::from_str("Kutaisi = /kʊˈtaɪsi/\nLympne"),
+            Err(LexiconError::Syntax(2))
+        ));
+        assert!(matches!(
+            Lexicon::from_str("Lympne = "),
+            Err(LexiconError::Syntax(1))
+        ));
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut lexicon = Lexicon::from_str(
+            "Senaki = /sɛˈnɑki/\nSenaki-Kolkhi = Senaki Kolki\nWind = Wint\nAl Ain = /ɑl ˈʕajn/",
+        )
+        .unwrap();
+        lexicon.extend(Lexicon::from_str("senaki = /sɛnɑki/\nAmp = Ampere").unwrap());
+
+        assert_eq!(
+            lexicon.apply(
+                r#"<speak>This is Senaki-Kolkhi, Senaki and senakis &amp; Al Ain. <phoneme alphabet="ipa" ph="w&#618;nd">Wind</phoneme></speak>"#
+            ),
+            r#"<speak>This is <sub alias="Senaki Kolki">Senaki-Kolkhi</sub>, <phoneme alphabet="ipa" ph="sɛnɑki">Senaki</phoneme> and senakis &amp; <phoneme alphabet="ipa" ph="ɑl ˈʕajn">Al Ain</phoneme>. <phoneme alphabet="ipa" ph="w&#618;nd">Wind</phoneme></speak>"#
+        );
+        assert_eq!(
+            Lexicon::default().apply("<speak>Senaki</speak>"),
+            "<speak>Senaki</speak>"
+        );
+    }
+
+    #[test]
+    fn test_default_lexicon() {
+        let lexicon = Lexicon::builtin();
+        assert_eq!(
+            lexicon.get("Ramat David"),
+            Some(&Pronunciation::Ipa("rɑˈmɑt dɑˈvid".to_string()))
+        );
+    }
+}
@@ -1,70 +1,124 @@
 #[macro_use]
 extern crate anyhow;
 
+pub mod audio;
 pub mod config;
 mod de;
 pub mod export;
 pub mod extract;
+#[cfg(feature = "http")]
+mod http;
 #[cfg(feature = "ipc")]
 pub mod ipc;
+pub mod lexicon;
+pub mod locale;
 pub mod station;
+pub mod status;
+pub mod template;
 pub mod tts;
+pub mod units;
 mod utils;
 pub mod weather;
 
+use std::collections::HashMap;
 use std::mem;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::path::Path;
-use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use config::{AwsConfig, AzureConfig, Config};
+use config::Config;
 use futures::future::FutureExt;
 use futures::select;
 use futures::sink::SinkExt;
 use futures::stream::{SplitSink, StreamExt};
-use srs::message::Coalition;
 use srs::{Client, VoiceStream};
 use tokio::runtime::{self, Runtime};
-use tokio::sync::{RwLock, oneshot};
+use tokio::sync::{RwLock, oneshot, watch};
 use tokio::time::sleep;
 
+use crate::audio::RadioEffects;
 use crate::export::ReportExporter;
+use crate::lexicon::Lexicon;
 use crate::station::{LatLngPosition, Station, Transmitter};
-use crate::tts::aws::{self, AmazonWebServicesConfig};
-use crate::tts::azure::{self, AzureCognitiveServicesConfig};
-use crate::tts::gcloud::{self, GoogleCloudConfig};
-use crate::tts::win::{self, WindowsConfig};
-use crate::tts::{TextToSpeechConfig, TextToSpeechProvider};
+use crate::status::{ConnectionState, StatusRegistry};
+use crate::tts::cache::AudioCache;
+use crate::tts::cooldown::TtsCooldowns;
+use crate::tts::prosody::Prosody;
+use crate::tts::{TextToSpeech, TextToSpeechProvider};
 
 pub struct Datis {
     stations: Vec<Station>,
     exporter: Option<ReportExporter>,
+    tts_cache: Option<AudioCache>,
+    tts_cooldowns: TtsCooldowns,
+    lexicon: Option<Arc<Lexicon>>,
+    status: StatusRegistry,
     config: Config,
     runtime: Runtime,
     started: bool,
+    /// Shutdown signals of the running stations, by station name.
+    station_signals: HashMap<String, oneshot::Sender<()>>,
+    /// Channels to pass changes to the running stations without restarting them, by station name.
+    station_updates: HashMap<String, watch::Sender<Station>>,
     shutdown_signals: Vec<oneshot::Sender<()>>,
 }
 
 impl Datis {
     pub fn new(stations: Vec<Station>, config: Config) -> Result<Self, Error> {
+        let status = StatusRegistry::default();
+        for station in &stations {
+            station.warn_if_language_mismatch();
+            status.register(station);
+        }
+
         Ok(Datis {
             stations,
             exporter: None,
+            tts_cache: None,
+            tts_cooldowns: TtsCooldowns::default(),
+            lexicon: None,
+            status,
             config,
             runtime: runtime::Builder::new_multi_thread().enable_all().build()?,
             started: false,
+            station_signals: HashMap::new(),
+            station_updates: HashMap::new(),
             shutdown_signals: Vec::new(),
         })
     }
 
     pub fn enable_exporter(&mut self, write_dir: impl AsRef<Path>) {
         let path = write_dir.as_ref().to_path_buf().join("atis-reports.json");
-        let exporter = ReportExporter::new(path);
+        let history_path = self
+            .config
+            .export_history
+            .then(|| write_dir.as_ref().join("atis-reports-history.jsonl"));
+        let exporter = ReportExporter::new(path, history_path);
         self.exporter = Some(exporter);
     }
 
+    /// Cache synthesized reports in `dir` to avoid repeated TTS requests for identical reports
+    /// (disabled if `tts_cache_size` is set to `0`).
+    pub fn enable_tts_cache(&mut self, dir: impl AsRef<Path>) {
+        if self.config.tts_cache_size > 0 {
+            let max_size = self.config.tts_cache_size * 1024 * 1024;
+            self.tts_cache = Some(AudioCache::new(dir, max_size));
+        }
+    }
+
+    /// Apply the built-in pronunciations and the ones of the lexicon at `path` (if it exists) to
+    /// the spoken reports, see `Lexicon`.
+    pub fn enable_lexicon(&mut self, path: impl AsRef<Path>) {
+        let mut lexicon = Lexicon::builtin();
+        match Lexicon::load(path.as_ref()) {
+            Ok(Some(custom)) => lexicon.extend(custom),
+            Ok(None) => {}
+            Err(err) => log::error!("Failed to load lexicon: {}", err),
+        }
+        self.lexicon = Some(Arc::new(lexicon));
+    }
+
     pub fn start(&mut self) -> Result<(), Error> {
         if self.started {
             return Ok(());
@@ -72,88 +126,12 @@ impl Datis {
 
         self.started = true;
 
-        for station in &mut self.stations {
-            let config = match station.tts {
-                TextToSpeechProvider::GoogleCloud { voice } => {
-                    if let Some(ref config) = self.config.gcloud {
-                        TextToSpeechConfig::GoogleCloud(GoogleCloudConfig {
-                            key: config.key.clone(),
-                            voice,
-                        })
-                    } else {
-                        log::error!(
-                            "Cannot start {} with TTS provider {:?} due to missing Google Cloud key",
-                            station.name,
-                            station.tts
-                        );
-                        continue;
-                    }
-                }
-                TextToSpeechProvider::AmazonWebServices { voice } => {
-                    if let Some(AwsConfig {
-                        ref key,
-                        ref secret,
-                        ref region,
-                    }) = self.config.aws
-                    {
-                        TextToSpeechConfig::AmazonWebServices(AmazonWebServicesConfig {
-                            key: key.clone(),
-                            secret: secret.clone(),
-                            region: match rusoto_core::Region::from_str(region) {
-                                Ok(region) => region,
-                                Err(err) => {
-                                    log::error!(
-                                        "Cannot start {} due to invalid AWS region {}: {}",
-                                        station.name,
-                                        region,
-                                        err
-                                    );
-                                    continue;
-                                }
-                            },
-                            voice,
-                        })
-                    } else {
-                        log::error!(
-                            "Cannot start {} due to missing AWS key, secret or region",
-                            station.name
-                        );
-                        continue;
-                    }
-                }
-                TextToSpeechProvider::Windows { ref voice } => {
-                    TextToSpeechConfig::Windows(WindowsConfig { voice: *voice })
-                }
-                TextToSpeechProvider::AzureCognitiveServices { voice } => {
-                    if let Some(AzureConfig {
-                        ref key,
-                        ref region,
-                    }) = self.config.azure
-                    {
-                        TextToSpeechConfig::AzureCognitiveServices(AzureCognitiveServicesConfig {
-                            key: key.clone(),
-                            region: region.clone(),
-                            voice,
-                        })
-                    } else {
-                        log::error!("Cannot start {} due to missing azure key", station.name);
-                        continue;
-                    }
-                }
-            };
+        if let Some(addr) = self.config.status_addr {
+            self.start_status_server(addr);
+        }
 
-            let (tx, rx) = oneshot::channel();
-            self.shutdown_signals.push(tx);
-            self.runtime.spawn(
-                spawn(
-                    station.clone(),
-                    self.config.srs_port,
-                    config,
-                    self.exporter.clone(),
-                    rx,
-                )
-                .map(|_| ()),
-            );
+        for station in self.stations.clone() {
+            self.start_station(&station);
         }
 
         log::debug!("Started all ATIS stations");
@@ -161,6 +139,265 @@ impl Datis {
         Ok(())
     }
 
+    pub fn status(&self) -> &StatusRegistry {
+        &self.status
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn stations(&self) -> &[Station] {
+        &self.stations
+    }
+
+    pub fn station(&self, name: &str) -> Option<&Station> {
+        self.stations.iter().find(|s| s.name == name)
+    }
+
+    /// Add a new station. The station is started right away, unless DATIS is currently paused.
+    pub fn add_station(&mut self, station: Station) -> Result<(), Error> {
+        if self.station(&station.name).is_some() {
+            return Err(Error::StationExists(station.name));
+        }
+
+        log::info!("Adding station {}", station.name);
+        station.warn_if_language_mismatch();
+        self.status.register(&station);
+        if self.started {
+            self.start_station(&station);
+        }
+        self.stations.push(station);
+
+        Ok(())
+    }
+
+    /// Stop and remove the station with the given `name`.
+    pub fn remove_station(&mut self, name: &str) -> Result<Station, Error> {
+        let ix = self
+            .stations
+            .iter()
+            .position(|s| s.name == name)
+            .ok_or_else(|| Error::StationNotFound(name.to_string()))?;
+
+        log::info!("Removing station {}", name);
+        self.stop_station(name);
+        self.status.remove(name);
+
+        Ok(self.stations.remove(ix))
+    }
+
+    /// Apply `update` to the station with the given `name` and restart it for the changes to take
+    /// effect. The latest report of the station is kept in the status until a new one is generated.
+    pub fn update_station(
+        &mut self,
+        name: &str,
+        update: impl FnOnce(&mut Station) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let station = self
+            .stations
+            .iter_mut()
+            .find(|s| s.name == name)
+            .ok_or_else(|| Error::StationNotFound(name.to_string()))?;
+        update(station)?;
+        // the name is used to identify the station, so it must not be changed
+        station.name = name.to_string();
+
+        let station = station.clone();
+        station.warn_if_language_mismatch();
+        self.status.register(&station);
+        self.restart_station(name)
+    }
+
+    /// Apply `update` to the station with the given `name` without restarting it. A running station
+    /// picks up the changes after its current broadcast and keeps its state (e.g. the runway in use
+    /// and the rotation of the information letter). Only suitable for changes of the report, use
+    /// `update_station` for anything affecting the connection to SRS (e.g. the frequency).
+    pub fn update_running_station(
+        &mut self,
+        name: &str,
+        update: impl FnOnce(&mut Station) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let station = self
+            .stations
+            .iter_mut()
+            .find(|s| s.name == name)
+            .ok_or_else(|| Error::StationNotFound(name.to_string()))?;
+        update(station)?;
+        station.name = name.to_string();
+
+        if let Some(updates) = self.station_updates.get(name) {
+            let _ = updates.send(station.clone());
+        }
+        Ok(())
+    }
+
+    pub fn set_frequency(&mut self, name: &str, freq: u64) -> Result<(), Error> {
+        self.update_station(name, |station| {
+            station.freq = freq;
+            Ok(())
+        })
+    }
+
+    pub fn set_voice(&mut self, name: &str, tts: TextToSpeechProvider) -> Result<(), Error> {
+        self.update_station(name, |station| {
+            station.tts = tts;
+            Ok(())
+        })
+    }
+
+    /// Change the message of a custom broadcast station.
+    pub fn set_message(&mut self, name: &str, message: impl Into<String>) -> Result<(), Error> {
+        self.update_running_station(name, |station| match &mut station.transmitter {
+            Transmitter::Custom(custom) => {
+                custom.message = message.into();
+                Ok(())
+            }
+            _ => Err(Error::NotCustomBroadcast(station.name.clone())),
+        })
+    }
+
+    /// Override the active runway of an airfield station, or reset it to be derived from the wind
+    /// again if `None`.
+    pub fn set_active_runway(&mut self, name: &str, runway: Option<String>) -> Result<(), Error> {
+        self.update_running_station(name, |station| match &mut station.transmitter {
+            Transmitter::Airfield(airfield) => {
+                airfield.active_rwy_override = runway;
+                Ok(())
+            }
+            _ => Err(Error::NotAnAirfield(station.name.clone())),
+        })
+    }
+
+    /// Mark a runway of an airfield station as closed (e.g. after it has been bombed), or open it
+    /// again. Closing one end of a runway also closes the other one.
+    pub fn set_runway_closed(
+        &mut self,
+        name: &str,
+        runway: &str,
+        closed: bool,
+    ) -> Result<(), Error> {
+        self.update_running_station(name, |station| match &mut station.transmitter {
+            Transmitter::Airfield(airfield) => {
+                airfield.set_runway_closed(runway, closed);
+                Ok(())
+            }
+            _ => Err(Error::NotAnAirfield(station.name.clone())),
+        })
+    }
+
+    /// Override the information letter (`A` to `Z`) of an airfield station, or reset it to be
+    /// rotated automatically again if `None`.
+    pub fn set_info_letter(&mut self, name: &str, letter: Option<char>) -> Result<(), Error> {
+        if let Some(letter) = letter.filter(|c| !c.is_ascii_alphabetic()) {
+            return Err(Error::InvalidInfoLetter(letter));
+        }
+
+        self.update_running_station(name, |station| match &mut station.transmitter {
+            Transmitter::Airfield(airfield) => {
+                airfield.info_ltr_override = letter.map(|c| c.to_ascii_uppercase());
+                Ok(())
+            }
+            _ => Err(Error::NotAnAirfield(station.name.clone())),
+        })
+    }
+
+    /// Restart the station with the given `name`, while all other stations keep running.
+    pub fn restart_station(&mut self, name: &str) -> Result<(), Error> {
+        let station = self
+            .station(name)
+            .cloned()
+            .ok_or_else(|| Error::StationNotFound(name.to_string()))?;
+
+        self.stop_station(name);
+        if self.started {
+            self.start_station(&station);
+        }
+
+        Ok(())
+    }
+
+    fn start_station(&mut self, station: &Station) {
+        // the voice of the station, followed by its own and the global fallback voices
+        let mut voices: Vec<(TextToSpeechProvider, Arc<dyn TextToSpeech>)> = Vec::new();
+        let mut first_err = None;
+        for tts in std::iter::once(&station.tts)
+            .chain(&station.fallback_tts)
+            .chain(&self.config.fallback_voices)
+        {
+            if voices.iter().any(|(t, _)| t == tts) {
+                continue;
+            }
+            match tts.engine(&self.config) {
+                Ok(engine) => voices.push((tts.clone(), engine)),
+                Err(err) => {
+                    log::warn!("Skipping voice {:?} of {}: {}", tts, station.name, err);
+                    first_err.get_or_insert(err);
+                }
+            }
+        }
+        if voices.is_empty() {
+            let err = first_err.unwrap_or_default();
+            log::error!("Cannot start {}: {}", station.name, err);
+            self.status.set_error(&station.name, err);
+            return;
+        }
+
+        let (tx, rx) = oneshot::channel();
+        self.station_signals.insert(station.name.clone(), tx);
+        let (updates_tx, updates) = watch::channel(station.clone());
+        self.station_updates
+            .insert(station.name.clone(), updates_tx);
+        let task = self.status.start_task(&station.name);
+        self.runtime.spawn(
+            spawn(
+                updates,
+                self.config.srs_port,
+                SpeechConfig {
+                    voices,
+                    cooldowns: self.tts_cooldowns.clone(),
+                    cooldown: Duration::from_secs(self.config.tts_cooldown),
+                    cache: self.tts_cache.clone(),
+                    prosody: station.prosody.or(self.config.prosody),
+                    lexicon: self.lexicon.clone(),
+                    effects: station
+                        .radio_effects
+                        .unwrap_or(self.config.radio_effects.enabled)
+                        .then_some(self.config.radio_effects),
+                },
+                self.exporter.clone(),
+                self.status.clone(),
+                task,
+                rx,
+            )
+            .map(|_| ()),
+        );
+    }
+
+    fn stop_station(&mut self, name: &str) {
+        if let Some(signal) = self.station_signals.remove(name) {
+            let _ = signal.send(());
+        }
+        self.station_updates.remove(name);
+    }
+
+    #[cfg(feature = "http")]
+    fn start_status_server(&mut self, addr: SocketAddr) {
+        let (tx, rx) = oneshot::channel();
+        self.shutdown_signals.push(tx);
+        let status = self.status.clone();
+        self.runtime.spawn(async move {
+            if let Err(err) = http::serve(addr, status, rx).await {
+                log::error!("Status server failed: {}", err);
+            }
+        });
+    }
+
+    #[cfg(not(feature = "http"))]
+    fn start_status_server(&mut self, _addr: SocketAddr) {
+        log::warn!("Cannot start status server, DATIS has been built without the `http` feature");
+    }
+
     pub fn stop(mut self) -> Result<(), Error> {
         self.pause()
     }
@@ -172,6 +409,11 @@ impl Datis {
     pub fn pause(&mut self) -> Result<(), Error> {
         log::debug!("Shutting down all stations");
 
+        for (_, signal) in self.station_signals.drain() {
+            let _ = signal.send(());
+        }
+        self.station_updates.clear();
+
         let shutdown_signals = mem::take(&mut self.shutdown_signals);
         for signal in shutdown_signals {
             let _ = signal.send(());
@@ -187,37 +429,165 @@ impl Datis {
 pub enum Error {
     #[error("Runtime error: {0}")]
     Runtime(#[from] std::io::Error),
+    #[error("Station {0} not found")]
+    StationNotFound(String),
+    #[error("Station {0} already exists")]
+    StationExists(String),
+    #[error("Station {0} is not a custom broadcast")]
+    NotCustomBroadcast(String),
+    #[error("Station {0} is not an airfield")]
+    NotAnAirfield(String),
+    #[error("Invalid information letter `{0}`, expected A to Z")]
+    InvalidInfoLetter(char),
+}
+
+/// Everything needed to turn the reports of a station into the Opus frames sent to SRS.
+struct SpeechConfig {
+    /// The voices to try in order, starting with the voice of the station.
+    voices: Vec<(TextToSpeechProvider, Arc<dyn TextToSpeech>)>,
+    cooldowns: TtsCooldowns,
+    /// Time a failed voice is skipped in favor of the next voice.
+    cooldown: Duration,
+    cache: Option<AudioCache>,
+    prosody: Prosody,
+    /// Pronunciations applied to the spoken reports.
+    lexicon: Option<Arc<Lexicon>>,
+    effects: Option<RadioEffects>,
+}
+
+impl SpeechConfig {
+    /// Synthesizes `ssml` with the first voice whose provider is not on cooldown and falls back to
+    /// the next voice if it fails. A cached report of a voice is used before the next voice is
+    /// tried. If all providers are on cooldown, all voices are tried anyway.
+    async fn synthesize(
+        &self,
+        station: &Station,
+        ssml: &str,
+        status: &StatusRegistry,
+    ) -> Result<Vec<Vec<u8>>, anyhow::Error> {
+        let effects = self.effects.as_ref();
+        let all_on_cooldown = self
+            .voices
+            .iter()
+            .all(|(tts, _)| self.cooldowns.is_active(tts));
+        let mut last_err = None;
+        for (tts, engine) in &self.voices {
+            let cached = match &self.cache {
+                Some(cache) => cache.get(tts, ssml, self.prosody, effects).await,
+                None => None,
+            };
+            let frames = if let Some(frames) = cached {
+                log::debug!("{} report found in TTS cache", station.name);
+                frames
+            } else if !all_on_cooldown && self.cooldowns.is_active(tts) {
+                log::debug!("Skipping voice {:?} of {} (on cooldown)", tts, station.name);
+                continue;
+            } else {
+                match self.synthesize_with(engine.as_ref(), ssml).await {
+                    Ok(frames) => {
+                        if let Some(cache) = &self.cache {
+                            if let Err(err) =
+                                cache.put(tts, ssml, self.prosody, effects, &frames).await
+                            {
+                                log::error!("Error caching TTS of {}: {}", station.name, err);
+                            }
+                        }
+                        frames
+                    }
+                    Err(err) => {
+                        log::error!(
+                            "TTS of {} with voice {:?} failed (skipping {} for {}s): {:?}",
+                            station.name,
+                            tts,
+                            tts.prefix(),
+                            self.cooldown.as_secs(),
+                            err
+                        );
+                        status.set_error(
+                            &station.name,
+                            format!("TTS with voice {:?} failed: {}", tts, err),
+                        );
+                        self.cooldowns.start(tts, self.cooldown);
+                        last_err = Some(err);
+                        continue;
+                    }
+                }
+            };
+
+            if tts != &station.tts {
+                log::warn!("{} is using the fallback voice {:?}", station.name, tts);
+            }
+            status.set_active_tts(&station.name, tts);
+            status.clear_error(&station.name);
+            return Ok(frames);
+        }
+
+        Err(last_err.unwrap_or_else(|| anyhow!("No voice available for {}", station.name)))
+    }
+
+    async fn synthesize_with(
+        &self,
+        engine: &dyn TextToSpeech,
+        ssml: &str,
+    ) -> Result<Vec<Vec<u8>>, anyhow::Error> {
+        let mut samples = engine.text_to_speech(ssml, self.prosody).await?;
+        if let Some(volume) = self.prosody.volume {
+            audio::amplify(&mut samples, volume as f64);
+        }
+        audio::encode(samples, self.effects).await
+    }
 }
 
 async fn spawn(
-    station: Station,
+    updates: watch::Receiver<Station>,
     port: u16,
-    tts_config: TextToSpeechConfig,
+    speech: SpeechConfig,
     exporter: Option<ReportExporter>,
+    status: StatusRegistry,
+    task: u64,
     shutdown_signal: oneshot::Receiver<()>,
 ) {
+    let station = updates.borrow().clone();
     let name = format!("ATIS {}", station.name);
     log::debug!("Connecting {} to 127.0.0.1:{}", name, port);
 
     let mut shutdown_signal = shutdown_signal.fuse();
     loop {
         let (tx, rx) = oneshot::channel();
-        let mut r = Box::pin(run(&station, port, &tts_config, exporter.as_ref(), rx)).fuse();
+        status.set_state(&station.name, task, ConnectionState::Connecting);
+        let mut r = Box::pin(run(
+            updates.clone(),
+            port,
+            &speech,
+            exporter.as_ref(),
+            &status,
+            task,
+            rx,
+        ))
+        .fuse();
 
         select! {
             result = r => {
+                status.set_state(&station.name, task, ConnectionState::Disconnected);
                 if let Err(err) = result
                 {
                     log::error!("{} failed: {:?}", name, err);
+                    status.set_error(&station.name, &err);
                 }
 
                 log::info!("Restarting ATIS {} in 60 seconds ...", station.name);
-                // TODO: handle shutdown signal during the delay
-                sleep(Duration::from_secs(60)).await;
+                select! {
+                    _ = sleep(Duration::from_secs(60)).fuse() => {}
+                    _ = shutdown_signal => {
+                        status.set_state(&station.name, task, ConnectionState::Stopped);
+                        break;
+                    }
+                }
             }
             _ = shutdown_signal => {
                 let _ = tx.send(());
                 let _ = r.await; // run until stopped
+                status.set_state(&station.name, task, ConnectionState::Stopped);
                 break;
             }
         }
@@ -225,47 +595,68 @@ async fn spawn(
 }
 
 async fn run(
-    station: &Station,
+    mut updates: watch::Receiver<Station>,
     port: u16,
-    tts_config: &TextToSpeechConfig,
+    speech: &SpeechConfig,
     exporter: Option<&ReportExporter>,
+    status: &StatusRegistry,
+    task: u64,
     shutdown_signal: oneshot::Receiver<()>,
 ) -> Result<(), anyhow::Error> {
+    let station = updates.borrow_and_update().clone();
     let name = format!("ATIS {}", station.name);
-    let mut client = Client::new(&name, station.freq, Coalition::Blue);
-    match &station.transmitter {
-        Transmitter::Airfield(airfield) => {
-            let pos = match &station.ipc {
-                station::MissionInterface::Static => {
-                    let _ = airfield;
-                    LatLngPosition::default()
-                }
-                #[cfg(feature = "ipc")]
-                station::MissionInterface::Ipc(ipc) => ipc.to_lat_lng(&airfield.position).await?,
-            };
-            client.set_position(pos).await;
-            // TODO: set unit?
-        }
-        Transmitter::Carrier(unit) => {
-            client.set_unit(unit.unit_id, &unit.unit_name);
-        }
-        Transmitter::Custom(custom) => {
-            client.set_unit(custom.unit_id, &custom.unit_name);
-        }
-        Transmitter::Weather(weather) => {
-            client.set_unit(weather.unit_id, &weather.unit_name);
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), port);
+
+    // SRS clients belong to a single coalition, so a station broadcasting to multiple coalitions
+    // connects once per coalition
+    let mut sinks = Vec::with_capacity(station.coalitions.len());
+    let mut streams = Vec::with_capacity(station.coalitions.len());
+    let mut positions = Vec::with_capacity(station.coalitions.len());
+    let mut shutdowns = Vec::with_capacity(station.coalitions.len());
+    for coalition in &station.coalitions {
+        let mut client = Client::new(&name, station.freq, *coalition);
+        match &station.transmitter {
+            Transmitter::Airfield(airfield) => {
+                let pos = match &station.ipc {
+                    station::MissionInterface::Static => {
+                        let _ = airfield;
+                        LatLngPosition::default()
+                    }
+                    #[cfg(feature = "ipc")]
+                    station::MissionInterface::Ipc(ipc) => {
+                        ipc.to_lat_lng(&airfield.position).await?
+                    }
+                };
+                client.set_position(pos).await;
+                // TODO: set unit?
+            }
+            Transmitter::Carrier(unit) => {
+                client.set_unit(unit.unit_id, &unit.unit_name);
+            }
+            Transmitter::Custom(custom) => {
+                client.set_unit(custom.unit_id, &custom.unit_name);
+            }
+            Transmitter::Weather(weather) => {
+                client.set_unit(weather.unit_id, &weather.unit_name);
+            }
         }
-    }
-    let pos = client.position_handle();
+        positions.push(client.position_handle());
 
-    let (tx, rx) = oneshot::channel();
+        let (tx, rx) = oneshot::channel();
+        shutdowns.push(tx);
 
-    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), port);
-    let (sink, stream) = client.start(addr, None, rx).await?.split();
+        let (sink, stream) = client.start(addr, None, rx).await?.split();
+        sinks.push(sink);
+        streams.push(stream);
+    }
+    status.set_state(&station.name, task, ConnectionState::Connected);
 
-    let mut stream = stream.fuse();
+    let mut stream = futures::stream::select_all(streams).fuse();
     let mut shutdown_signal = shutdown_signal.fuse();
-    let mut broadcast = Box::pin(audio_broadcast(sink, station, pos, tts_config, exporter)).fuse();
+    let mut broadcast = Box::pin(audio_broadcast(
+        sinks, updates, positions, speech, exporter, status,
+    ))
+    .fuse();
 
     loop {
         select! {
@@ -281,8 +672,10 @@ async fn run(
             }
 
             _ = shutdown_signal => {
-                // shutdown socket
-                let _ =tx.send(());
+                // shutdown sockets
+                for tx in shutdowns.drain(..) {
+                    let _ = tx.send(());
+                }
 
                 break;
             }
@@ -295,12 +688,14 @@ async fn run(
 }
 
 async fn audio_broadcast(
-    mut sink: SplitSink<VoiceStream, Vec<u8>>,
-    station: &Station,
-    position: Arc<RwLock<LatLngPosition>>,
-    tts_config: &TextToSpeechConfig,
+    mut sinks: Vec<SplitSink<VoiceStream, Vec<u8>>>,
+    mut updates: watch::Receiver<Station>,
+    positions: Vec<Arc<RwLock<LatLngPosition>>>,
+    speech: &SpeechConfig,
     exporter: Option<&ReportExporter>,
+    status: &StatusRegistry,
 ) -> Result<(), anyhow::Error> {
+    let mut station = updates.borrow_and_update().clone();
     let interval = match &station.transmitter {
         Transmitter::Weather(_) => {
             Duration::from_secs(60 * 15) // 15min
@@ -312,12 +707,23 @@ async fn audio_broadcast(
     let mut interval_start;
     let mut report_ix = 0;
     let mut previous_report = "".to_string();
+    let mut previous_rwy = None;
     let mut frames = Vec::new();
 
     loop {
         interval_start = Instant::now();
+        if updates.has_changed().unwrap_or(false) {
+            station = updates.borrow_and_update().clone();
+        }
 
-        let report = match station.generate_report(report_ix).await? {
+        let report = match station
+            .generate_report(
+                report_ix,
+                previous_rwy.as_deref(),
+                speech.lexicon.as_deref(),
+            )
+            .await?
+        {
             Some(report) => report,
             None => {
                 log::debug!(
@@ -325,21 +731,27 @@ async fn audio_broadcast(
                     station.name
                 );
                 // postpone the next playback of the report by some seconds ...
+                status.set_next_broadcast(&station.name, Duration::from_secs(30));
                 sleep(Duration::from_secs(30)).await;
                 continue;
             }
         };
         if let Some(exporter) = exporter {
-            if let Err(err) = exporter.export(&station.name, report.textual).await {
+            if let Err(err) = exporter.export(&station, &report).await {
                 log::error!("Error exporting report: {}", err);
             }
         }
+        status.set_report(&station, &report);
+        previous_rwy = report
+            .details
+            .as_ref()
+            .and_then(|details| details.active_runway.clone());
 
         log::debug!("{} Position: {:?}", station.name, report.position);
 
-        {
+        for position in &positions {
             let mut pos = position.write().await;
-            *pos = report.position;
+            *pos = report.position.clone();
         }
 
         report_ix += 1;
@@ -348,20 +760,7 @@ async fn audio_broadcast(
         if report.spoken != previous_report {
             log::debug!("{} report has changed -> executing TTS", station.name);
             // only to TTS if the report has changed from the previous iteration
-            frames = match tts_config {
-                TextToSpeechConfig::GoogleCloud(config) => {
-                    gcloud::text_to_speech(&report.spoken, config).await?
-                }
-                TextToSpeechConfig::AmazonWebServices(config) => {
-                    aws::text_to_speech(&report.spoken, config).await?
-                }
-                TextToSpeechConfig::Windows(config) => {
-                    win::text_to_speech(&report.spoken, config).await?
-                }
-                TextToSpeechConfig::AzureCognitiveServices(config) => {
-                    azure::text_to_speech(&report.spoken, config).await?
-                }
-            };
+            frames = speech.synthesize(&station, &report.spoken, status).await?;
         }
         previous_report = report.spoken;
 
@@ -375,7 +774,9 @@ async fn audio_broadcast(
             let start = Instant::now();
 
             for (i, frame) in frames.iter().enumerate() {
-                sink.send(frame.to_vec()).await?;
+                for sink in &mut sinks {
+                    sink.send(frame.to_vec()).await?;
+                }
 
                 // wait for the current ~playtime before sending the next package
                 let playtime = Duration::from_millis((i as u64 + 1) * 20); // 20m per frame count
@@ -388,15 +789,22 @@ async fn audio_broadcast(
             // postpone the next playback of the report by some seconds ...
             match &station.transmitter {
                 Transmitter::Airfield(_) | Transmitter::Weather(_) => {
+                    status.set_next_broadcast(&station.name, Duration::from_secs(3));
                     sleep(Duration::from_secs(3)).await;
+                    // create a new report right away if the station has been changed
+                    if updates.has_changed().unwrap_or(false) {
+                        break;
+                    }
                 }
                 Transmitter::Carrier(_) => {
+                    status.set_next_broadcast(&station.name, Duration::from_secs(10));
                     sleep(Duration::from_secs(10)).await;
                     // always create a new report for carriers, since they are usually
                     // constantly moving
                     break;
                 }
                 Transmitter::Custom(_) => {
+                    status.set_next_broadcast(&station.name, Duration::from_secs(1));
                     sleep(Duration::from_secs(1)).await;
                     // always create a new report to get an update on the position of the
                     // broadcasting unit
@@ -406,3 +814,156 @@ async fn audio_broadcast(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::station::{Coalition, Custom, MissionInterface, Position};
+    use crate::tts::local::{self, LocalConfig};
+
+    fn broadcast(name: &str) -> Station {
+        Station {
+            name: name.to_string(),
+            freq: 251_000_000,
+            tts: TextToSpeechProvider::default(),
+            fallback_tts: Vec::new(),
+            prosody: Prosody::default(),
+            transmitter: Transmitter::Custom(Custom {
+                position: Some(Position::default()),
+                unit_id: 42,
+                unit_name: "Soldier".to_string(),
+                message: "Hello world".to_string(),
+            }),
+            ipc: MissionInterface::Static,
+            coalitions: vec![Coalition::Blue],
+            template: None,
+            radio_effects: None,
+        }
+    }
+
+    #[test]
+    fn test_station_management() {
+        let mut datis = Datis::new(vec![broadcast("One")], Config::default()).unwrap();
+
+        datis.add_station(broadcast("Two")).unwrap();
+        assert!(matches!(
+            datis.add_station(broadcast("One")),
+            Err(Error::StationExists(_))
+        ));
+        assert_eq!(datis.stations().len(), 2);
+        assert!(datis.status().get("Two").is_some());
+
+        datis.set_frequency("Two", 255_000_000).unwrap();
+        datis.set_message("Two", "Goodbye").unwrap();
+        let two = datis.station("Two").unwrap();
+        assert_eq!(two.freq, 255_000_000);
+        assert!(matches!(&two.transmitter, Transmitter::Custom(c) if c.message == "Goodbye"));
+        assert_eq!(datis.status().get("Two").unwrap().freq, 255_000_000);
+        assert!(matches!(
+            datis.set_info_letter("Two", Some('1')),
+            Err(Error::InvalidInfoLetter('1'))
+        ));
+        assert!(matches!(
+            datis.set_info_letter("Two", Some('b')),
+            Err(Error::NotAnAirfield(_))
+        ));
+
+        assert_eq!(datis.remove_station("One").unwrap().name, "One");
+        assert!(matches!(
+            datis.remove_station("One"),
+            Err(Error::StationNotFound(_))
+        ));
+        assert!(datis.status().get("One").is_none());
+        assert_eq!(datis.stations().len(), 1);
+    }
+
+    #[test]
+    fn test_superseded_task_state() {
+        let station = broadcast("One");
+        let status = StatusRegistry::default();
+        status.register(&station);
+
+        let old = status.start_task("One");
+        status.set_state("One", old, ConnectionState::Connected);
+        let new = status.start_task("One");
+        status.set_state("One", new, ConnectionState::Connecting);
+        // the old task finishing its shutdown must not override the state of the new one
+        status.set_state("One", old, ConnectionState::Stopped);
+        assert_eq!(
+            status.get("One").unwrap().state,
+            ConnectionState::Connecting
+        );
+
+        // updating the settings keeps the state
+        status.register(&Station {
+            freq: 255_000_000,
+            ..station
+        });
+        let one = status.get("One").unwrap();
+        assert_eq!(one.state, ConnectionState::Connecting);
+        assert_eq!(one.freq, 255_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_tts_failover() {
+        let espeak = |path: &str| {
+            let tts = TextToSpeechProvider::Local {
+                voice: local::VoiceKind::Espeak {
+                    voice: Some(path.to_string()),
+                },
+            };
+            let engine: Arc<dyn TextToSpeech> = Arc::new(LocalConfig {
+                voice: local::VoiceKind::Espeak { voice: None },
+                piper: None,
+                espeak: Some(std::path::PathBuf::from(path)),
+            });
+            (tts, engine)
+        };
+
+        let piper = {
+            let voice = local::VoiceKind::Piper {
+                model: "/nonexistent/voice.onnx".to_string(),
+            };
+            let engine: Arc<dyn TextToSpeech> = Arc::new(LocalConfig {
+                voice: voice.clone(),
+                piper: Some(std::path::PathBuf::from("/nonexistent/piper")),
+                espeak: None,
+            });
+            (TextToSpeechProvider::Local { voice }, engine)
+        };
+
+        let station = broadcast("One");
+        let status = StatusRegistry::default();
+        status.register(&station);
+        let speech = SpeechConfig {
+            // the second eSpeak voice is skipped, as its provider is on cooldown after the first
+            // one failed
+            voices: vec![
+                espeak("/nonexistent/espeak-a"),
+                espeak("/nonexistent/espeak-b"),
+                piper,
+            ],
+            cooldowns: TtsCooldowns::default(),
+            cooldown: Duration::from_secs(60),
+            cache: None,
+            prosody: Prosody::default(),
+            lexicon: None,
+            effects: None,
+        };
+
+        assert!(speech.synthesize(&station, "Hello", &status).await.is_err());
+        for (tts, _) in &speech.voices {
+            assert!(speech.cooldowns.is_active(tts));
+        }
+        assert!(
+            status
+                .get("One")
+                .unwrap()
+                .last_error
+                .unwrap()
+                .starts_with("TTS with voice Piper")
+        );
+    }
+}
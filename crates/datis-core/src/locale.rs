@@ -0,0 +1,841 @@
+//! Languages reports can be generated in, including their phrasing, number pronunciation and
+//! phonetic alphabet.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::template::{DEFAULT_AIRFIELD_TEMPLATE, DEFAULT_WEATHER_TEMPLATE};
+use crate::weather::{CloudCoverage, WeatherCondition};
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+    Fr,
+    Ru,
+    Es,
+    Zh,
+}
+
+/// The wording of all parts of a report. Each `{}` is replaced with the respective value (see
+/// [`fill`]), in the order documented for each phrase.
+pub struct Phrases {
+    pub airfield_template: &'static str,
+    pub weather_template: &'static str,
+    /// runway
+    pub runway_in_use: &'static str,
+    /// arrival runway, departure runway
+    pub runway_arr_dep: &'static str,
+    /// left, center, right
+    pub runway_sides: [&'static str; 3],
+    pub and: &'static str,
+    /// frequency
+    pub traffic: &'static str,
+    pub wind_word: &'static str,
+    pub wind_word_spoken: &'static str,
+    /// wind word, direction, speed
+    pub wind: &'static str,
+    /// gust speed
+    pub gusting: &'static str,
+    /// from direction, to direction
+    pub variable: &'static str,
+    /// speed
+    pub headwind: &'static str,
+    /// speed
+    pub tailwind: &'static str,
+    /// speed
+    pub crosswind_left: &'static str,
+    /// speed
+    pub crosswind_right: &'static str,
+    /// runway, components
+    pub runway_components: &'static str,
+    /// limit
+    pub caution_crosswind: &'static str,
+    /// limit
+    pub caution_tailwind: &'static str,
+    pub winds_aloft: &'static str,
+    /// altitude, direction, speed
+    pub wind_aloft: &'static str,
+    /// altitude in meters, direction, speed
+    pub wind_aloft_meters: &'static str,
+    /// altitude, coverage
+    pub ceiling: &'static str,
+    /// altitude in meters, coverage
+    pub ceiling_meters: &'static str,
+    /// clear, few, scattered, broken, overcast
+    pub coverage: [&'static str; 5],
+    /// slight rain, rain, heavy rain, thunderstorm, fog, dust storm
+    pub conditions: [&'static str; 6],
+    /// visibility
+    pub visibility: &'static str,
+    /// temperature
+    pub temperature: &'static str,
+    /// dew point
+    pub dew_point: &'static str,
+    /// QNH in inHg
+    pub altimeter: &'static str,
+    /// QNH in hPa
+    pub hectopascal: &'static str,
+    /// QFE in inHg, optional break, QFE in hPa
+    pub qfe: &'static str,
+    /// flight level
+    pub transition_level: &'static str,
+    /// flight level
+    pub transition_level_spoken: &'static str,
+    /// density altitude
+    pub density_altitude: &'static str,
+    /// density altitude in meters
+    pub density_altitude_meters: &'static str,
+    pub remarks: &'static str,
+    /// QNH in the unit of the station
+    pub qnh: &'static str,
+    /// QFE in the unit of the station
+    pub qfe_single: &'static str,
+    /// Units, the value is filled in for `{}`.
+    pub knots: &'static str,
+    pub kilometers_per_hour: &'static str,
+    pub nautical_miles: &'static str,
+    pub statute_miles: &'static str,
+    pub kilometers: &'static str,
+    pub meters: &'static str,
+    pub millimeters: &'static str,
+}
+
+impl Locale {
+    /// All languages, in the order of their declaration.
+    pub const ALL: [Locale; 6] = [
+        Locale::En,
+        Locale::De,
+        Locale::Fr,
+        Locale::Ru,
+        Locale::Es,
+        Locale::Zh,
+    ];
+
+    /// The language of the `<speak />` element of the SSML reports.
+    pub fn ssml_lang(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+            Locale::Fr => "fr",
+            Locale::Ru => "ru",
+            Locale::Es => "es",
+            Locale::Zh => "zh",
+        }
+    }
+
+    pub fn phrases(self) -> &'static Phrases {
+        match self {
+            Locale::En => &EN,
+            Locale::De => &DE,
+            Locale::Fr => &FR,
+            Locale::Ru => &RU,
+            Locale::Es => &ES,
+            Locale::Zh => &ZH,
+        }
+    }
+
+    /// Pronounce each digit of `n` separately, if `pronounce` is `true`.
+    pub fn pronounce_number<S>(self, n: S, pronounce: bool) -> String
+    where
+        S: ToString,
+    {
+        let (digits, decimal) = match self {
+            // keep the aviation specific pronunciation (e.g. `NINER`)
+            Locale::En => return crate::utils::pronounce_number(n, pronounce),
+            Locale::De => (DE_DIGITS, "Komma"),
+            Locale::Fr => (FR_DIGITS, "décimal"),
+            Locale::Ru => (RU_DIGITS, "запятая"),
+            Locale::Es => (ES_DIGITS, "decimal"),
+            Locale::Zh => (ZH_DIGITS, "点"),
+        };
+        if !pronounce {
+            return n.to_string();
+        }
+
+        n.to_string()
+            .chars()
+            .map(|c| match c {
+                '.' => decimal.to_string(),
+                '0'..='9' => digits[c.to_digit(10).unwrap() as usize].to_string(),
+                _ => c.to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /// Get the word of the phonetic alphabet for the letter number `idx` (wrapping after Z).
+    pub fn phonetic_letter(self, idx: usize) -> &'static str {
+        let alphabet = match self {
+            Locale::Ru => RU_PHONETIC_ALPHABET,
+            _ => ICAO_PHONETIC_ALPHABET,
+        };
+        alphabet[idx % alphabet.len()]
+    }
+
+    pub fn cloud_coverage(self, coverage: &CloudCoverage) -> &'static str {
+        let coverage_phrases = &self.phrases().coverage;
+        match coverage {
+            CloudCoverage::Clear => coverage_phrases[0],
+            CloudCoverage::Few => coverage_phrases[1],
+            CloudCoverage::Scattered => coverage_phrases[2],
+            CloudCoverage::Broken => coverage_phrases[3],
+            CloudCoverage::Overcast => coverage_phrases[4],
+        }
+    }
+
+    pub fn weather_condition(self, condition: &WeatherCondition) -> &'static str {
+        let conditions = &self.phrases().conditions;
+        match condition {
+            WeatherCondition::SlightRain => conditions[0],
+            WeatherCondition::Rain => conditions[1],
+            WeatherCondition::HeavyRain => conditions[2],
+            WeatherCondition::Thunderstorm => conditions[3],
+            WeatherCondition::Fog => conditions[4],
+            WeatherCondition::DustStorm => conditions[5],
+        }
+    }
+}
+
+/// Replace the `{}`s in `phrase` with `args` in order.
+pub fn fill(phrase: &str, args: &[&str]) -> String {
+    let mut result = String::with_capacity(phrase.len());
+    let mut args = args.iter();
+    let mut parts = phrase.split("{}");
+    if let Some(first) = parts.next() {
+        result += first;
+    }
+    for part in parts {
+        result += args.next().copied().unwrap_or_default();
+        result += part;
+    }
+    result
+}
+
+impl FromStr for Locale {
+    type Err = LocaleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s
+            .to_lowercase()
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+        {
+            "en" => Ok(Locale::En),
+            "de" => Ok(Locale::De),
+            "fr" => Ok(Locale::Fr),
+            "ru" => Ok(Locale::Ru),
+            "es" => Ok(Locale::Es),
+            "zh" | "cmn" => Ok(Locale::Zh),
+            _ => Err(LocaleError(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.ssml_lang().to_uppercase())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Unsupported language `{0}`")]
+pub struct LocaleError(String);
+
+static ICAO_PHONETIC_ALPHABET: &[&str] = &[
+    "Alpha", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India", "Juliett",
+    "Kilo", "Lima", "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo", "Sierra", "Tango",
+    "Uniform", "Victor", "Whiskey", "X-ray", "Yankee", "Zulu",
+];
+
+static RU_PHONETIC_ALPHABET: &[&str] = &[
+    "Альфа",
+    "Браво",
+    "Чарли",
+    "Дельта",
+    "Эхо",
+    "Фокстрот",
+    "Гольф",
+    "Отель",
+    "Индия",
+    "Джульетт",
+    "Кило",
+    "Лима",
+    "Майк",
+    "Новембер",
+    "Оскар",
+    "Папа",
+    "Квебек",
+    "Ромео",
+    "Сьерра",
+    "Танго",
+    "Юниформ",
+    "Виктор",
+    "Виски",
+    "Икс-рей",
+    "Янки",
+    "Зулу",
+];
+
+static DE_DIGITS: &[&str] = &[
+    "null", "eins", "zwo", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+];
+static FR_DIGITS: &[&str] = &[
+    "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+];
+static RU_DIGITS: &[&str] = &[
+    "ноль",
+    "один",
+    "два",
+    "три",
+    "четыре",
+    "пять",
+    "шесть",
+    "семь",
+    "восемь",
+    "девять",
+];
+static ES_DIGITS: &[&str] = &[
+    "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+];
+// the digits as used in Chinese radio telephony (e.g. `幺` instead of `一`)
+static ZH_DIGITS: &[&str] = &["洞", "幺", "两", "三", "四", "五", "六", "拐", "八", "九"];
+
+static EN: Phrases = Phrases {
+    airfield_template: DEFAULT_AIRFIELD_TEMPLATE,
+    weather_template: DEFAULT_WEATHER_TEMPLATE,
+    runway_in_use: "Runway in use is {}",
+    runway_arr_dep: "Runway in use is {} for arrivals and {} for departures",
+    runway_sides: ["left", "center", "right"],
+    and: "and",
+    traffic: "Traffic frequency {}",
+    wind_word: "Wind",
+    wind_word_spoken: r#"<phoneme alphabet="ipa" ph="w&#618;nd">Wind</phoneme>"#,
+    wind: "{} {} at {}",
+    gusting: ", gusting {}",
+    variable: ", variable between {} and {}",
+    headwind: "headwind {}",
+    tailwind: "tailwind {}",
+    crosswind_left: "crosswind {} from the left",
+    crosswind_right: "crosswind {} from the right",
+    runway_components: "Runway {} {}",
+    caution_crosswind: "Caution, crosswind exceeds {}",
+    caution_tailwind: "Caution, tailwind exceeds {}",
+    winds_aloft: "Winds aloft",
+    wind_aloft: "{} feet, {} at {}",
+    wind_aloft_meters: "{} meters, {} at {}",
+    ceiling: "Ceiling {} {}",
+    ceiling_meters: "Ceiling {} meters {}",
+    coverage: ["Clear", "Few", "Scattered", "Broken", "Overcast"],
+    conditions: [
+        "Slight Rain",
+        "Rain",
+        "Heavy Rain",
+        "Thunderstorm",
+        "Fog",
+        "Dust Storm",
+    ],
+    visibility: "Visibility {}",
+    temperature: "Temperature {} celcius",
+    dew_point: "Dew point {} celcius",
+    altimeter: "ALTIMETER {}",
+    hectopascal: "{} hectopascal",
+    qfe: "QFE {} {}or {}",
+    transition_level: "Transition level FL{}",
+    transition_level_spoken: "Transition level flight level {}",
+    density_altitude: "Density altitude {} feet",
+    density_altitude_meters: "Density altitude {} meters",
+    remarks: "REMARKS",
+    qnh: "QNH {}",
+    qfe_single: "QFE {}",
+    knots: "{} knots",
+    kilometers_per_hour: "{} kilometers per hour",
+    nautical_miles: "{}",
+    statute_miles: "{} miles",
+    kilometers: "{} kilometers",
+    meters: "{} meters",
+    millimeters: "{} millimeters",
+};
+
+static DE: Phrases = Phrases {
+    airfield_template: "\
+Hier ist {name} Information {information}.
+{runway}.
+{traffic}.
+{wind}.
+{runway_wind}.
+{ceiling}.
+{weather}.
+{visibility}.
+{temperature}.
+{dew_point}.
+{altimeter}.
+{transition_level}.
+{remarks}.
+{hpa}.
+{qfe}.
+{density_altitude}.
+Ende Information {information}.",
+    weather_template: "\
+Hier ist Wetterstation {name} Information {information}.
+{wind}.
+{ceiling}.
+{weather}.
+{visibility}.
+{temperature}.
+{altimeter}.
+{winds_aloft}.
+BEMERKUNGEN.
+{hpa}.
+{qfe}.
+Ende Information {information}.",
+    runway_in_use: "Piste in Benutzung {}",
+    runway_arr_dep: "Piste in Benutzung {} für Landungen und {} für Starts",
+    runway_sides: ["links", "Mitte", "rechts"],
+    and: "und",
+    traffic: "Verkehrsfrequenz {}",
+    wind_word: "Wind",
+    wind_word_spoken: "Wind",
+    wind: "{} {} Grad, {}",
+    gusting: ", in Böen {}",
+    variable: ", wechselnd zwischen {} und {}",
+    headwind: "Gegenwind {}",
+    tailwind: "Rückenwind {}",
+    crosswind_left: "Seitenwind {} von links",
+    crosswind_right: "Seitenwind {} von rechts",
+    runway_components: "Piste {} {}",
+    caution_crosswind: "Achtung, Seitenwind über {}",
+    caution_tailwind: "Achtung, Rückenwind über {}",
+    winds_aloft: "Höhenwinde",
+    wind_aloft: "{} Fuß, {} Grad, {}",
+    wind_aloft_meters: "{} Meter, {} Grad, {}",
+    ceiling: "Wolkenuntergrenze {} Fuß, {}",
+    ceiling_meters: "Wolkenuntergrenze {} Meter, {}",
+    coverage: [
+        "wolkenlos",
+        "gering",
+        "aufgelockert",
+        "durchbrochen",
+        "bedeckt",
+    ],
+    conditions: [
+        "Leichter Regen",
+        "Regen",
+        "Starker Regen",
+        "Gewitter",
+        "Nebel",
+        "Staubsturm",
+    ],
+    visibility: "Sicht {}",
+    temperature: "Temperatur {} Grad",
+    dew_point: "Taupunkt {} Grad",
+    altimeter: "Höhenmesser {}",
+    hectopascal: "{} Hektopascal",
+    qfe: "QFE {} {}oder {}",
+    transition_level: "Übergangsfläche FL{}",
+    transition_level_spoken: "Übergangsfläche Flugfläche {}",
+    density_altitude: "Dichtehöhe {} Fuß",
+    density_altitude_meters: "Dichtehöhe {} Meter",
+    remarks: "BEMERKUNGEN",
+    qnh: "QNH {}",
+    qfe_single: "QFE {}",
+    knots: "{} Knoten",
+    kilometers_per_hour: "{} Kilometer pro Stunde",
+    nautical_miles: "{} Seemeilen",
+    statute_miles: "{} Meilen",
+    kilometers: "{} Kilometer",
+    meters: "{} Meter",
+    millimeters: "{} Millimeter",
+};
+
+static FR: Phrases = Phrases {
+    airfield_template: "\
+Ici {name} information {information}.
+{runway}.
+{traffic}.
+{wind}.
+{runway_wind}.
+{ceiling}.
+{weather}.
+{visibility}.
+{temperature}.
+{dew_point}.
+{altimeter}.
+{transition_level}.
+{remarks}.
+{hpa}.
+{qfe}.
+{density_altitude}.
+Fin de l'information {information}.",
+    weather_template: "\
+Ici station météo {name} information {information}.
+{wind}.
+{ceiling}.
+{weather}.
+{visibility}.
+{temperature}.
+{altimeter}.
+{winds_aloft}.
+REMARQUES.
+{hpa}.
+{qfe}.
+Fin de l'information {information}.",
+    runway_in_use: "Piste en service {}",
+    runway_arr_dep: "Piste en service {} pour les arrivées et {} pour les départs",
+    runway_sides: ["gauche", "centre", "droite"],
+    and: "et",
+    traffic: "Fréquence de trafic {}",
+    wind_word: "Vent",
+    wind_word_spoken: "Vent",
+    wind: "{} {} degrés, {}",
+    gusting: ", rafales {}",
+    variable: ", variable entre {} et {}",
+    headwind: "vent de face {}",
+    tailwind: "vent arrière {}",
+    crosswind_left: "vent de travers {} de la gauche",
+    crosswind_right: "vent de travers {} de la droite",
+    runway_components: "Piste {} {}",
+    caution_crosswind: "Attention, vent de travers supérieur à {}",
+    caution_tailwind: "Attention, vent arrière supérieur à {}",
+    winds_aloft: "Vents en altitude",
+    wind_aloft: "{} pieds, {} degrés, {}",
+    wind_aloft_meters: "{} mètres, {} degrés, {}",
+    ceiling: "Plafond {} pieds, {}",
+    ceiling_meters: "Plafond {} mètres, {}",
+    coverage: ["ciel clair", "peu nuageux", "épars", "fragmenté", "couvert"],
+    conditions: [
+        "Pluie faible",
+        "Pluie",
+        "Forte pluie",
+        "Orage",
+        "Brouillard",
+        "Tempête de poussière",
+    ],
+    visibility: "Visibilité {}",
+    temperature: "Température {} degrés",
+    dew_point: "Point de rosée {} degrés",
+    altimeter: "Altimètre {}",
+    hectopascal: "{} hectopascals",
+    qfe: "QFE {} {}ou {}",
+    transition_level: "Niveau de transition FL{}",
+    transition_level_spoken: "Niveau de transition niveau de vol {}",
+    density_altitude: "Altitude densité {} pieds",
+    density_altitude_meters: "Altitude densité {} mètres",
+    remarks: "REMARQUES",
+    qnh: "QNH {}",
+    qfe_single: "QFE {}",
+    knots: "{} nœuds",
+    kilometers_per_hour: "{} kilomètres par heure",
+    nautical_miles: "{} milles nautiques",
+    statute_miles: "{} miles",
+    kilometers: "{} kilomètres",
+    meters: "{} mètres",
+    millimeters: "{} millimètres",
+};
+
+static RU: Phrases = Phrases {
+    airfield_template: "\
+Говорит {name}, информация {information}.
+{runway}.
+{traffic}.
+{wind}.
+{runway_wind}.
+{ceiling}.
+{weather}.
+{visibility}.
+{temperature}.
+{dew_point}.
+{altimeter}.
+{transition_level}.
+{remarks}.
+{hpa}.
+{qfe}.
+{density_altitude}.
+Конец информации {information}.",
+    weather_template: "\
+Говорит метеостанция {name}, информация {information}.
+{wind}.
+{ceiling}.
+{weather}.
+{visibility}.
+{temperature}.
+{altimeter}.
+{winds_aloft}.
+ПРИМЕЧАНИЯ.
+{hpa}.
+{qfe}.
+Конец информации {information}.",
+    runway_in_use: "Рабочая полоса {}",
+    runway_arr_dep: "Рабочая полоса {} для посадки и {} для взлёта",
+    runway_sides: ["левая", "центральная", "правая"],
+    and: "и",
+    traffic: "Частота движения {}",
+    wind_word: "Ветер",
+    wind_word_spoken: "Ветер",
+    wind: "{} {} градусов, {}",
+    gusting: ", порывы {}",
+    variable: ", переменный от {} до {}",
+    headwind: "встречный ветер {}",
+    tailwind: "попутный ветер {}",
+    crosswind_left: "боковой ветер {} слева",
+    crosswind_right: "боковой ветер {} справа",
+    runway_components: "Полоса {} {}",
+    caution_crosswind: "Внимание, боковой ветер более {}",
+    caution_tailwind: "Внимание, попутный ветер более {}",
+    winds_aloft: "Ветер на высотах",
+    wind_aloft: "{} футов, {} градусов, {}",
+    wind_aloft_meters: "{} метров, {} градусов, {}",
+    ceiling: "Нижняя граница облаков {} футов, {}",
+    ceiling_meters: "Нижняя граница облаков {} метров, {}",
+    coverage: [
+        "ясно",
+        "незначительная",
+        "рассеянная",
+        "значительная",
+        "сплошная",
+    ],
+    conditions: [
+        "Слабый дождь",
+        "Дождь",
+        "Сильный дождь",
+        "Гроза",
+        "Туман",
+        "Пыльная буря",
+    ],
+    visibility: "Видимость {}",
+    temperature: "Температура {} градусов",
+    dew_point: "Точка росы {} градусов",
+    altimeter: "Высотомер {}",
+    hectopascal: "{} гектопаскалей",
+    qfe: "QFE {} {}или {}",
+    transition_level: "Эшелон перехода FL{}",
+    transition_level_spoken: "Эшелон перехода {}",
+    density_altitude: "Высота по плотности {} футов",
+    density_altitude_meters: "Высота по плотности {} метров",
+    remarks: "ПРИМЕЧАНИЯ",
+    qnh: "QNH {}",
+    qfe_single: "QFE {}",
+    knots: "{} узлов",
+    kilometers_per_hour: "{} километров в час",
+    nautical_miles: "{} морских миль",
+    statute_miles: "{} миль",
+    kilometers: "{} километров",
+    meters: "{} метров",
+    millimeters: "{} миллиметров",
+};
+
+static ES: Phrases = Phrases {
+    airfield_template: "\
+Aquí {name} información {information}.
+{runway}.
+{traffic}.
+{wind}.
+{runway_wind}.
+{ceiling}.
+{weather}.
+{visibility}.
+{temperature}.
+{dew_point}.
+{altimeter}.
+{transition_level}.
+{remarks}.
+{hpa}.
+{qfe}.
+{density_altitude}.
+Fin de información {information}.",
+    weather_template: "\
+Aquí estación meteorológica {name} información {information}.
+{wind}.
+{ceiling}.
+{weather}.
+{visibility}.
+{temperature}.
+{altimeter}.
+{winds_aloft}.
+OBSERVACIONES.
+{hpa}.
+{qfe}.
+Fin de información {information}.",
+    runway_in_use: "Pista en uso {}",
+    runway_arr_dep: "Pista en uso {} para llegadas y {} para salidas",
+    runway_sides: ["izquierda", "central", "derecha"],
+    and: "y",
+    traffic: "Frecuencia de tráfico {}",
+    wind_word: "Viento",
+    wind_word_spoken: "Viento",
+    wind: "{} {} grados, {}",
+    gusting: ", ráfagas de {}",
+    variable: ", variable entre {} y {}",
+    headwind: "viento de cara {}",
+    tailwind: "viento de cola {}",
+    crosswind_left: "viento cruzado {} por la izquierda",
+    crosswind_right: "viento cruzado {} por la derecha",
+    runway_components: "Pista {} {}",
+    caution_crosswind: "Precaución, viento cruzado superior a {}",
+    caution_tailwind: "Precaución, viento de cola superior a {}",
+    winds_aloft: "Vientos en altura",
+    wind_aloft: "{} pies, {} grados, {}",
+    wind_aloft_meters: "{} metros, {} grados, {}",
+    ceiling: "Techo de nubes {} pies, {}",
+    ceiling_meters: "Techo de nubes {} metros, {}",
+    coverage: [
+        "despejado",
+        "escasas",
+        "dispersas",
+        "fragmentadas",
+        "cubierto",
+    ],
+    conditions: [
+        "Lluvia ligera",
+        "Lluvia",
+        "Lluvia fuerte",
+        "Tormenta",
+        "Niebla",
+        "Tormenta de polvo",
+    ],
+    visibility: "Visibilidad {}",
+    temperature: "Temperatura {} grados",
+    dew_point: "Punto de rocío {} grados",
+    altimeter: "Altímetro {}",
+    hectopascal: "{} hectopascales",
+    qfe: "QFE {} {}o {}",
+    transition_level: "Nivel de transición FL{}",
+    transition_level_spoken: "Nivel de transición nivel de vuelo {}",
+    density_altitude: "Altitud de densidad {} pies",
+    density_altitude_meters: "Altitud de densidad {} metros",
+    remarks: "OBSERVACIONES",
+    qnh: "QNH {}",
+    qfe_single: "QFE {}",
+    knots: "{} nudos",
+    kilometers_per_hour: "{} kilómetros por hora",
+    nautical_miles: "{} millas náuticas",
+    statute_miles: "{} millas",
+    kilometers: "{} kilómetros",
+    meters: "{} metros",
+    millimeters: "{} milímetros",
+};
+
+static ZH: Phrases = Phrases {
+    airfield_template: "\
+这里是{name}，通播{information}。
+{runway}。
+{traffic}。
+{wind}。
+{runway_wind}。
+{ceiling}。
+{weather}。
+{visibility}。
+{temperature}。
+{dew_point}。
+{altimeter}。
+{transition_level}。
+{remarks}。
+{hpa}。
+{qfe}。
+{density_altitude}。
+通播{information}结束。",
+    weather_template: "\
+这里是气象站{name}，通播{information}。
+{wind}。
+{ceiling}。
+{weather}。
+{visibility}。
+{temperature}。
+{altimeter}。
+{winds_aloft}。
+备注。
+{hpa}。
+{qfe}。
+通播{information}结束。",
+    runway_in_use: "使用跑道{}",
+    runway_arr_dep: "着陆跑道{}，起飞跑道{}",
+    runway_sides: ["左", "中", "右"],
+    and: "和",
+    traffic: "交通频率{}",
+    wind_word: "风",
+    wind_word_spoken: "风",
+    wind: "{}{}度，{}",
+    gusting: "，阵风{}",
+    variable: "，风向在{}和{}之间变化",
+    headwind: "逆风{}",
+    tailwind: "顺风{}",
+    crosswind_left: "左侧风{}",
+    crosswind_right: "右侧风{}",
+    runway_components: "跑道{}{}",
+    caution_crosswind: "注意，侧风超过{}",
+    caution_tailwind: "注意，顺风超过{}",
+    winds_aloft: "高空风",
+    wind_aloft: "{}英尺，{}度，{}",
+    wind_aloft_meters: "{}米，{}度，{}",
+    ceiling: "云底高{}英尺，{}",
+    ceiling_meters: "云底高{}米，{}",
+    coverage: ["晴", "少云", "疏云", "多云", "阴天"],
+    conditions: ["小雨", "雨", "大雨", "雷暴", "雾", "沙尘暴"],
+    visibility: "能见度{}",
+    temperature: "温度{}度",
+    dew_point: "露点{}度",
+    altimeter: "高度表{}",
+    hectopascal: "{}百帕",
+    qfe: "场压{} {}或{}",
+    transition_level: "过渡高度层FL{}",
+    transition_level_spoken: "过渡高度层{}",
+    density_altitude: "密度高度{}英尺",
+    density_altitude_meters: "密度高度{}米",
+    remarks: "备注",
+    qnh: "修正海压{}",
+    qfe_single: "场压{}",
+    knots: "{}节",
+    kilometers_per_hour: "{}公里每小时",
+    nautical_miles: "{}海里",
+    statute_miles: "{}英里",
+    kilometers: "{}公里",
+    meters: "{}米",
+    millimeters: "{}毫米",
+};
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_locale() {
+        assert_eq!(Locale::from_str("de").unwrap(), Locale::De);
+        assert_eq!(Locale::from_str("FR").unwrap(), Locale::Fr);
+        assert_eq!(Locale::from_str("ru-RU").unwrap(), Locale::Ru);
+        assert_eq!(Locale::from_str("cmn-CN").unwrap(), Locale::Zh);
+        assert!(Locale::from_str("xx").is_err());
+    }
+
+    #[test]
+    fn test_pronounce_number() {
+        assert_eq!(Locale::En.pronounce_number(2995, true), "2 NINER NINER 5");
+        assert_eq!(
+            Locale::De.pronounce_number(2995, true),
+            "zwo neun neun fünf"
+        );
+        assert_eq!(Locale::De.pronounce_number(2995, false), "2995");
+        assert_eq!(Locale::Fr.pronounce_number(1.5, true), "un décimal cinq");
+        assert_eq!(Locale::Zh.pronounce_number("07", true), "洞 拐");
+    }
+
+    #[test]
+    fn test_phonetic_letter() {
+        assert_eq!(Locale::En.phonetic_letter(0), "Alpha");
+        assert_eq!(Locale::De.phonetic_letter(27), "Bravo");
+        assert_eq!(Locale::Ru.phonetic_letter(2), "Чарли");
+    }
+
+    #[test]
+    fn test_fill() {
+        assert_eq!(
+            fill("Wind {} at {} knots", &["090", "5"]),
+            "Wind 090 at 5 knots"
+        );
+        assert_eq!(fill("{} hectopascal", &["1013"]), "1013 hectopascal");
+        assert_eq!(fill("REMARKS", &[]), "REMARKS");
+    }
+}
@@ -1,26 +1,47 @@
+use std::collections::HashMap;
 use std::fmt::Write;
+use std::str::FromStr;
 
-pub use srs::message::{LatLngPosition, Position};
+pub use srs::message::{Coalition, LatLngPosition, Position};
+use time::PrimitiveDateTime;
 use uom::num::Zero;
 use uom::si::angle::degree;
-use uom::si::f64::{Angle, Length as LengthF64, ThermodynamicTemperature as Temperature};
+use uom::si::f64::{Angle, Length as LengthF64, ThermodynamicTemperature as Temperature, Velocity};
 use uom::si::i32::Length;
-use uom::si::length::{foot, meter, nautical_mile};
-use uom::si::pressure::{hectopascal, inch_of_mercury};
+use uom::si::length::{foot, kilometer, meter, mile, nautical_mile};
+use uom::si::pressure::{hectopascal, inch_of_mercury, millimeter_of_mercury};
 use uom::si::thermodynamic_temperature::degree_celsius;
-use uom::si::velocity::knot;
+use uom::si::velocity::{kilometer_per_hour, knot};
 
+use crate::lexicon::Lexicon;
+use crate::locale::{Locale, fill};
+use crate::template::Template;
 use crate::tts::TextToSpeechProvider;
-use crate::utils::{pronounce_number, round, round_hundreds};
-use crate::weather::WeatherInfo;
+use crate::tts::prosody::Prosody;
+use crate::units::UnitProfile;
+use crate::utils::{escape_xml, pronounce_number, round, round_hundreds};
+use crate::weather::{WeatherInfo, round_wind_dir};
 
 #[derive(Clone)]
 pub struct Station {
     pub name: String,
     pub freq: u64,
     pub tts: TextToSpeechProvider,
+    /// Voices tried in order when `tts` fails.
+    pub fallback_tts: Vec<TextToSpeechProvider>,
+    /// Speaking rate, pitch and volume of the voice (falls back to the global defaults).
+    pub prosody: Prosody,
     pub transmitter: Transmitter,
     pub ipc: MissionInterface,
+    /// The SRS coalitions the station registers with (one SRS client each). With coalition radio
+    /// security enabled, SRS servers only forward the broadcast to clients of these coalitions.
+    pub coalitions: Vec<Coalition>,
+    /// Overrides the default template of the transmitter for the wording of the report (not
+    /// supported for custom broadcasts).
+    pub template: Option<Template>,
+    /// Overrides whether radio effects are applied to the station's audio (see
+    /// `Config::radio_effects`).
+    pub radio_effects: Option<bool>,
 }
 
 #[derive(Clone)]
@@ -41,14 +62,71 @@ pub enum Transmitter {
 #[derive(Debug, PartialEq, Clone)]
 pub struct Airfield {
     pub name: String,
+    /// The ICAO code of the station, used as the ident of its METAR.
+    pub icao: Option<String>,
     pub position: Position,
-    pub runways: Vec<String>,
+    pub runways: Vec<Runway>,
     pub traffic_freq: Option<u64>,
     pub info_ltr_offset: usize,
     pub info_ltr_override: Option<char>,
     pub active_rwy_override: Option<String>,
     pub no_hpa: bool,
     pub no_qfe: bool,
+    /// Whether to report the (estimated) dew point along with the temperature.
+    pub dew_point: bool,
+    /// Whether to report the density altitude in the remarks section.
+    pub density_alt: bool,
+    /// Transition altitude (in feet) used to report the transition level, which is not reported
+    /// if `None`.
+    pub transition_alt: Option<u32>,
+    pub runway_rules: RunwayRules,
+    /// The language of the report.
+    pub locale: Locale,
+    /// The units of the report.
+    pub units: UnitProfile,
+    /// Crosswind (in knots) above which the report includes a warning.
+    pub crosswind_limit: Option<u32>,
+    /// Tailwind (in knots) above which the report includes a warning.
+    pub tailwind_limit: Option<u32>,
+}
+
+/// A physical runway strip with the designators of both of its ends, e.g. `04L` and `22R`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Runway {
+    pub start: String,
+    pub end: String,
+}
+
+impl Runway {
+    pub fn new(start: impl Into<String>, end: impl Into<String>) -> Self {
+        Runway {
+            start: start.into(),
+            end: end.into(),
+        }
+    }
+
+    pub fn designators(&self) -> [&str; 2] {
+        [&self.start, &self.end]
+    }
+}
+
+/// Rules for the selection of the active runway(s). Runways are matched either by their full
+/// designator (e.g. `22R`) or by their number (e.g. `22` for both `22L` and `22R`).
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct RunwayRules {
+    /// The runway preferred when the wind is below `calm_wind_limit`.
+    pub calm_rwy: Option<String>,
+    /// Wind speed (in knots) below which the calm wind runway is used (defaults to 5 knots).
+    pub calm_wind_limit: Option<u32>,
+    /// Tailwind (in knots) up to which the previously active runway is kept, to not flip
+    /// between runways on small wind changes (defaults to 3 knots).
+    pub hysteresis: Option<u32>,
+    /// Runways that are never selected. Closing one end of a runway also closes the other one.
+    pub closed: Vec<String>,
+    /// Runways that can be selected for arrivals (all if empty).
+    pub arrival: Vec<String>,
+    /// Runways that can be selected for departures (all if empty).
+    pub departure: Vec<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -69,20 +147,74 @@ pub struct Custom {
 #[derive(Debug, PartialEq, Clone)]
 pub struct WeatherTransmitter {
     pub name: String,
+    /// The ICAO code of the station, used as the ident of its METAR.
+    pub icao: Option<String>,
     pub position: Option<Position>,
     pub unit_id: u32,
     pub unit_name: String,
     pub info_ltr_offset: usize,
     pub info_ltr_override: Option<char>,
+    /// The altitudes (MSL) to report the winds aloft for (none if empty).
+    pub winds_aloft: Vec<LengthF64>,
+    /// The language of the report.
+    pub locale: Locale,
+    /// The units of the report.
+    pub units: UnitProfile,
 }
 
+#[derive(Clone)]
 pub struct Report {
     pub textual: String,
     pub spoken: String,
     pub position: LatLngPosition,
+    /// The data the report has been generated from (not available for custom broadcasts).
+    pub details: Option<ReportDetails>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReportDetails {
+    pub weather: WeatherInfo,
+    /// The mission time the report has been generated at (`None` for static reports).
+    pub time: Option<PrimitiveDateTime>,
+    /// The altitude the weather has been evaluated for.
+    pub alt: Length,
+    pub information_letter: Option<&'static str>,
+    pub active_runway: Option<String>,
+}
+
+const DEFAULT_CALM_WIND_LIMIT_KT: u32 = 5;
+const DEFAULT_RUNWAY_HYSTERESIS_KT: u32 = 3;
+const DEFAULT_CROSSWIND_LIMIT_KT: u32 = 15;
+const DEFAULT_TAILWIND_LIMIT_KT: u32 = 5;
+
+/// The coalitions of a station that broadcasts to all coalitions (`COALITION ALL` and stations on
+/// neutral units).
+pub fn all_coalitions() -> Vec<Coalition> {
+    vec![Coalition::Blue, Coalition::Red]
+}
+
+/// Get the default transition altitude (in feet) for the given DCS map (`env.mission.theatre`),
+/// based on the procedures of the countries the map is located in.
+pub fn default_transition_alt(theatre: &str) -> u32 {
+    match theatre {
+        "Caucasus" => 6_000,
+        "Syria" => 8_000,
+        "PersianGulf" | "SinaiMap" => 13_000,
+        "Afghanistan" | "Iraq" => 15_000,
+        "Nevada" | "MarianaIslands" => 18_000,
+        "Normandy" | "TheChannel" => 3_000,
+        "Kola" => 7_000,
+        "Falklands" => 3_000,
+        _ => 6_000,
+    }
 }
 
-const SPEAK_START_TAG: &str = "<speak version=\"1.0\" xml:lang=\"en\">\n";
+fn speak_start_tag(locale: Locale) -> String {
+    format!(
+        "<speak version=\"1.0\" xml:lang=\"{}\">\n",
+        locale.ssml_lang()
+    )
+}
 
 #[inline]
 fn break_(spoken: bool) -> &'static str {
@@ -96,44 +228,172 @@ fn break_(spoken: bool) -> &'static str {
     if spoken { "| " } else { "" }
 }
 
-fn wind_report(weather: &WeatherInfo, spoken: bool) -> String {
+/// Whether `pattern` (either a full designator or just the runway number) matches `rwy`.
+fn runway_matches(pattern: &str, rwy: &str) -> bool {
+    pattern.eq_ignore_ascii_case(rwy) || pattern == runway_number(rwy)
+}
+
+/// Get the runway number of a designator without its `L`/`C`/`R` suffix, e.g. `04` for `04L`.
+fn runway_number(rwy: &str) -> &str {
+    rwy.trim_end_matches(['L', 'C', 'R'])
+}
+
+/// Get the magnetic heading of a runway from its designator, e.g. `040` for `04L`.
+pub fn runway_heading(rwy: &str) -> Option<Angle> {
+    runway_number(rwy)
+        .parse::<f64>()
+        .ok()
+        .map(|rwy| Angle::new::<degree>(rwy * 10.0))
+}
+
+/// Pronounce a group of parallel runways separated by `,`, e.g. `27L,27R` as
+/// "two seven left and right".
+fn pronounce_runway(group: &str, locale: Locale, spoken: bool) -> String {
+    let phrases = locale.phrases();
+    let designators: Vec<&str> = group.split(',').map(str::trim).collect();
+    let number = runway_number(designators[0]);
+    let sides: Vec<&str> = designators
+        .iter()
+        .filter_map(|rwy| match rwy.strip_prefix(number)? {
+            "L" => Some(phrases.runway_sides[0]),
+            "C" => Some(phrases.runway_sides[1]),
+            "R" => Some(phrases.runway_sides[2]),
+            _ => None,
+        })
+        .collect();
+
+    let number = locale.pronounce_number(number, spoken);
+    match sides.split_last() {
+        None => number,
+        Some((last, [])) => format!("{} {}", number, last),
+        Some((last, rest)) => format!("{} {} {} {}", number, rest.join(", "), phrases.and, last),
+    }
+}
+
+/// Join multiple sentences of a report part, the last sentence is left without a full stop so
+/// that it can be completed by the template.
+fn join_sentences(sentences: Vec<String>, spoken: bool) -> String {
+    sentences.join(&format!(". {}", break_(spoken)))
+}
+
+/// Report a speed in the unit of the station, e.g. "5 knots".
+fn speed_report(speed: Velocity, locale: Locale, units: UnitProfile, spoken: bool) -> String {
+    let phrases = locale.phrases();
+    match units {
+        UnitProfile::Soviet => fill(
+            phrases.kilometers_per_hour,
+            &[&locale.pronounce_number(speed.get::<kilometer_per_hour>().round(), spoken)],
+        ),
+        UnitProfile::Mixed | UnitProfile::Faa | UnitProfile::Icao => fill(
+            phrases.knots,
+            &[&locale.pronounce_number(speed.get::<knot>().round(), spoken)],
+        ),
+    }
+}
+
+fn wind_report(weather: &WeatherInfo, locale: Locale, units: UnitProfile, spoken: bool) -> String {
+    let phrases = locale.phrases();
     let wind_dir = format!(
         "{:0>3}",
         weather.wind_dir.get::<degree>().round().to_string()
     );
-    format!(
-        "{} {} at {} knots. {}",
-        if spoken {
-            r#"<phoneme alphabet="ipa" ph="w&#618;nd">Wind</phoneme>"#
+    let mut report = fill(
+        phrases.wind,
+        &[
+            if spoken {
+                phrases.wind_word_spoken
+            } else {
+                phrases.wind_word
+            },
+            &locale.pronounce_number(wind_dir, spoken),
+            &speed_report(weather.wind_speed, locale, units, spoken),
+        ],
+    );
+
+    if let Some(gust) = weather.get_wind_gust() {
+        report += &fill(
+            phrases.gusting,
+            &[&speed_report(gust, locale, units, spoken)],
+        );
+    }
+
+    if let Some((from, to)) = weather.get_wind_variability() {
+        report += &fill(
+            phrases.variable,
+            &[
+                &locale.pronounce_number(format!("{:03}", round_wind_dir(from)), spoken),
+                &locale.pronounce_number(format!("{:03}", round_wind_dir(to)), spoken),
+            ],
+        );
+    }
+
+    report
+}
+
+fn winds_aloft_report(
+    weather: &WeatherInfo,
+    locale: Locale,
+    units: UnitProfile,
+    spoken: bool,
+) -> String {
+    if weather.winds_aloft.is_empty() {
+        return String::new();
+    }
+
+    let phrases = locale.phrases();
+    let mut sentences = vec![String::from(phrases.winds_aloft)];
+    for wind in &weather.winds_aloft {
+        let (phrase, alt) = if units.metric_altitudes() {
+            (phrases.wind_aloft_meters, wind.alt.get::<meter>())
         } else {
-            "Wind"
-        },
-        pronounce_number(wind_dir, spoken),
-        pronounce_number((weather.wind_speed.get::<knot>()).round(), spoken), // to knots
-        break_(spoken),
-    )
+            (phrases.wind_aloft, wind.alt.get::<foot>())
+        };
+        sentences.push(fill(
+            phrase,
+            &[
+                &round(alt, -2).to_string(),
+                &locale.pronounce_number(format!("{:03}", round_wind_dir(wind.wind_dir)), spoken),
+                &speed_report(wind.wind_speed, locale, units, spoken),
+            ],
+        ));
+    }
+    join_sentences(sentences, spoken)
 }
 
-fn ceiling_report(weather: &WeatherInfo, alt: Length, spoken: bool) -> String {
+fn ceiling_report(
+    weather: &WeatherInfo,
+    alt: Length,
+    locale: Locale,
+    units: UnitProfile,
+) -> String {
     if let Some(ceiling) = weather.get_ceiling(alt) {
-        return format!(
-            "Ceiling {} {}. {}",
-            round_hundreds(ceiling.alt.get::<foot>()),
-            ceiling.coverage,
-            break_(spoken)
+        let phrases = locale.phrases();
+        let (phrase, alt) = if units.metric_altitudes() {
+            // in steps of 10m
+            (
+                phrases.ceiling_meters,
+                (ceiling.alt.get::<meter>() / 10) * 10,
+            )
+        } else {
+            (phrases.ceiling, round_hundreds(ceiling.alt.get::<foot>()))
+        };
+        return fill(
+            phrase,
+            &[&alt.to_string(), locale.cloud_coverage(&ceiling.coverage)],
         );
     }
 
     String::new()
 }
 
-fn weather_condition_report(weather: &WeatherInfo, alt: Length, spoken: bool) -> String {
+fn weather_condition_report(weather: &WeatherInfo, alt: Length, locale: Locale) -> String {
     let conditions = weather.get_weather_conditions(alt);
     if conditions.is_empty() {
         return String::new();
     }
 
     let ix_last = conditions.len();
+    let and = format!(" {} ", locale.phrases().and);
     let mut result = String::new();
     for (i, c) in conditions.into_iter().enumerate() {
         write!(
@@ -142,97 +402,384 @@ fn weather_condition_report(weather: &WeatherInfo, alt: Length, spoken: bool) ->
             if i == 0 {
                 ""
             } else if i == ix_last {
-                " and "
+                &and
             } else {
                 ", "
             },
-            c
+            locale.weather_condition(&c)
         )
         .unwrap()
     }
 
-    format!("{}. {}", result, break_(spoken))
+    result
 }
 
-fn visibility_report(weather: &WeatherInfo, alt: Length, spoken: bool) -> String {
+fn visibility_report(
+    weather: &WeatherInfo,
+    alt: Length,
+    locale: Locale,
+    units: UnitProfile,
+    spoken: bool,
+) -> String {
+    let phrases = locale.phrases();
     if let Some(visibility) = weather.get_visibility(alt) {
         if visibility < Length::new::<nautical_mile>(5) {
             let visibility = LengthF64::new::<meter>(visibility.get::<meter>() as f64);
-            let visibility = round(visibility.get::<nautical_mile>(), 1);
-            return format!(
-                "Visibility {}. {}",
-                pronounce_number(visibility, spoken),
-                break_(spoken)
-            );
+            let visibility = match units {
+                UnitProfile::Mixed => fill(
+                    phrases.nautical_miles,
+                    &[&locale
+                        .pronounce_number(round(visibility.get::<nautical_mile>(), 1), spoken)],
+                ),
+                UnitProfile::Faa => fill(
+                    phrases.statute_miles,
+                    &[&locale.pronounce_number(round(visibility.get::<mile>(), 1), spoken)],
+                ),
+                // meters below 5km, as reported in METARs
+                UnitProfile::Icao | UnitProfile::Soviet => {
+                    if visibility < LengthF64::new::<kilometer>(5.0) {
+                        fill(
+                            phrases.meters,
+                            &[&locale
+                                .pronounce_number(round(visibility.get::<meter>(), -2), spoken)],
+                        )
+                    } else {
+                        fill(
+                            phrases.kilometers,
+                            &[&locale
+                                .pronounce_number(visibility.get::<kilometer>().round(), spoken)],
+                        )
+                    }
+                }
+            };
+            return fill(phrases.visibility, &[&visibility]);
         }
     }
 
     String::new()
 }
 
-fn temperature_report(weather: &WeatherInfo, spoken: bool) -> String {
-    format!(
-        "Temperature {} celcius. {}",
-        pronounce_number(
+fn temperature_report(weather: &WeatherInfo, locale: Locale, spoken: bool) -> String {
+    fill(
+        locale.phrases().temperature,
+        &[&locale.pronounce_number(
             round(weather.temperature.get::<degree_celsius>(), 1),
-            spoken
-        ),
-        break_(spoken),
+            spoken,
+        )],
     )
 }
 
-fn altimeter_report(weather: &WeatherInfo, alt: Length, spoken: bool) -> String {
-    format!(
-        "ALTIMETER {}. {}",
-        // times 100, because we don't want to speak the DECIMAL place
-        pronounce_number(
-            (weather.get_qnh(alt).get::<inch_of_mercury>() * 100.0).round(),
-            spoken
-        ),
-        break_(spoken),
+fn dew_point_report(weather: &WeatherInfo, alt: Length, locale: Locale, spoken: bool) -> String {
+    fill(
+        locale.phrases().dew_point,
+        &[&locale.pronounce_number(
+            weather.get_dew_point(alt).get::<degree_celsius>().round() as i32,
+            spoken,
+        )],
     )
 }
 
-fn hectopascal_report(weather: &WeatherInfo, alt: Length, spoken: bool) -> String {
-    format!(
-        "{} hectopascal. {}",
-        pronounce_number(weather.get_qnh(alt).get::<hectopascal>().round(), spoken),
-        break_(spoken),
+fn altimeter_report(
+    weather: &WeatherInfo,
+    alt: Length,
+    locale: Locale,
+    units: UnitProfile,
+    spoken: bool,
+) -> String {
+    let phrases = locale.phrases();
+    let qnh = weather.get_qnh(alt);
+    match units {
+        UnitProfile::Mixed | UnitProfile::Faa => fill(
+            phrases.altimeter,
+            // times 100, because we don't want to speak the DECIMAL place
+            &[&locale.pronounce_number((qnh.get::<inch_of_mercury>() * 100.0).round(), spoken)],
+        ),
+        UnitProfile::Icao => fill(
+            phrases.qnh,
+            &[&locale.pronounce_number(qnh.get::<hectopascal>().round(), spoken)],
+        ),
+        // altimeters of Soviet-era aircraft are set to the QFE
+        UnitProfile::Soviet => fill(
+            phrases.qfe_single,
+            &[&fill(
+                phrases.millimeters,
+                &[&locale.pronounce_number(
+                    weather.get_qfe().get::<millimeter_of_mercury>().round(),
+                    spoken,
+                )],
+            )],
+        ),
+    }
+}
+
+/// The QNH in hPa, which is only reported (in the remarks) in addition to the QNH in inHg.
+fn hectopascal_report(
+    weather: &WeatherInfo,
+    alt: Length,
+    locale: Locale,
+    units: UnitProfile,
+    spoken: bool,
+) -> String {
+    if units != UnitProfile::Mixed {
+        return String::new();
+    }
+
+    fill(
+        locale.phrases().hectopascal,
+        &[&locale.pronounce_number(weather.get_qnh(alt).get::<hectopascal>().round(), spoken)],
     )
 }
 
-fn qfe_report(weather: &WeatherInfo, spoken: bool) -> String {
-    format!(
-        "QFE {} {}or {}. {}",
-        // times 100, because we don't want to speak the DECIMAL place
-        pronounce_number(
-            (weather.get_qfe().get::<inch_of_mercury>() * 100.0).round(),
-            spoken
+fn qfe_report(
+    weather: &WeatherInfo,
+    alt: Length,
+    locale: Locale,
+    units: UnitProfile,
+    spoken: bool,
+) -> String {
+    let phrases = locale.phrases();
+    let qfe = weather.get_qfe();
+    // times 100, because we don't want to speak the DECIMAL place
+    let qfe_inhg =
+        || locale.pronounce_number((qfe.get::<inch_of_mercury>() * 100.0).round(), spoken);
+    let qfe_hpa = || locale.pronounce_number(qfe.get::<hectopascal>().round(), spoken);
+    match units {
+        UnitProfile::Mixed => fill(
+            phrases.qfe,
+            &[
+                &qfe_inhg(),
+                if spoken {
+                    // add break to make it easier to mentally process the different numbers
+                    "<break time=\"500ms\" /> "
+                } else {
+                    ""
+                },
+                &qfe_hpa(),
+            ],
         ),
+        UnitProfile::Faa => fill(phrases.qfe_single, &[&qfe_inhg()]),
+        UnitProfile::Icao => fill(phrases.qfe_single, &[&qfe_hpa()]),
+        // the QFE is already given as the altimeter, so give the QNH instead
+        UnitProfile::Soviet => fill(
+            phrases.qnh,
+            &[&fill(
+                phrases.millimeters,
+                &[&locale.pronounce_number(
+                    weather.get_qnh(alt).get::<millimeter_of_mercury>().round(),
+                    spoken,
+                )],
+            )],
+        ),
+    }
+}
+
+fn transition_level_report(transition_level: u32, locale: Locale, spoken: bool) -> String {
+    let phrases = locale.phrases();
+    fill(
         if spoken {
-            // add break to make it easier to mentally process the different numbers
-            "<break time=\"500ms\" /> "
+            phrases.transition_level_spoken
         } else {
-            ""
+            phrases.transition_level
         },
-        pronounce_number(weather.get_qfe().get::<hectopascal>().round(), spoken),
-        break_(spoken),
+        &[&locale.pronounce_number(transition_level, spoken)],
     )
 }
 
+fn density_altitude_report(
+    weather: &WeatherInfo,
+    locale: Locale,
+    units: UnitProfile,
+    spoken: bool,
+) -> String {
+    let phrases = locale.phrases();
+    let density_alt = weather.get_density_altitude();
+    // reported in steps of 100ft or 10m
+    let (phrase, density_alt) = if units.metric_altitudes() {
+        (
+            phrases.density_altitude_meters,
+            round(f64::from(density_alt.get::<meter>()), -1),
+        )
+    } else {
+        (
+            phrases.density_altitude,
+            round(f64::from(density_alt.get::<foot>()), -2),
+        )
+    };
+    fill(phrase, &[&locale.pronounce_number(density_alt, spoken)])
+}
+
+/// Values of the placeholders shared by all weather related templates, see `docs/Templates.md`.
+fn weather_values(
+    weather: &WeatherInfo,
+    alt: Length,
+    locale: Locale,
+    units: UnitProfile,
+    spoken: bool,
+) -> HashMap<&'static str, String> {
+    let pronounce_number = |n: f64| locale.pronounce_number(n, spoken);
+    let mut values = HashMap::new();
+    values.insert("wind", wind_report(weather, locale, units, spoken));
+    values.insert(
+        "wind_dir",
+        locale.pronounce_number(format!("{:03}", round_wind_dir(weather.wind_dir)), spoken),
+    );
+    values.insert(
+        "wind_speed",
+        pronounce_number(weather.wind_speed.get::<knot>().round()),
+    );
+    values.insert(
+        "wind_gust",
+        weather
+            .get_wind_gust()
+            .map(|gust| pronounce_number(gust.get::<knot>().round()))
+            .unwrap_or_default(),
+    );
+    values.insert(
+        "winds_aloft",
+        winds_aloft_report(weather, locale, units, spoken),
+    );
+    values.insert("ceiling", ceiling_report(weather, alt, locale, units));
+    values.insert(
+        "ceiling_ft",
+        weather
+            .get_ceiling(alt)
+            .map(|ceiling| round_hundreds(ceiling.alt.get::<foot>()).to_string())
+            .unwrap_or_default(),
+    );
+    values.insert("weather", weather_condition_report(weather, alt, locale));
+    values.insert(
+        "visibility",
+        visibility_report(weather, alt, locale, units, spoken),
+    );
+    values.insert("temperature", temperature_report(weather, locale, spoken));
+    values.insert(
+        "temperature_celsius",
+        pronounce_number(weather.temperature.get::<degree_celsius>().round()),
+    );
+    values.insert(
+        "dew_point_celsius",
+        pronounce_number(weather.get_dew_point(alt).get::<degree_celsius>().round()),
+    );
+    values.insert(
+        "altimeter",
+        altimeter_report(weather, alt, locale, units, spoken),
+    );
+    values.insert(
+        "hpa",
+        hectopascal_report(weather, alt, locale, units, spoken),
+    );
+    values.insert("qfe", qfe_report(weather, alt, locale, units, spoken));
+    let qnh = weather.get_qnh(alt);
+    let qfe = weather.get_qfe();
+    values.insert(
+        "qnh_inhg",
+        pronounce_number((qnh.get::<inch_of_mercury>() * 100.0).round()),
+    );
+    values.insert(
+        "qnh_hpa",
+        pronounce_number(qnh.get::<hectopascal>().round()),
+    );
+    values.insert(
+        "qfe_inhg",
+        pronounce_number((qfe.get::<inch_of_mercury>() * 100.0).round()),
+    );
+    values.insert(
+        "qfe_hpa",
+        pronounce_number(qfe.get::<hectopascal>().round()),
+    );
+    values.insert(
+        "qfe_mmhg",
+        pronounce_number(qfe.get::<millimeter_of_mercury>().round()),
+    );
+    values
+}
+
+/// A value taken verbatim from the mission (e.g. a name), which has to be escaped for SSML.
+fn text_value(value: &str, spoken: bool) -> String {
+    if spoken {
+        escape_xml(value)
+    } else {
+        value.to_string()
+    }
+}
+
+fn render(
+    default: &Template,
+    custom: Option<&Template>,
+    values: &HashMap<&str, String>,
+    locale: Locale,
+    spoken: bool,
+) -> String {
+    let separator = format!(" {}", break_(spoken));
+    let report = custom.unwrap_or(default).render(values, &separator, spoken);
+    if spoken {
+        format!("{}{}\n</speak>", speak_start_tag(locale), report)
+    } else {
+        report
+    }
+}
+
 impl Station {
-    pub async fn generate_report(&self, report_nr: usize) -> Result<Option<Report>, anyhow::Error> {
-        match &self.ipc {
-            MissionInterface::Static => self.generate_static_report(report_nr).await,
-            #[cfg(feature = "ipc")]
-            MissionInterface::Ipc(ipc) => self.generate_report_from_ipc(report_nr, ipc).await,
+    /// The language of the reports, `None` for stations that only report in English.
+    pub fn locale(&self) -> Option<Locale> {
+        match &self.transmitter {
+            Transmitter::Airfield(airfield) => Some(airfield.locale),
+            Transmitter::Weather(weather) => Some(weather.locale),
+            Transmitter::Carrier(_) | Transmitter::Custom(_) => None,
+        }
+    }
+
+    /// Logs a warning if the voice of the station is known to not speak the language of its
+    /// reports (e.g. `LANG DE` with an English voice).
+    pub fn warn_if_language_mismatch(&self) {
+        let (Some(locale), Some(language)) = (self.locale(), self.tts.language()) else {
+            return;
+        };
+        if Locale::from_str(&language).ok() != Some(locale) {
+            log::warn!(
+                "The voice {:?} of station {} speaks {}, but its reports are in {} (set a matching voice with `VOICE`)",
+                self.tts,
+                self.name,
+                language,
+                locale.ssml_lang()
+            );
         }
     }
 
+    /// Generate the report number `report_nr`. `previous_rwy` is the active runway of the
+    /// previous report (if any), which is kept as long as the wind allows it. The `lexicon` (if
+    /// any) is applied to the spoken report only, and only for English reports, since its
+    /// pronunciations are English.
+    pub async fn generate_report(
+        &self,
+        report_nr: usize,
+        previous_rwy: Option<&str>,
+        lexicon: Option<&Lexicon>,
+    ) -> Result<Option<Report>, anyhow::Error> {
+        let report = match &self.ipc {
+            MissionInterface::Static => {
+                self.generate_static_report(report_nr, previous_rwy).await?
+            }
+            #[cfg(feature = "ipc")]
+            MissionInterface::Ipc(ipc) => {
+                self.generate_report_from_ipc(report_nr, previous_rwy, ipc)
+                    .await?
+            }
+        };
+        let lexicon = lexicon.filter(|_| self.locale().unwrap_or_default() == Locale::En);
+        Ok(report.map(|mut report| {
+            if let Some(lexicon) = lexicon {
+                report.spoken = lexicon.apply(&report.spoken);
+            }
+            report
+        }))
+    }
+
     #[cfg(feature = "ipc")]
     async fn generate_report_from_ipc(
         &self,
         report_nr: usize,
+        previous_rwy: Option<&str>,
         ipc: &crate::ipc::MissionRpc,
     ) -> Result<Option<Report>, anyhow::Error> {
         use anyhow::Context;
@@ -247,12 +794,12 @@ impl Station {
                     .to_lat_lng(&airfield.position)
                     .await
                     .context("failed to retrieve unit position")?;
-                let date = ipc
-                    .get_mission_start_date()
+                let time = ipc
+                    .get_mission_time()
                     .await
-                    .context("failed to retrieve mission start date")?;
+                    .context("failed to retrieve mission time")?;
                 let declination =
-                    igrf::declination(position.lat, position.lng, position.alt as u32, date)
+                    igrf::declination(position.lat, position.lng, position.alt as u32, time.date())
                         .map(|f| f.d)
                         .unwrap_or_else(|err| match err {
                             igrf::Error::DateOutOfRange(f) => f.d,
@@ -265,20 +812,34 @@ impl Station {
                 weather.wind_dir =
                     Angle::new::<degree>((weather.wind_dir.get::<degree>() - declination).floor());
 
+                let alt = Length::new::<meter>(position.alt as i32);
+                let rwy = airfield.get_active_runway(&weather, previous_rwy);
+                let rwy = rwy.as_deref();
                 Ok(Some(Report {
                     textual: airfield.generate_report(
                         report_nr,
                         &weather,
-                        Length::new::<meter>(position.alt as i32),
+                        alt,
+                        rwy,
+                        self.template.as_ref(),
                         false,
                     )?,
                     spoken: airfield.generate_report(
                         report_nr,
                         &weather,
-                        Length::new::<meter>(position.alt as i32),
+                        alt,
+                        rwy,
+                        self.template.as_ref(),
                         true,
                     )?,
                     position,
+                    details: Some(airfield.report_details(
+                        report_nr,
+                        weather,
+                        Some(time),
+                        alt,
+                        rwy,
+                    )),
                 }))
             }
             Transmitter::Carrier(unit) => {
@@ -302,27 +863,50 @@ impl Station {
                         .await
                         .context("failed to retrieve unit position")?;
                     let mission_hour = ipc.get_mission_hour().await?;
-                    let date = ipc
-                        .get_mission_start_date()
+                    let time = ipc
+                        .get_mission_time()
                         .await
-                        .context("failed to retrieve mission start date")?;
-                    let declination =
-                        igrf::declination(position.lat, position.lng, position.alt as u32, date)
-                            .map(|f| f.d)
-                            .unwrap_or_else(|err| match err {
-                                igrf::Error::DateOutOfRange(f) => f.d,
-                                err => {
-                                    log::error!("Failed to estimate magnetic declination: {}", err);
-                                    0.0
-                                }
-                            });
+                        .context("failed to retrieve mission time")?;
+                    let declination = igrf::declination(
+                        position.lat,
+                        position.lng,
+                        position.alt as u32,
+                        time.date(),
+                    )
+                    .map(|f| f.d)
+                    .unwrap_or_else(|err| match err {
+                        igrf::Error::DateOutOfRange(f) => f.d,
+                        err => {
+                            log::error!("Failed to estimate magnetic declination: {}", err);
+                            0.0
+                        }
+                    });
 
                     let heading = (heading.to_degrees() - declination).floor() as u16;
 
                     Ok(Some(Report {
-                        textual: unit.generate_report(&weather, heading, mission_hour, false)?,
-                        spoken: unit.generate_report(&weather, heading, mission_hour, true)?,
+                        textual: unit.generate_report(
+                            &weather,
+                            heading,
+                            mission_hour,
+                            self.template.as_ref(),
+                            false,
+                        )?,
+                        spoken: unit.generate_report(
+                            &weather,
+                            heading,
+                            mission_hour,
+                            self.template.as_ref(),
+                            true,
+                        )?,
                         position,
+                        details: Some(ReportDetails {
+                            weather,
+                            time: Some(time),
+                            alt: Length::new::<foot>(70),
+                            information_letter: None,
+                            active_runway: None,
+                        }),
                     }))
                 } else {
                     Ok(None)
@@ -349,6 +933,7 @@ impl Station {
                         escape_xml(&custom.message)
                     ),
                     position,
+                    details: None,
                 }))
             }
             Transmitter::Weather(weather) => {
@@ -360,29 +945,43 @@ impl Station {
                         .context("failed to retrieve unit position")?,
                 };
 
-                let weather_info = ipc
+                let mut weather_info = ipc
                     .get_weather_at(&pos)
                     .await
                     .context("failed to retrieve weather")?;
+                if !weather.winds_aloft.is_empty() {
+                    weather_info.winds_aloft = ipc
+                        .get_winds_aloft(&pos, &weather.winds_aloft)
+                        .await
+                        .context("failed to retrieve winds aloft")?;
+                }
                 let position = ipc
                     .to_lat_lng(&pos)
                     .await
                     .context("failed to convert unit position to lat lng")?;
+                let time = ipc
+                    .get_mission_time()
+                    .await
+                    .context("failed to retrieve mission time")?;
 
+                let alt = Length::new::<meter>(position.alt as i32);
                 Ok(Some(Report {
                     textual: weather.generate_report(
                         report_nr,
                         &weather_info,
-                        Length::new::<meter>(position.alt as i32),
+                        alt,
+                        self.template.as_ref(),
                         false,
                     )?,
                     spoken: weather.generate_report(
                         report_nr,
                         &weather_info,
-                        Length::new::<meter>(position.alt as i32),
+                        alt,
+                        self.template.as_ref(),
                         true,
                     )?,
                     position,
+                    details: Some(weather.report_details(report_nr, weather_info, Some(time), alt)),
                 }))
             }
         }
@@ -391,6 +990,7 @@ impl Station {
     async fn generate_static_report(
         &self,
         report_nr: usize,
+        previous_rwy: Option<&str>,
     ) -> Result<Option<Report>, anyhow::Error> {
         use uom::si::f64::{Pressure, Velocity};
         use uom::si::pressure::pascal;
@@ -407,24 +1007,63 @@ impl Station {
         };
 
         match &self.transmitter {
-            Transmitter::Airfield(airfield) => Ok(Some(Report {
-                textual: airfield.generate_report(
-                    report_nr,
-                    &weather_info,
-                    Length::zero(),
-                    false,
-                )?,
-                spoken: airfield.generate_report(report_nr, &weather_info, Length::zero(), true)?,
-                position: LatLngPosition::default(),
-            })),
+            Transmitter::Airfield(airfield) => {
+                let rwy = airfield.get_active_runway(&weather_info, previous_rwy);
+                let rwy = rwy.as_deref();
+                Ok(Some(Report {
+                    textual: airfield.generate_report(
+                        report_nr,
+                        &weather_info,
+                        Length::zero(),
+                        rwy,
+                        self.template.as_ref(),
+                        false,
+                    )?,
+                    spoken: airfield.generate_report(
+                        report_nr,
+                        &weather_info,
+                        Length::zero(),
+                        rwy,
+                        self.template.as_ref(),
+                        true,
+                    )?,
+                    position: LatLngPosition::default(),
+                    details: Some(airfield.report_details(
+                        report_nr,
+                        weather_info,
+                        None,
+                        Length::zero(),
+                        rwy,
+                    )),
+                }))
+            }
             Transmitter::Carrier(unit) => {
                 let heading = 180;
                 let mission_hour = 7;
 
                 Ok(Some(Report {
-                    textual: unit.generate_report(&weather_info, heading, mission_hour, false)?,
-                    spoken: unit.generate_report(&weather_info, heading, mission_hour, true)?,
+                    textual: unit.generate_report(
+                        &weather_info,
+                        heading,
+                        mission_hour,
+                        self.template.as_ref(),
+                        false,
+                    )?,
+                    spoken: unit.generate_report(
+                        &weather_info,
+                        heading,
+                        mission_hour,
+                        self.template.as_ref(),
+                        true,
+                    )?,
                     position: LatLngPosition::default(),
+                    details: Some(ReportDetails {
+                        weather: weather_info,
+                        time: None,
+                        alt: Length::new::<foot>(70),
+                        information_letter: None,
+                        active_runway: None,
+                    }),
                 }))
             }
             Transmitter::Custom(custom) => Ok(Some(Report {
@@ -434,162 +1073,451 @@ impl Station {
                     escape_xml(&custom.message)
                 ),
                 position: LatLngPosition::default(),
+                details: None,
             })),
             Transmitter::Weather(weather) => Ok(Some(Report {
                 textual: weather.generate_report(
                     report_nr,
                     &weather_info,
                     Length::zero(),
+                    self.template.as_ref(),
                     false,
                 )?,
-                spoken: weather.generate_report(report_nr, &weather_info, Length::zero(), true)?,
+                spoken: weather.generate_report(
+                    report_nr,
+                    &weather_info,
+                    Length::zero(),
+                    self.template.as_ref(),
+                    true,
+                )?,
                 position: LatLngPosition::default(),
+                details: Some(weather.report_details(
+                    report_nr,
+                    weather_info,
+                    None,
+                    Length::zero(),
+                )),
             })),
         }
     }
 }
 
 impl Airfield {
-    fn get_active_runway(&self, wind_dir: Angle) -> Option<&str> {
+    /// Close the runway `rwy` (both of its ends), or open it again. Opening a runway also removes
+    /// closures made via the designator of its other end (e.g. opening `21L` after closing `03R`).
+    pub fn set_runway_closed(&mut self, rwy: &str, closed: bool) {
+        let rwy = rwy.trim().to_uppercase();
+        let strip = self.runways.iter().find(|strip| {
+            strip
+                .designators()
+                .iter()
+                .any(|designator| runway_matches(&rwy, designator))
+        });
+        self.runway_rules.closed.retain(|closed| match strip {
+            Some(strip) => !strip
+                .designators()
+                .iter()
+                .any(|designator| runway_matches(closed, designator)),
+            None => *closed != rwy,
+        });
+        if closed {
+            self.runway_rules.closed.push(rwy);
+        }
+    }
+
+    /// Select the active runway based on the current wind and the runway rules. Parallel
+    /// runways in use at the same time are separated by `,` (e.g. `27L,27R`), separate arrival
+    /// and departure runways are returned as `{arrival}/{departure}`. The `previous` active
+    /// runway is kept as long as its tailwind stays within the hysteresis.
+    fn get_active_runway(&self, weather: &WeatherInfo, previous: Option<&str>) -> Option<String> {
         if let Some(rwy_override) = &self.active_rwy_override {
-            return Some(rwy_override);
+            return Some(rwy_override.clone());
         }
 
-        let lr: &[_] = &['L', 'R'];
-        for rwy in &self.runways {
-            let rwy = rwy.trim_matches(lr);
-            if let Ok(mut rwy_dir) = rwy.parse::<f64>() {
-                rwy_dir *= 10.0; // e.g. 04 to 040
-                let phi = (wind_dir.get::<degree>() - rwy_dir).abs() % 360.0;
-                let distance = if phi > 180.0 { 360.0 - phi } else { phi };
-                if distance <= 90.0 {
-                    return Some(rwy);
+        let rules = &self.runway_rules;
+        if let Some(calm_rwy) = &rules.calm_rwy {
+            let limit = rules.calm_wind_limit.unwrap_or(DEFAULT_CALM_WIND_LIMIT_KT);
+            if weather.wind_speed.get::<knot>() < f64::from(limit) {
+                let group = self
+                    .open_runways()
+                    .filter(|rwy| runway_matches(calm_rwy, rwy))
+                    .collect::<Vec<_>>();
+                if !group.is_empty() {
+                    return Some(group.join(","));
                 }
+            }
+        }
+
+        let (previous_arr, previous_dep) = match previous {
+            Some(previous) => match previous.split_once('/') {
+                Some((arr, dep)) => (Some(arr), Some(dep)),
+                None => (Some(previous), Some(previous)),
+            },
+            None => (None, None),
+        };
+        let arr = self.select_runway(weather, &rules.arrival, previous_arr);
+        let dep = self.select_runway(weather, &rules.departure, previous_dep);
+
+        match (arr, dep) {
+            (Some(arr), Some(dep)) if arr != dep => Some(format!("{}/{}", arr, dep)),
+            (Some(rwy), _) | (None, Some(rwy)) => Some(rwy),
+            (None, None) => None,
+        }
+    }
+
+    /// All runway designators that are not closed, with parallel runways ordered from left to
+    /// right.
+    fn open_runways(&self) -> impl Iterator<Item = &str> {
+        let mut designators = self
+            .runways
+            .iter()
+            .filter(|rwy| {
+                !self.runway_rules.closed.iter().any(|closed| {
+                    rwy.designators()
+                        .iter()
+                        .any(|designator| runway_matches(closed, designator))
+                })
+            })
+            .flat_map(|rwy| rwy.designators())
+            .collect::<Vec<_>>();
+        designators.sort_by_key(|rwy| {
+            (
+                runway_number(rwy),
+                match rwy.chars().last() {
+                    Some('L') => 0,
+                    Some('C') => 1,
+                    Some('R') => 2,
+                    _ => 0,
+                },
+            )
+        });
+        designators.into_iter()
+    }
+
+    /// Select the open runway number out of `allowed` (all if empty) with the strongest
+    /// headwind, which is also the one with the least tailwind. All allowed parallel runways
+    /// with this number are returned.
+    fn select_runway(
+        &self,
+        weather: &WeatherInfo,
+        allowed: &[String],
+        previous: Option<&str>,
+    ) -> Option<String> {
+        let mut candidates = Vec::new();
+        for rwy in self.open_runways() {
+            if !allowed.is_empty() && !allowed.iter().any(|a| runway_matches(a, rwy)) {
+                continue;
+            }
+
+            if let Some(heading) = runway_heading(rwy) {
+                let headwind = weather.get_wind_components(heading).0.get::<knot>();
+                candidates.push((rwy, headwind));
             } else {
                 log::error!("Error parsing runway: {}", rwy);
             }
         }
 
-        None
+        let hysteresis = self
+            .runway_rules
+            .hysteresis
+            .unwrap_or(DEFAULT_RUNWAY_HYSTERESIS_KT);
+        let previous = previous.and_then(|previous| previous.split(',').next().map(runway_number));
+        let number = if let Some((rwy, _)) = candidates.iter().find(|(rwy, headwind)| {
+            Some(runway_number(rwy)) == previous && -headwind <= f64::from(hysteresis)
+        }) {
+            runway_number(rwy)
+        } else {
+            let mut active: Option<(&str, f64)> = None;
+            for (rwy, headwind) in &candidates {
+                if active.is_none_or(|(_, max)| *headwind > max) {
+                    active = Some((rwy, *headwind));
+                }
+            }
+            runway_number(active?.0)
+        };
+
+        let mut group = candidates
+            .iter()
+            .map(|(rwy, _)| *rwy)
+            .filter(|rwy| runway_number(rwy) == number)
+            .collect::<Vec<_>>();
+        group.dedup();
+        Some(group.join(","))
     }
 
-    pub fn generate_report(
+    /// Report the headwind and crosswind components for the runway(s) in use, including
+    /// warnings if they exceed the limits of the airfield.
+    fn runway_wind_report(
         &self,
-        report_nr: usize,
+        active_rwy: Option<&str>,
         weather: &WeatherInfo,
-        alt: Length,
         spoken: bool,
-    ) -> Result<String, anyhow::Error> {
-        let mut report = if spoken { SPEAK_START_TAG } else { "" }.to_string();
-
-        let information_num = if let Some(ltr_override) = self.info_ltr_override {
-            (ltr_override.to_ascii_uppercase() as usize) - 65
-        } else {
-            self.info_ltr_offset + report_nr
+    ) -> String {
+        if weather.wind_speed.get::<knot>().round() < 1.0 {
+            return String::new();
+        }
+        let Some(active_rwy) = active_rwy else {
+            return String::new();
         };
-        let information_letter = phonetic_alphabet::lookup(information_num);
 
-        write!(
-            report,
-            "This is {} information {}. {}",
-            self.name,
-            information_letter,
-            break_(spoken)
-        )
-        .unwrap();
+        let mut runways: Vec<&str> = active_rwy.split('/').collect();
+        runways.dedup();
+        let crosswind_limit = self.crosswind_limit.unwrap_or(DEFAULT_CROSSWIND_LIMIT_KT);
+        let tailwind_limit = self.tailwind_limit.unwrap_or(DEFAULT_TAILWIND_LIMIT_KT);
+
+        let locale = self.locale;
+        let phrases = locale.phrases();
+        let speed =
+            |knots: f64| speed_report(Velocity::new::<knot>(knots), locale, self.units, spoken);
+        let mut sentences = Vec::new();
+        let mut crosswind_warning = false;
+        let mut tailwind_warning = false;
+        for rwy in &runways {
+            let Some(heading) = rwy.split(',').next().and_then(runway_heading) else {
+                continue;
+            };
+            let (headwind, crosswind) = weather.get_wind_components(heading);
+            let headwind = headwind.get::<knot>().round();
+            let crosswind = crosswind.get::<knot>().round();
+
+            let mut parts = Vec::new();
+            if headwind > 0.0 {
+                parts.push(fill(phrases.headwind, &[&speed(headwind)]));
+            } else if headwind < 0.0 {
+                parts.push(fill(phrases.tailwind, &[&speed(-headwind)]));
+            }
+            if crosswind != 0.0 {
+                parts.push(fill(
+                    if crosswind > 0.0 {
+                        phrases.crosswind_right
+                    } else {
+                        phrases.crosswind_left
+                    },
+                    &[&speed(crosswind.abs())],
+                ));
+            }
+            if parts.is_empty() {
+                continue;
+            }
 
-        if let Some(rwy) = self.get_active_runway(weather.wind_dir) {
-            if let Some((arr, dep)) = rwy.split_once('/') {
-                let arr = pronounce_number(arr, spoken);
-                let dep = pronounce_number(dep, spoken);
-                write!(
-                    report,
-                    "Runway in use is {} for arrivals and {} for departures. {}",
-                    arr,
-                    dep,
-                    break_(spoken)
-                )
-                .unwrap();
+            let components = parts.join(", ");
+            if runways.len() > 1 {
+                sentences.push(fill(
+                    phrases.runway_components,
+                    &[&pronounce_runway(rwy, locale, spoken), &components],
+                ));
             } else {
-                let rwy = pronounce_number(rwy, spoken);
-                write!(report, "Runway in use is {}. {}", rwy, break_(spoken)).unwrap();
+                let mut chars = components.chars();
+                let first = chars.next().into_iter().flat_map(char::to_uppercase);
+                sentences.push(first.chain(chars).collect());
             }
-        } else {
-            log::error!("Could not find active runway for {}", self.name);
-        }
 
-        if let Some(traffic_freq) = self.traffic_freq {
-            write!(
-                report,
-                "Traffic frequency {}. {}",
-                pronounce_number(round(traffic_freq as f64 / 1_000_000.0, 3), spoken),
-                break_(spoken)
-            )
-            .unwrap();
+            crosswind_warning |= crosswind.abs() > f64::from(crosswind_limit);
+            tailwind_warning |= -headwind > f64::from(tailwind_limit);
         }
 
-        report += &wind_report(weather, spoken);
-        report += &ceiling_report(weather, alt, spoken);
-        report += &weather_condition_report(weather, alt, spoken);
-        report += &visibility_report(weather, alt, spoken);
-        report += &temperature_report(weather, spoken);
-        report += &altimeter_report(weather, alt, spoken);
-
-        if !self.no_hpa || !self.no_qfe {
-            write!(report, "REMARKS. {}", break_(spoken)).unwrap();
+        if crosswind_warning {
+            sentences.push(fill(
+                phrases.caution_crosswind,
+                &[&speed(f64::from(crosswind_limit))],
+            ));
         }
-
-        if !self.no_hpa {
-            report += &hectopascal_report(weather, alt, spoken);
+        if tailwind_warning {
+            sentences.push(fill(
+                phrases.caution_tailwind,
+                &[&speed(f64::from(tailwind_limit))],
+            ));
         }
 
-        if !self.no_qfe {
-            report += &qfe_report(weather, spoken);
-        }
+        join_sentences(sentences, spoken)
+    }
 
-        write!(report, "End information {}.", information_letter).unwrap();
+    pub fn information_letter(&self, report_nr: usize) -> &'static str {
+        information_letter(self.info_ltr_override, self.info_ltr_offset, report_nr)
+    }
 
-        if spoken {
-            report += "\n</speak>";
+    pub fn report_details(
+        &self,
+        report_nr: usize,
+        weather: WeatherInfo,
+        time: Option<PrimitiveDateTime>,
+        alt: Length,
+        active_rwy: Option<&str>,
+    ) -> ReportDetails {
+        ReportDetails {
+            information_letter: Some(self.information_letter(report_nr)),
+            active_runway: active_rwy.map(String::from),
+            weather,
+            time,
+            alt,
         }
-
-        Ok(report)
     }
-}
 
-impl Carrier {
     pub fn generate_report(
         &self,
+        report_nr: usize,
         weather: &WeatherInfo,
-        heading: u16,
-        mission_hour: u16,
+        alt: Length,
+        active_rwy: Option<&str>,
+        template: Option<&Template>,
         spoken: bool,
     ) -> Result<String, anyhow::Error> {
-        let mut report = if spoken { SPEAK_START_TAG } else { "" }.to_string();
+        let locale = self.locale;
+        let phrases = locale.phrases();
+        let mut values = weather_values(weather, alt, locale, self.units, spoken);
+        values.insert("name", text_value(&self.name, spoken));
+        values.insert(
+            "information",
+            locale
+                .phonetic_letter(information_index(
+                    self.info_ltr_override,
+                    self.info_ltr_offset,
+                    report_nr,
+                ))
+                .to_string(),
+        );
+
+        let runway = match active_rwy {
+            Some(rwy) => match rwy.split_once('/') {
+                Some((arr, dep)) => fill(
+                    phrases.runway_arr_dep,
+                    &[
+                        &pronounce_runway(arr, locale, spoken),
+                        &pronounce_runway(dep, locale, spoken),
+                    ],
+                ),
+                None => fill(
+                    phrases.runway_in_use,
+                    &[&pronounce_runway(rwy, locale, spoken)],
+                ),
+            },
+            None => {
+                log::error!("Could not find active runway for {}", self.name);
+                String::new()
+            }
+        };
+        values.insert("runway", runway);
+        values.insert(
+            "active_runway",
+            active_rwy
+                .map(|rwy| {
+                    rwy.split('/')
+                        .map(|rwy| pronounce_runway(rwy, locale, spoken))
+                        .collect::<Vec<_>>()
+                        .join(&format!(" {} ", phrases.and))
+                })
+                .unwrap_or_default(),
+        );
 
-        write!(report, "99, {}", break_(spoken)).unwrap();
+        let traffic_freq = self
+            .traffic_freq
+            .map(|freq| locale.pronounce_number(round(freq as f64 / 1_000_000.0, 3), spoken));
+        values.insert(
+            "traffic",
+            traffic_freq
+                .as_ref()
+                .map(|freq| fill(phrases.traffic, &[freq]))
+                .unwrap_or_default(),
+        );
+        values.insert("traffic_freq", traffic_freq.unwrap_or_default());
 
-        let wind_dir = format!(
-            "{:0>3}",
-            weather.wind_dir.get::<degree>().round().to_string()
+        values.insert(
+            "runway_wind",
+            self.runway_wind_report(active_rwy, weather, spoken),
         );
-        write!(
-            report,
-            r#"{}'s {} {} at {} knots, {}"#,
-            self.name,
-            if spoken {
-                r#"<phoneme alphabet="ipa" ph="w&#618;nd">wind</phoneme>"#
+        values.insert(
+            "dew_point",
+            if self.dew_point {
+                dew_point_report(weather, alt, locale, spoken)
             } else {
-                "wind"
+                String::new()
             },
-            pronounce_number(wind_dir, spoken),
-            pronounce_number((weather.wind_speed.get::<knot>()).round(), spoken),
-            break_(spoken),
-        )
-        .unwrap();
+        );
 
-        let alt = Length::new::<foot>(70); // carrier deck alt
-        report += &altimeter_report(weather, alt, spoken);
+        let transition_level = self.transition_alt.map(|transition_alt| {
+            let transition_alt = LengthF64::new::<foot>(f64::from(transition_alt));
+            weather.get_transition_level(alt, transition_alt)
+        });
+        values.insert(
+            "transition_level",
+            transition_level
+                .map(|level| transition_level_report(level, locale, spoken))
+                .unwrap_or_default(),
+        );
+        values.insert(
+            "flight_level",
+            transition_level
+                .map(|level| locale.pronounce_number(level, spoken))
+                .unwrap_or_default(),
+        );
+
+        if self.no_hpa {
+            values.insert("hpa", String::new());
+        }
+        if self.no_qfe {
+            values.insert("qfe", String::new());
+        }
+        values.insert(
+            "density_altitude",
+            if self.density_alt {
+                density_altitude_report(weather, locale, self.units, spoken)
+            } else {
+                String::new()
+            },
+        );
+        values.insert(
+            "remarks",
+            if ["hpa", "qfe", "density_altitude"]
+                .iter()
+                .any(|key| values.get(key).is_some_and(|value| !value.is_empty()))
+            {
+                String::from(phrases.remarks)
+            } else {
+                String::new()
+            },
+        );
+
+        Ok(render(
+            Template::default_airfield(locale),
+            template,
+            &values,
+            locale,
+            spoken,
+        ))
+    }
+}
+
+impl Carrier {
+    pub fn generate_report(
+        &self,
+        weather: &WeatherInfo,
+        heading: u16,
+        mission_hour: u16,
+        template: Option<&Template>,
+        spoken: bool,
+    ) -> Result<String, anyhow::Error> {
+        let alt = Length::new::<foot>(70); // carrier deck alt
+        // the carrier report sticks to the (English) US Navy phraseology
+        let mut values = weather_values(weather, alt, Locale::En, UnitProfile::Mixed, spoken);
+        values.insert("name", text_value(&self.name, spoken));
+
+        let wind_dir = format!(
+            "{:0>3}",
+            weather.wind_dir.get::<degree>().round().to_string()
+        );
+        values.insert(
+            "wind",
+            format!(
+                "{} {} at {} knots",
+                if spoken {
+                    r#"<phoneme alphabet="ipa" ph="w&#618;nd">wind</phoneme>"#
+                } else {
+                    "wind"
+                },
+                pronounce_number(wind_dir, spoken),
+                pronounce_number((weather.wind_speed.get::<knot>()).round(), spoken),
+            ),
+        );
 
         // Case 1: daytime, ceiling >= 3000ft; visibility distance >= 5nm
         // Case 2: daytime, ceiling >= 1000ft; visibility distance >= 5nm
@@ -615,7 +1543,7 @@ impl Carrier {
             case = 3;
         }
 
-        write!(report, "CASE {}, {}", case, break_(spoken)).unwrap();
+        values.insert("case", case.to_string());
 
         let brc = heading;
         let mut fh = heading - 9; // 9 -> 9deg angled deck
@@ -623,99 +1551,97 @@ impl Carrier {
             fh -= 360;
         }
 
-        let brc = format!("{:0>3}", brc);
-        write!(
-            report,
-            "BRC {}, {}",
-            pronounce_number(brc, spoken),
-            break_(spoken)
-        )
-        .unwrap();
+        values.insert("brc", pronounce_number(format!("{:0>3}", brc), spoken));
+        values.insert(
+            "final_heading",
+            pronounce_number(format!("{:0>3}", fh), spoken),
+        );
 
-        let fh = format!("{:0>3}", fh);
-        write!(
-            report,
-            "expected final heading {}, {}",
-            pronounce_number(fh, spoken),
-            break_(spoken),
-        )
-        .unwrap();
+        Ok(render(
+            Template::default_carrier(),
+            template,
+            &values,
+            Locale::En,
+            spoken,
+        ))
+    }
+}
 
-        report += "report initial.";
+impl WeatherTransmitter {
+    pub fn information_letter(&self, report_nr: usize) -> &'static str {
+        information_letter(self.info_ltr_override, self.info_ltr_offset, report_nr)
+    }
 
-        if spoken {
-            report += "\n</speak>";
+    pub fn report_details(
+        &self,
+        report_nr: usize,
+        weather: WeatherInfo,
+        time: Option<PrimitiveDateTime>,
+        alt: Length,
+    ) -> ReportDetails {
+        ReportDetails {
+            weather,
+            time,
+            alt,
+            information_letter: Some(self.information_letter(report_nr)),
+            active_runway: None,
         }
-
-        Ok(report)
     }
-}
 
-impl WeatherTransmitter {
     pub fn generate_report(
         &self,
         report_nr: usize,
         weather: &WeatherInfo,
         alt: Length,
+        template: Option<&Template>,
         spoken: bool,
     ) -> Result<String, anyhow::Error> {
-        let information_num = if let Some(ltr_override) = self.info_ltr_override {
-            (ltr_override.to_ascii_uppercase() as usize) - 65
-        } else {
-            self.info_ltr_offset + report_nr
-        };
-        let information_letter = phonetic_alphabet::lookup(information_num);
-        let mut report = if spoken { SPEAK_START_TAG } else { "" }.to_string();
-
-        write!(
-            report,
-            "This is weather station {} information {}. {}",
-            self.name,
-            information_letter,
-            break_(spoken)
-        )
-        .unwrap();
-
-        report += &wind_report(weather, spoken);
-        report += &ceiling_report(weather, alt, spoken);
-        report += &weather_condition_report(weather, alt, spoken);
-        report += &visibility_report(weather, alt, spoken);
-        report += &temperature_report(weather, spoken);
-        report += &altimeter_report(weather, alt, spoken);
-
-        write!(report, "REMARKS. {}", break_(spoken)).unwrap();
-        report += &hectopascal_report(weather, alt, spoken);
-        report += &qfe_report(weather, spoken);
-
-        write!(report, "End information {}.", information_letter).unwrap();
-
-        if spoken {
-            report += "\n</speak>";
-        }
+        let locale = self.locale;
+        let mut values = weather_values(weather, alt, locale, self.units, spoken);
+        values.insert("name", text_value(&self.name, spoken));
+        values.insert(
+            "information",
+            locale
+                .phonetic_letter(information_index(
+                    self.info_ltr_override,
+                    self.info_ltr_offset,
+                    report_nr,
+                ))
+                .to_string(),
+        );
 
-        Ok(report)
+        Ok(render(
+            Template::default_weather(locale),
+            template,
+            &values,
+            locale,
+            spoken,
+        ))
     }
 }
 
-fn escape_xml(s: &str) -> String {
-    s.replace('<', "&lt;").replace('&', "&amp;")
+fn information_letter(
+    ltr_override: Option<char>,
+    ltr_offset: usize,
+    report_nr: usize,
+) -> &'static str {
+    Locale::En.phonetic_letter(information_index(ltr_override, ltr_offset, report_nr))
 }
 
-mod phonetic_alphabet {
-    static PHONETIC_ALPHABET: &[&str] = &[
-        "Alpha", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India",
-        "Juliett", "Kilo", "Lima", "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo",
-        "Sierra", "Tango", "Uniform", "Victor", "Whiskey", "X-ray", "Yankee", "Zulu",
-    ];
-
-    pub fn lookup(idx: usize) -> &'static str {
-        PHONETIC_ALPHABET[idx % PHONETIC_ALPHABET.len()]
+/// The number of the information letter (`0` for Alpha) of the report `report_nr`.
+fn information_index(ltr_override: Option<char>, ltr_offset: usize, report_nr: usize) -> usize {
+    // anything but A to Z (e.g. a digit from the mission) is ignored
+    if let Some(ltr_override) = ltr_override.filter(char::is_ascii_alphabetic) {
+        (ltr_override.to_ascii_uppercase() as usize) - 65
+    } else {
+        ltr_offset + report_nr
     }
 }
 
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
+    use uom::si::f64::{Pressure, Velocity};
 
     use super::*;
     use crate::tts::TextToSpeechProvider;
@@ -724,48 +1650,248 @@ mod test {
     fn test_active_runway() {
         let airfield = Airfield {
             name: String::from("Kutaisi"),
+            icao: None,
             position: Position::default(),
-            runways: vec![String::from("04"), String::from("22R")],
+            runways: vec![Runway::new("04", "22R")],
             traffic_freq: None,
             info_ltr_offset: 0,
             info_ltr_override: None,
             active_rwy_override: None,
             no_hpa: false,
             no_qfe: false,
+            dew_point: false,
+            density_alt: false,
+            transition_alt: None,
+            runway_rules: RunwayRules::default(),
+            locale: Locale::default(),
+            units: UnitProfile::default(),
+            crosswind_limit: None,
+            tailwind_limit: None,
+        };
+
+        let wind = |dir: f64| WeatherInfo {
+            wind_speed: Velocity::new::<knot>(10.0),
+            wind_dir: Angle::new::<degree>(dir),
+            ..Default::default()
         };
 
         assert_eq!(
-            airfield.get_active_runway(Angle::new::<degree>(0.0)),
+            airfield.get_active_runway(&wind(0.0), None).as_deref(),
             Some("04")
         );
         assert_eq!(
-            airfield.get_active_runway(Angle::new::<degree>(30.0)),
+            airfield.get_active_runway(&wind(30.0), None).as_deref(),
             Some("04")
         );
         assert_eq!(
-            airfield.get_active_runway(Angle::new::<degree>(129.0)),
+            airfield.get_active_runway(&wind(129.0), None).as_deref(),
             Some("04")
         );
         assert_eq!(
-            airfield.get_active_runway(Angle::new::<degree>(311.0)),
+            airfield.get_active_runway(&wind(311.0), None).as_deref(),
             Some("04")
         );
         assert_eq!(
-            airfield.get_active_runway(Angle::new::<degree>(180.0)),
-            Some("22")
+            airfield.get_active_runway(&wind(180.0), None).as_deref(),
+            Some("22R")
         );
         assert_eq!(
-            airfield.get_active_runway(Angle::new::<degree>(270.0)),
-            Some("22")
+            airfield.get_active_runway(&wind(270.0), None).as_deref(),
+            Some("22R")
         );
         assert_eq!(
-            airfield.get_active_runway(Angle::new::<degree>(309.0)),
-            Some("22")
+            airfield.get_active_runway(&wind(309.0), None).as_deref(),
+            Some("22R")
         );
         assert_eq!(
-            airfield.get_active_runway(Angle::new::<degree>(131.0)),
+            airfield.get_active_runway(&wind(131.0), None).as_deref(),
+            Some("22R")
+        );
+    }
+
+    #[test]
+    fn test_calm_wind_runway() {
+        let airfield = Airfield {
+            name: String::from("Kutaisi"),
+            icao: None,
+            position: Position::default(),
+            runways: vec![Runway::new("04", "22")],
+            traffic_freq: None,
+            info_ltr_offset: 0,
+            info_ltr_override: None,
+            active_rwy_override: None,
+            no_hpa: false,
+            no_qfe: false,
+            dew_point: false,
+            density_alt: false,
+            transition_alt: None,
+            runway_rules: RunwayRules {
+                calm_rwy: Some(String::from("22")),
+                ..Default::default()
+            },
+            locale: Locale::default(),
+            units: UnitProfile::default(),
+            crosswind_limit: None,
+            tailwind_limit: None,
+        };
+        let wind = |speed: f64| WeatherInfo {
+            wind_speed: Velocity::new::<knot>(speed),
+            wind_dir: Angle::new::<degree>(40.0),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            airfield.get_active_runway(&wind(3.0), None).as_deref(),
             Some("22")
         );
+        assert_eq!(
+            airfield.get_active_runway(&wind(8.0), None).as_deref(),
+            Some("04")
+        );
+    }
+
+    #[test]
+    fn test_runway_rules() {
+        let mut airfield = Airfield {
+            name: String::from("Nellis"),
+            icao: None,
+            position: Position::default(),
+            runways: vec![Runway::new("03L", "21R"), Runway::new("03R", "21L")],
+            traffic_freq: None,
+            info_ltr_offset: 0,
+            info_ltr_override: None,
+            active_rwy_override: None,
+            no_hpa: false,
+            no_qfe: false,
+            dew_point: false,
+            density_alt: false,
+            transition_alt: None,
+            runway_rules: RunwayRules {
+                hysteresis: Some(4),
+                ..Default::default()
+            },
+            locale: Locale::default(),
+            units: UnitProfile::default(),
+            crosswind_limit: None,
+            tailwind_limit: None,
+        };
+        let wind = |dir: f64| WeatherInfo {
+            wind_speed: Velocity::new::<knot>(10.0),
+            wind_dir: Angle::new::<degree>(dir),
+            ..Default::default()
+        };
+
+        // keep the previous runway while its tailwind is within the hysteresis
+        assert_eq!(
+            airfield.get_active_runway(&wind(140.0), None).as_deref(),
+            Some("21L,21R")
+        );
+        assert_eq!(
+            airfield
+                .get_active_runway(&wind(140.0), Some("03L,03R"))
+                .as_deref(),
+            Some("03L,03R")
+        );
+        assert_eq!(
+            airfield
+                .get_active_runway(&wind(180.0), Some("03L,03R"))
+                .as_deref(),
+            Some("21L,21R")
+        );
+
+        // closing one end of a runway also closes the other one
+        airfield.runway_rules.closed = vec![String::from("03R")];
+        assert_eq!(
+            airfield.get_active_runway(&wind(210.0), None).as_deref(),
+            Some("21R")
+        );
+        assert_eq!(
+            airfield.get_active_runway(&wind(30.0), None).as_deref(),
+            Some("03L")
+        );
+
+        // runways are opened again via either of their ends
+        airfield.set_runway_closed("21l", false);
+        assert!(airfield.runway_rules.closed.is_empty());
+        airfield.set_runway_closed("21R", true);
+        assert_eq!(
+            airfield.get_active_runway(&wind(30.0), None).as_deref(),
+            Some("03R")
+        );
+        airfield.set_runway_closed("03L", false);
+        assert!(airfield.runway_rules.closed.is_empty());
+
+        // separate arrival and departure runways
+        airfield.runway_rules.closed = Vec::new();
+        airfield.runway_rules.arrival = vec![String::from("21L")];
+        airfield.runway_rules.departure = vec![String::from("03R"), String::from("21R")];
+        assert_eq!(
+            airfield.get_active_runway(&wind(30.0), None).as_deref(),
+            Some("21L/03R")
+        );
+        assert_eq!(
+            airfield.get_active_runway(&wind(210.0), None).as_deref(),
+            Some("21L/21R")
+        );
+    }
+
+    #[test]
+    fn test_pronounce_runway() {
+        assert_eq!(pronounce_runway("04", Locale::En, false), "04");
+        assert_eq!(pronounce_runway("04", Locale::En, true), "ZERO 4");
+        assert_eq!(pronounce_runway("22R", Locale::En, false), "22 right");
+        assert_eq!(
+            pronounce_runway("27L,27R", Locale::En, true),
+            "2 7 left and right"
+        );
+        assert_eq!(
+            pronounce_runway("27L,27C,27R", Locale::En, false),
+            "27 left, center and right"
+        );
+    }
+
+    #[test]
+    fn test_runway_wind_report() {
+        let airfield = Airfield {
+            name: String::from("Kutaisi"),
+            icao: None,
+            position: Position::default(),
+            runways: vec![Runway::new("04", "22")],
+            traffic_freq: None,
+            info_ltr_offset: 0,
+            info_ltr_override: None,
+            active_rwy_override: None,
+            no_hpa: false,
+            no_qfe: false,
+            dew_point: false,
+            density_alt: false,
+            transition_alt: None,
+            runway_rules: RunwayRules::default(),
+            locale: Locale::default(),
+            units: UnitProfile::default(),
+            crosswind_limit: Some(10),
+            tailwind_limit: None,
+        };
+        let weather = WeatherInfo {
+            wind_speed: Velocity::new::<knot>(20.0),
+            wind_dir: Angle::new::<degree>(100.0),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            airfield.runway_wind_report(Some("04"), &weather, false),
+            "Headwind 10 knots, crosswind 17 knots from the right. Caution, crosswind exceeds 10 knots"
+        );
+
+        assert_eq!(
+            airfield.runway_wind_report(Some("04/22"), &weather, false),
+            "Runway 04 headwind 10 knots, crosswind 17 knots from the right. Runway 22 tailwind 10 knots, crosswind 17 knots from the left. Caution, crosswind exceeds 10 knots. Caution, tailwind exceeds 5 knots"
+        );
+
+        assert_eq!(
+            airfield.runway_wind_report(Some("04"), &WeatherInfo::default(), false),
+            ""
+        );
     }
 
     #[tokio::test]
@@ -774,29 +1900,72 @@ mod test {
             name: String::from("Kutaisi"),
             freq: 251_000_000,
             tts: TextToSpeechProvider::default(),
+            fallback_tts: Vec::new(),
+            prosody: Prosody::default(),
             transmitter: Transmitter::Airfield(Airfield {
                 name: String::from("Kutaisi"),
+                icao: None,
                 position: Position::default(),
-                runways: vec![String::from("04"), String::from("22")],
+                runways: vec![Runway::new("04", "22")],
                 traffic_freq: Some(249_500_000),
                 info_ltr_offset: 0,
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
             }),
             ipc: MissionInterface::Static,
+            coalitions: vec![Coalition::Blue],
+            template: None,
+            radio_effects: None,
         };
 
-        let report = station.generate_report(26).await.unwrap().unwrap();
+        let report = station
+            .generate_report(26, None, None)
+            .await
+            .unwrap()
+            .unwrap();
         assert_eq!(
             report.spoken,
-            "<speak version=\"1.0\" xml:lang=\"en\">\nThis is Kutaisi information Alpha. | Runway in use is ZERO 4. | Traffic frequency 2 4 NINER DECIMAL 5. | <phoneme alphabet=\"ipa\" ph=\"w&#618;nd\">Wind</phoneme> 3 3 ZERO at 5 knots. | Temperature 2 2 celcius. | ALTIMETER 2 NINER NINER 7. | REMARKS. | 1 ZERO 1 5 hectopascal. | QFE 2 NINER NINER 7 <break time=\"500ms\" /> or 1 ZERO 1 5. | End information Alpha.\n</speak>"
+            "<speak version=\"1.0\" xml:lang=\"en\">\nThis is Kutaisi information Alpha. | Runway in use is ZERO 4. | Traffic frequency 2 4 NINER DECIMAL 5. | <phoneme alphabet=\"ipa\" ph=\"w&#618;nd\">Wind</phoneme> 3 3 ZERO at 5 knots. | Headwind 2 knots, crosswind 5 knots from the left. | Temperature 2 2 celcius. | ALTIMETER 2 NINER NINER 7. | REMARKS. | 1 ZERO 1 5 hectopascal. | QFE 2 NINER NINER 7 <break time=\"500ms\" /> or 1 ZERO 1 5. | End information Alpha.\n</speak>"
         );
         assert_eq!(
             report.textual,
-            "This is Kutaisi information Alpha. Runway in use is 04. Traffic frequency 249.5. Wind 330 at 5 knots. Temperature 22 celcius. ALTIMETER 2997. REMARKS. 1015 hectopascal. QFE 2997 or 1015. End information Alpha."
+            "This is Kutaisi information Alpha. Runway in use is 04. Traffic frequency 249.5. Wind 330 at 5 knots. Headwind 2 knots, crosswind 5 knots from the left. Temperature 22 celcius. ALTIMETER 2997. REMARKS. 1015 hectopascal. QFE 2997 or 1015. End information Alpha."
         );
+
+        // the lexicon only affects the spoken report
+        let lexicon = Lexicon::builtin();
+        let with_lexicon = station
+            .generate_report(26, None, Some(&lexicon))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(with_lexicon.spoken.starts_with(
+            "<speak version=\"1.0\" xml:lang=\"en\">\nThis is <phoneme alphabet=\"ipa\" ph=\"kʊˈtaɪsi\">Kutaisi</phoneme> information Alpha."
+        ));
+        assert_eq!(with_lexicon.textual, report.textual);
+
+        // the (English) lexicon is not applied to reports in other languages
+        let mut station = station;
+        if let Transmitter::Airfield(airfield) = &mut station.transmitter {
+            airfield.locale = Locale::De;
+        }
+        let german = station
+            .generate_report(26, None, Some(&lexicon))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!german.spoken.contains("<phoneme"));
+        assert!(german.spoken.contains("Kutaisi"));
     }
 
     #[tokio::test]
@@ -805,28 +1974,46 @@ mod test {
             name: String::from("Kutaisi"),
             freq: 251_000_000,
             tts: TextToSpeechProvider::default(),
+            fallback_tts: Vec::new(),
+            prosody: Prosody::default(),
             transmitter: Transmitter::Airfield(Airfield {
                 name: String::from("Kutaisi"),
+                icao: None,
                 position: Position::default(),
-                runways: vec![String::from("04"), String::from("22")],
+                runways: vec![Runway::new("04", "22")],
                 traffic_freq: Some(249_500_000),
                 info_ltr_offset: 15, // Should be "Papa"
                 info_ltr_override: None,
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
             }),
             ipc: MissionInterface::Static,
+            coalitions: vec![Coalition::Blue],
+            template: None,
+            radio_effects: None,
         };
 
-        let report = station.generate_report(26).await.unwrap().unwrap();
+        let report = station
+            .generate_report(26, None, None)
+            .await
+            .unwrap()
+            .unwrap();
         assert_eq!(
             report.spoken,
-            "<speak version=\"1.0\" xml:lang=\"en\">\nThis is Kutaisi information Papa. | Runway in use is ZERO 4. | Traffic frequency 2 4 NINER DECIMAL 5. | <phoneme alphabet=\"ipa\" ph=\"w&#618;nd\">Wind</phoneme> 3 3 ZERO at 5 knots. | Temperature 2 2 celcius. | ALTIMETER 2 NINER NINER 7. | REMARKS. | 1 ZERO 1 5 hectopascal. | QFE 2 NINER NINER 7 <break time=\"500ms\" /> or 1 ZERO 1 5. | End information Papa.\n</speak>"
+            "<speak version=\"1.0\" xml:lang=\"en\">\nThis is Kutaisi information Papa. | Runway in use is ZERO 4. | Traffic frequency 2 4 NINER DECIMAL 5. | <phoneme alphabet=\"ipa\" ph=\"w&#618;nd\">Wind</phoneme> 3 3 ZERO at 5 knots. | Headwind 2 knots, crosswind 5 knots from the left. | Temperature 2 2 celcius. | ALTIMETER 2 NINER NINER 7. | REMARKS. | 1 ZERO 1 5 hectopascal. | QFE 2 NINER NINER 7 <break time=\"500ms\" /> or 1 ZERO 1 5. | End information Papa.\n</speak>"
         );
         assert_eq!(
             report.textual,
-            "This is Kutaisi information Papa. Runway in use is 04. Traffic frequency 249.5. Wind 330 at 5 knots. Temperature 22 celcius. ALTIMETER 2997. REMARKS. 1015 hectopascal. QFE 2997 or 1015. End information Papa."
+            "This is Kutaisi information Papa. Runway in use is 04. Traffic frequency 249.5. Wind 330 at 5 knots. Headwind 2 knots, crosswind 5 knots from the left. Temperature 22 celcius. ALTIMETER 2997. REMARKS. 1015 hectopascal. QFE 2997 or 1015. End information Papa."
         );
     }
 
@@ -836,40 +2023,259 @@ mod test {
             name: String::from("Kutaisi"),
             freq: 251_000_000,
             tts: TextToSpeechProvider::default(),
+            fallback_tts: Vec::new(),
+            prosody: Prosody::default(),
             transmitter: Transmitter::Airfield(Airfield {
                 name: String::from("Kutaisi"),
+                icao: None,
                 position: Position::default(),
-                runways: vec![String::from("04"), String::from("22")],
+                runways: vec![Runway::new("04", "22")],
                 traffic_freq: Some(249_500_000),
                 info_ltr_offset: 15,
                 info_ltr_override: Some('Q'),
                 active_rwy_override: None,
                 no_hpa: false,
                 no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
             }),
             ipc: MissionInterface::Static,
+            coalitions: vec![Coalition::Blue],
+            template: None,
+            radio_effects: None,
         };
 
-        let report = station.generate_report(26).await.unwrap().unwrap();
+        let report = station
+            .generate_report(26, None, None)
+            .await
+            .unwrap()
+            .unwrap();
         assert_eq!(
             report.spoken,
-            "<speak version=\"1.0\" xml:lang=\"en\">\nThis is Kutaisi information Quebec. | Runway in use is ZERO 4. | Traffic frequency 2 4 NINER DECIMAL 5. | <phoneme alphabet=\"ipa\" ph=\"w&#618;nd\">Wind</phoneme> 3 3 ZERO at 5 knots. | Temperature 2 2 celcius. | ALTIMETER 2 NINER NINER 7. | REMARKS. | 1 ZERO 1 5 hectopascal. | QFE 2 NINER NINER 7 <break time=\"500ms\" /> or 1 ZERO 1 5. | End information Quebec.\n</speak>"
+            "<speak version=\"1.0\" xml:lang=\"en\">\nThis is Kutaisi information Quebec. | Runway in use is ZERO 4. | Traffic frequency 2 4 NINER DECIMAL 5. | <phoneme alphabet=\"ipa\" ph=\"w&#618;nd\">Wind</phoneme> 3 3 ZERO at 5 knots. | Headwind 2 knots, crosswind 5 knots from the left. | Temperature 2 2 celcius. | ALTIMETER 2 NINER NINER 7. | REMARKS. | 1 ZERO 1 5 hectopascal. | QFE 2 NINER NINER 7 <break time=\"500ms\" /> or 1 ZERO 1 5. | End information Quebec.\n</speak>"
         );
         assert_eq!(
             report.textual,
-            "This is Kutaisi information Quebec. Runway in use is 04. Traffic frequency 249.5. Wind 330 at 5 knots. Temperature 22 celcius. ALTIMETER 2997. REMARKS. 1015 hectopascal. QFE 2997 or 1015. End information Quebec."
+            "This is Kutaisi information Quebec. Runway in use is 04. Traffic frequency 249.5. Wind 330 at 5 knots. Headwind 2 knots, crosswind 5 knots from the left. Temperature 22 celcius. ALTIMETER 2997. REMARKS. 1015 hectopascal. QFE 2997 or 1015. End information Quebec."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_report_with_dew_point_and_density_alt() {
+        let station = Station {
+            name: String::from("Kutaisi"),
+            freq: 251_000_000,
+            tts: TextToSpeechProvider::default(),
+            fallback_tts: Vec::new(),
+            prosody: Prosody::default(),
+            transmitter: Transmitter::Airfield(Airfield {
+                name: String::from("Kutaisi"),
+                icao: None,
+                position: Position::default(),
+                runways: vec![Runway::new("04", "22")],
+                traffic_freq: None,
+                info_ltr_offset: 0,
+                info_ltr_override: None,
+                active_rwy_override: None,
+                no_hpa: true,
+                no_qfe: true,
+                dew_point: true,
+                density_alt: true,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+            }),
+            ipc: MissionInterface::Static,
+            coalitions: vec![Coalition::Blue],
+            template: None,
+            radio_effects: None,
+        };
+
+        let report = station
+            .generate_report(0, None, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            report.textual,
+            "This is Kutaisi information Alpha. Runway in use is 04. Wind 330 at 5 knots. Headwind 2 knots, crosswind 5 knots from the left. Temperature 22 celcius. Dew point 8 celcius. ALTIMETER 2997. REMARKS. Density altitude 800 feet. End information Alpha."
+        );
+    }
+
+    #[test]
+    fn test_report_templates() {
+        let airfield = Airfield {
+            name: String::from("Kutaisi"),
+            icao: None,
+            position: Position::default(),
+            runways: vec![Runway::new("04", "22")],
+            traffic_freq: None,
+            info_ltr_offset: 0,
+            info_ltr_override: None,
+            active_rwy_override: None,
+            no_hpa: true,
+            no_qfe: true,
+            dew_point: false,
+            density_alt: false,
+            transition_alt: Some(6_000),
+            runway_rules: RunwayRules::default(),
+            locale: Locale::default(),
+            units: UnitProfile::default(),
+            crosswind_limit: None,
+            tailwind_limit: None,
+        };
+        let weather = WeatherInfo {
+            temperature: Temperature::new::<degree_celsius>(15.0),
+            pressure_sealevel: Pressure::new::<hectopascal>(1013.25),
+            pressure_groundlevel: Pressure::new::<hectopascal>(1013.25),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            airfield
+                .generate_report(0, &weather, Length::zero(), Some("04"), None, false)
+                .unwrap(),
+            "This is Kutaisi information Alpha. Runway in use is 04. Wind 000 at 0 knots. Temperature 15 celcius. ALTIMETER 2992. Transition level FL70. End information Alpha."
+        );
+
+        let template = Template::from_str(
+            "{name}, information {information}.\nRunway {active_runway}.\n{traffic}.\nQFE {qfe_mmhg} millimeters.\nTransition level {flight_level}.",
+        )
+        .unwrap();
+        assert_eq!(
+            airfield
+                .generate_report(
+                    0,
+                    &weather,
+                    Length::zero(),
+                    Some("04"),
+                    Some(&template),
+                    false
+                )
+                .unwrap(),
+            "Kutaisi, information Alpha. Runway 04. QFE 760 millimeters. Transition level 70."
+        );
+        assert_eq!(
+            airfield
+                .generate_report(
+                    0,
+                    &weather,
+                    Length::zero(),
+                    Some("04"),
+                    Some(&template),
+                    true
+                )
+                .unwrap(),
+            "<speak version=\"1.0\" xml:lang=\"en\">\nKutaisi, information Alpha. | Runway ZERO 4. | QFE 7 6 ZERO millimeters. | Transition level 7 ZERO.\n</speak>"
+        );
+
+        // the template text and the names from the mission are escaped for SSML
+        let airfield = Airfield {
+            name: String::from("R&D <Range>"),
+            ..airfield
+        };
+        let template = Template::from_str("This is {name} & friends.").unwrap();
+        assert_eq!(
+            airfield
+                .generate_report(
+                    0,
+                    &weather,
+                    Length::zero(),
+                    Some("04"),
+                    Some(&template),
+                    false
+                )
+                .unwrap(),
+            "This is R&D <Range> & friends."
+        );
+        assert_eq!(
+            airfield
+                .generate_report(
+                    0,
+                    &weather,
+                    Length::zero(),
+                    Some("04"),
+                    Some(&template),
+                    true
+                )
+                .unwrap(),
+            "<speak version=\"1.0\" xml:lang=\"en\">\nThis is R&amp;D &lt;Range&gt; &amp; friends.\n</speak>"
+        );
+    }
+
+    #[test]
+    fn test_transition_level_report() {
+        let mut airfield = Airfield {
+            name: String::from("Kutaisi"),
+            icao: None,
+            position: Position::default(),
+            runways: vec![Runway::new("04", "22")],
+            traffic_freq: None,
+            info_ltr_offset: 0,
+            info_ltr_override: None,
+            active_rwy_override: None,
+            no_hpa: true,
+            no_qfe: true,
+            dew_point: false,
+            density_alt: false,
+            transition_alt: Some(6_000),
+            runway_rules: RunwayRules::default(),
+            locale: Locale::default(),
+            units: UnitProfile::default(),
+            crosswind_limit: None,
+            tailwind_limit: None,
+        };
+        let weather = WeatherInfo {
+            pressure_sealevel: Pressure::new::<hectopascal>(1013.25),
+            pressure_groundlevel: Pressure::new::<hectopascal>(1013.25),
+            ..Default::default()
+        };
+
+        let report = airfield
+            .generate_report(0, &weather, Length::zero(), Some("04"), None, false)
+            .unwrap();
+        assert!(report.contains(" Transition level FL70. "), "{report}");
+        let report = airfield
+            .generate_report(0, &weather, Length::zero(), Some("04"), None, true)
+            .unwrap();
+        assert!(
+            report.contains(" Transition level flight level 7 ZERO. | "),
+            "{report}"
         );
+
+        airfield.transition_alt = None;
+        let report = airfield
+            .generate_report(0, &weather, Length::zero(), Some("04"), None, false)
+            .unwrap();
+        assert!(!report.contains("Transition level"), "{report}");
+    }
+
+    #[test]
+    fn test_information_index() {
+        assert_eq!(information_index(None, 3, 2), 5);
+        assert_eq!(information_index(Some('c'), 3, 2), 2);
+        assert_eq!(information_index(Some('1'), 3, 2), 5);
+        assert_eq!(information_index(Some('ä'), 3, 2), 5);
     }
 
     #[test]
     fn test_phonetic_alpha_lookup() {
-        assert_eq!(phonetic_alphabet::lookup(0), "Alpha");
-        assert_eq!(phonetic_alphabet::lookup(14), "Oscar");
-        assert_eq!(phonetic_alphabet::lookup(25), "Zulu");
+        assert_eq!(Locale::En.phonetic_letter(0), "Alpha");
+        assert_eq!(Locale::En.phonetic_letter(14), "Oscar");
+        assert_eq!(Locale::En.phonetic_letter(25), "Zulu");
         //It should also wrap around if the idx is higher than 25.
-        assert_eq!(phonetic_alphabet::lookup(26), "Alpha");
-        assert_eq!(phonetic_alphabet::lookup(40), "Oscar");
-        assert_eq!(phonetic_alphabet::lookup(51), "Zulu");
+        assert_eq!(Locale::En.phonetic_letter(26), "Alpha");
+        assert_eq!(Locale::En.phonetic_letter(40), "Oscar");
+        assert_eq!(Locale::En.phonetic_letter(51), "Zulu");
     }
 
     #[tokio::test]
@@ -878,15 +2284,24 @@ mod test {
             name: String::from("Mother"),
             freq: 251_000_000,
             tts: TextToSpeechProvider::default(),
+            fallback_tts: Vec::new(),
+            prosody: Prosody::default(),
             transmitter: Transmitter::Carrier(Carrier {
                 name: "Stennis".to_string(),
                 unit_id: 42,
                 unit_name: "Stennis".to_string(),
             }),
             ipc: MissionInterface::Static,
+            coalitions: vec![Coalition::Blue],
+            template: None,
+            radio_effects: None,
         };
 
-        let report = station.generate_report(26).await.unwrap().unwrap();
+        let report = station
+            .generate_report(26, None, None)
+            .await
+            .unwrap()
+            .unwrap();
         assert_eq!(
             report.spoken,
             "<speak version=\"1.0\" xml:lang=\"en\">\n99, | Stennis's <phoneme alphabet=\"ipa\" ph=\"w&#618;nd\">wind</phoneme> 3 3 ZERO at 5 knots, | ALTIMETER 3 ZERO ZERO 5. | CASE 1, | BRC 1 8 ZERO, | expected final heading 1 7 1, | report initial.\n</speak>"
@@ -903,6 +2318,8 @@ mod test {
             name: String::from("Broadcast station"),
             freq: 251_000_000,
             tts: TextToSpeechProvider::default(),
+            fallback_tts: Vec::new(),
+            prosody: Prosody::default(),
             transmitter: Transmitter::Custom(Custom {
                 position: Some(Position::default()),
                 unit_id: 42,
@@ -910,9 +2327,16 @@ mod test {
                 message: "Hello world".to_string(),
             }),
             ipc: MissionInterface::Static,
+            coalitions: vec![Coalition::Blue],
+            template: None,
+            radio_effects: None,
         };
 
-        let report = station.generate_report(26).await.unwrap().unwrap();
+        let report = station
+            .generate_report(26, None, None)
+            .await
+            .unwrap()
+            .unwrap();
         assert_eq!(
             report.spoken,
             "<speak version=\"1.0\" xml:lang=\"en\">Hello world</speak>"
@@ -926,18 +2350,31 @@ mod test {
             name: String::from("Mother"),
             freq: 251_000_000,
             tts: TextToSpeechProvider::default(),
+            fallback_tts: Vec::new(),
+            prosody: Prosody::default(),
             transmitter: Transmitter::Weather(WeatherTransmitter {
                 name: "Mountain Range".to_string(),
+                icao: None,
                 position: Some(Position::default()),
                 unit_id: 42,
                 unit_name: "Weather Post".to_string(),
                 info_ltr_offset: 15, // Should be "Papa",
                 info_ltr_override: None,
+                winds_aloft: Vec::new(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
             }),
             ipc: MissionInterface::Static,
+            coalitions: vec![Coalition::Blue],
+            template: None,
+            radio_effects: None,
         };
 
-        let report = station.generate_report(26).await.unwrap().unwrap();
+        let report = station
+            .generate_report(26, None, None)
+            .await
+            .unwrap()
+            .unwrap();
         assert_eq!(
             report.spoken,
             "<speak version=\"1.0\" xml:lang=\"en\">\nThis is weather station Mountain Range information Papa. | <phoneme alphabet=\"ipa\" ph=\"w&#618;nd\">Wind</phoneme> 3 3 ZERO at 5 knots. | Temperature 2 2 celcius. | ALTIMETER 2 NINER NINER 7. | REMARKS. | 1 ZERO 1 5 hectopascal. | QFE 2 NINER NINER 7 <break time=\"500ms\" /> or 1 ZERO 1 5. | End information Papa.\n</speak>"
@@ -947,4 +2384,148 @@ mod test {
             "This is weather station Mountain Range information Papa. Wind 330 at 5 knots. Temperature 22 celcius. ALTIMETER 2997. REMARKS. 1015 hectopascal. QFE 2997 or 1015. End information Papa."
         );
     }
+
+    #[tokio::test]
+    async fn test_localized_weather_report() {
+        let station = Station {
+            name: String::from("Mother"),
+            freq: 251_000_000,
+            tts: TextToSpeechProvider::default(),
+            fallback_tts: Vec::new(),
+            prosody: Prosody::default(),
+            transmitter: Transmitter::Weather(WeatherTransmitter {
+                name: "Mountain Range".to_string(),
+                icao: None,
+                position: Some(Position::default()),
+                unit_id: 42,
+                unit_name: "Weather Post".to_string(),
+                info_ltr_offset: 15,
+                info_ltr_override: None,
+                winds_aloft: Vec::new(),
+                locale: Locale::De,
+                units: UnitProfile::default(),
+            }),
+            ipc: MissionInterface::Static,
+            coalitions: vec![Coalition::Blue],
+            template: None,
+            radio_effects: None,
+        };
+
+        let report = station
+            .generate_report(26, None, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            report.spoken,
+            "<speak version=\"1.0\" xml:lang=\"de\">\nHier ist Wetterstation Mountain Range Information Papa. | Wind drei drei null Grad, fünf Knoten. | Temperatur zwo zwo Grad. | Höhenmesser zwo neun neun sieben. | BEMERKUNGEN. | eins null eins fünf Hektopascal. | QFE zwo neun neun sieben <break time=\"500ms\" /> oder eins null eins fünf. | Ende Information Papa.\n</speak>"
+        );
+        assert_eq!(
+            report.textual,
+            "Hier ist Wetterstation Mountain Range Information Papa. Wind 330 Grad, 5 Knoten. Temperatur 22 Grad. Höhenmesser 2997. BEMERKUNGEN. 1015 Hektopascal. QFE 2997 oder 1015. Ende Information Papa."
+        );
+    }
+
+    #[test]
+    fn test_wind_report_with_gusts() {
+        let weather = WeatherInfo {
+            wind_speed: Velocity::new::<knot>(15.0),
+            wind_dir: Angle::new::<degree>(90.0),
+            turbulence: Velocity::new::<knot>(12.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            wind_report(&weather, Locale::En, UnitProfile::Mixed, false),
+            "Wind 090 at 15 knots, gusting 27 knots, variable between 050 and 130"
+        );
+        assert_eq!(
+            wind_report(&weather, Locale::En, UnitProfile::Mixed, true),
+            "<phoneme alphabet=\"ipa\" ph=\"w&#618;nd\">Wind</phoneme> ZERO NINER ZERO at 1 5 knots, gusting 2 7 knots, variable between ZERO 5 ZERO and 1 3 ZERO"
+        );
+    }
+
+    #[test]
+    fn test_unit_profiles() {
+        let weather = WeatherInfo {
+            wind_speed: Velocity::new::<knot>(10.0),
+            wind_dir: Angle::new::<degree>(90.0),
+            pressure_sealevel: Pressure::new::<hectopascal>(1036.0),
+            pressure_groundlevel: Pressure::new::<hectopascal>(1000.0),
+            ..Default::default()
+        };
+        let alt = Length::new::<foot>(1_000);
+
+        assert_eq!(
+            wind_report(&weather, Locale::En, UnitProfile::Soviet, false),
+            "Wind 090 at 19 kilometers per hour"
+        );
+        for (units, altimeter, qfe) in [
+            (UnitProfile::Mixed, "ALTIMETER 3062", "QFE 2953 or 1000"),
+            (UnitProfile::Faa, "ALTIMETER 3062", "QFE 2953"),
+            (UnitProfile::Icao, "QNH 1037", "QFE 1000"),
+            // QFE as the altimeter setting of Soviet-era aircraft
+            (
+                UnitProfile::Soviet,
+                "QFE 750 millimeters",
+                "QNH 778 millimeters",
+            ),
+        ] {
+            assert_eq!(
+                altimeter_report(&weather, alt, Locale::En, units, false),
+                altimeter
+            );
+            assert_eq!(qfe_report(&weather, alt, Locale::En, units, false), qfe);
+        }
+        assert_eq!(
+            hectopascal_report(&weather, alt, Locale::En, UnitProfile::Icao, false),
+            ""
+        );
+        let weather = WeatherInfo {
+            temperature: Temperature::new::<degree_celsius>(30.0),
+            pressure_sealevel: Pressure::new::<hectopascal>(1013.0),
+            pressure_groundlevel: Pressure::new::<hectopascal>(1000.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            density_altitude_report(&weather, Locale::En, UnitProfile::Mixed, false),
+            "Density altitude 2200 feet"
+        );
+        assert_eq!(
+            density_altitude_report(&weather, Locale::En, UnitProfile::Soviet, false),
+            "Density altitude 680 meters"
+        );
+    }
+
+    #[test]
+    fn test_winds_aloft_report() {
+        use crate::weather::WindAloft;
+
+        let wind = |alt: f64, dir: f64, speed: f64| WindAloft {
+            alt: LengthF64::new::<foot>(alt),
+            wind_speed: Velocity::new::<knot>(speed),
+            wind_dir: Angle::new::<degree>(dir),
+            temperature: Temperature::new::<degree_celsius>(0.0),
+        };
+        let weather = WeatherInfo {
+            winds_aloft: vec![wind(2_000.0, 268.0, 14.6), wind(26_000.0, 3.0, 61.0)],
+            ..Default::default()
+        };
+        assert_eq!(
+            winds_aloft_report(&weather, Locale::En, UnitProfile::Mixed, false),
+            "Winds aloft. 2000 feet, 270 at 15 knots. 26000 feet, 360 at 61 knots"
+        );
+        assert_eq!(
+            winds_aloft_report(&weather, Locale::En, UnitProfile::Soviet, false),
+            "Winds aloft. 600 meters, 270 at 27 kilometers per hour. 7900 meters, 360 at 113 kilometers per hour"
+        );
+        assert_eq!(
+            winds_aloft_report(
+                &WeatherInfo::default(),
+                Locale::En,
+                UnitProfile::Mixed,
+                false
+            ),
+            ""
+        );
+    }
 }
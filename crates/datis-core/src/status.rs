@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use crate::export::ExportedReport;
+use crate::station::{Report, Station, Transmitter};
+use crate::tts::TextToSpeechProvider;
+use crate::weather::metar::generate_metar;
+
+/// Keeps track of the live state of all stations, e.g. to be served by the status HTTP server.
+#[derive(Clone, Default)]
+pub struct StatusRegistry(Arc<RwLock<HashMap<String, StationStatus>>>);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StationStatus {
+    pub name: String,
+    pub freq: u64,
+    pub tts: String,
+    /// The voice that synthesized the latest report, differs from `tts` when a fallback voice had
+    /// to be used.
+    pub active_tts: Option<String>,
+    pub coalition: String,
+    pub state: ConnectionState,
+    pub report: Option<ExportedReport>,
+    pub report_ssml: Option<String>,
+    pub metar: Option<String>,
+    /// Unix timestamp (in seconds) of the next scheduled broadcast.
+    pub next_broadcast: Option<u64>,
+    pub last_error: Option<String>,
+    /// The generation of the task currently running the station, see `StatusRegistry::start_task`.
+    #[serde(skip)]
+    task: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConnectionState {
+    Stopped,
+    Connecting,
+    Connected,
+    Disconnected,
+}
+
+impl StatusRegistry {
+    /// Register the station, or update the settings of an already registered station while keeping
+    /// its latest report and state.
+    pub fn register(&self, station: &Station) {
+        let mut stations = self.0.write().unwrap();
+        let status = stations
+            .entry(station.name.clone())
+            .or_insert_with(|| StationStatus {
+                name: station.name.clone(),
+                freq: 0,
+                tts: String::new(),
+                active_tts: None,
+                coalition: String::new(),
+                state: ConnectionState::Stopped,
+                report: None,
+                report_ssml: None,
+                metar: None,
+                next_broadcast: None,
+                last_error: None,
+                task: 0,
+            });
+        status.freq = station.freq;
+        status.tts = format!("{:?}", station.tts);
+        status.active_tts = None;
+        status.coalition = station
+            .coalitions
+            .iter()
+            .map(|coalition| format!("{:?}", coalition))
+            .collect::<Vec<_>>()
+            .join(", ");
+    }
+
+    /// Start a new task for the station and return its generation. State updates of previous
+    /// tasks of the station (which might still be shutting down) are ignored from now on.
+    pub fn start_task(&self, name: &str) -> u64 {
+        let mut task = 0;
+        self.update(name, |status| {
+            status.task += 1;
+            task = status.task;
+        });
+        task
+    }
+
+    pub fn remove(&self, name: &str) {
+        self.0.write().unwrap().remove(name);
+    }
+
+    /// Set the state of the station, unless `task` has already been superseded by a newer task.
+    pub fn set_state(&self, name: &str, task: u64, state: ConnectionState) {
+        self.update(name, |status| {
+            if status.task != task {
+                return;
+            }
+            status.state = state;
+            if state != ConnectionState::Connected {
+                status.next_broadcast = None;
+            }
+        });
+    }
+
+    pub fn set_error(&self, name: &str, err: impl ToString) {
+        let err = err.to_string();
+        self.update(name, |status| status.last_error = Some(err));
+    }
+
+    pub fn clear_error(&self, name: &str) {
+        self.update(name, |status| status.last_error = None);
+    }
+
+    pub fn set_active_tts(&self, name: &str, tts: &TextToSpeechProvider) {
+        let tts = format!("{:?}", tts);
+        self.update(name, |status| status.active_tts = Some(tts));
+    }
+
+    pub fn set_report(&self, station: &Station, report: &Report) {
+        // METARs are only generated for airfield and weather stations with a known ICAO code
+        let ident = match &station.transmitter {
+            Transmitter::Airfield(airfield) => airfield.icao.as_deref(),
+            Transmitter::Weather(weather) => weather.icao.as_deref(),
+            Transmitter::Carrier(_) | Transmitter::Custom(_) => None,
+        };
+        let metar = ident.zip(report.details.as_ref()).map(|(ident, details)| {
+            // the observation time is the mission time, only static reports use the current time
+            let time = details.time.unwrap_or_else(|| {
+                let now = OffsetDateTime::now_utc();
+                PrimitiveDateTime::new(now.date(), now.time())
+            });
+            generate_metar(ident, time, &details.weather, details.alt)
+        });
+        let exported = ExportedReport::new(station, report);
+
+        self.update(&station.name, |status| {
+            status.report = Some(exported);
+            status.report_ssml = Some(report.spoken.clone());
+            status.metar = metar;
+        });
+    }
+
+    pub fn set_next_broadcast(&self, name: &str, delay: Duration) {
+        let next = SystemTime::now() + delay;
+        let next = next
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        self.update(name, |status| status.next_broadcast = Some(next));
+    }
+
+    pub fn get(&self, name: &str) -> Option<StationStatus> {
+        self.0.read().unwrap().get(name).cloned()
+    }
+
+    pub fn all(&self) -> Vec<StationStatus> {
+        let mut stations: Vec<_> = self.0.read().unwrap().values().cloned().collect();
+        stations.sort_by(|a, b| a.name.cmp(&b.name));
+        stations
+    }
+
+    fn update(&self, name: &str, f: impl FnOnce(&mut StationStatus)) {
+        if let Some(status) = self.0.write().unwrap().get_mut(name) {
+            f(status);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use time::{Date, Month, Time};
+
+    use super::*;
+    use crate::locale::Locale;
+    use crate::station::{Airfield, Coalition, MissionInterface, Position, Runway, RunwayRules};
+    use crate::tts::prosody::Prosody;
+    use crate::units::UnitProfile;
+
+    #[tokio::test]
+    async fn test_metar_mission_time() {
+        let station = Station {
+            name: String::from("Kutaisi"),
+            freq: 251_000_000,
+            tts: TextToSpeechProvider::default(),
+            fallback_tts: Vec::new(),
+            prosody: Prosody::default(),
+            transmitter: Transmitter::Airfield(Airfield {
+                name: String::from("Kutaisi"),
+                icao: Some(String::from("UGKO")),
+                position: Position::default(),
+                runways: vec![Runway::new("04", "22")],
+                traffic_freq: None,
+                info_ltr_offset: 0,
+                info_ltr_override: None,
+                active_rwy_override: None,
+                no_hpa: false,
+                no_qfe: false,
+                dew_point: false,
+                density_alt: false,
+                transition_alt: None,
+                runway_rules: RunwayRules::default(),
+                locale: Locale::default(),
+                units: UnitProfile::default(),
+                crosswind_limit: None,
+                tailwind_limit: None,
+            }),
+            ipc: MissionInterface::Static,
+            coalitions: vec![Coalition::Blue],
+            template: None,
+            radio_effects: None,
+        };
+        let mut report = station
+            .generate_report(0, None, None)
+            .await
+            .unwrap()
+            .unwrap();
+        if let Some(details) = &mut report.details {
+            details.time = Some(PrimitiveDateTime::new(
+                Date::from_calendar_date(2021, Month::June, 21).unwrap(),
+                Time::from_hms(7, 35, 0).unwrap(),
+            ));
+        }
+
+        let status = StatusRegistry::default();
+        status.register(&station);
+        status.set_report(&station, &report);
+        let metar = status.get("Kutaisi").unwrap().metar.unwrap();
+        assert_eq!(&metar[..22], "METAR UGKO 210735Z 330");
+    }
+}
@@ -0,0 +1,338 @@
+//! Templates for the wording of reports.
+//!
+//! A template is plain text with `{placeholder}`s (use `{{` and `}}` for literal braces). Every
+//! line of a template is a sentence of the report. Lines whose placeholders are all empty are
+//! skipped, so optional parts of a report (e.g. the traffic frequency) simply disappear. Empty
+//! lines and lines starting with `#` are ignored.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use crate::locale::Locale;
+use crate::utils::escape_xml;
+
+pub const DEFAULT_AIRFIELD_TEMPLATE: &str = "\
+This is {name} information {information}.
+{runway}.
+{traffic}.
+{wind}.
+{runway_wind}.
+{ceiling}.
+{weather}.
+{visibility}.
+{temperature}.
+{dew_point}.
+{altimeter}.
+{transition_level}.
+{remarks}.
+{hpa}.
+{qfe}.
+{density_altitude}.
+End information {information}.";
+
+pub const DEFAULT_CARRIER_TEMPLATE: &str = "\
+99,
+{name}'s {wind},
+{altimeter}.
+CASE {case},
+BRC {brc},
+expected final heading {final_heading},
+report initial.";
+
+pub const DEFAULT_WEATHER_TEMPLATE: &str = "\
+This is weather station {name} information {information}.
+{wind}.
+{ceiling}.
+{weather}.
+{visibility}.
+{temperature}.
+{altimeter}.
+{winds_aloft}.
+REMARKS.
+{hpa}.
+{qfe}.
+End information {information}.";
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Template {
+    lines: Vec<Vec<Part>>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Part {
+    Text(String),
+    Placeholder(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TemplateError {
+    #[error("Unclosed placeholder in line {0}")]
+    Unclosed(usize),
+    #[error("Unexpected `}}` in line {0}")]
+    UnexpectedClose(usize),
+    #[error("Invalid template name `{0}`")]
+    InvalidName(String),
+    #[error("Failed to read template {0}")]
+    Read(String, #[source] std::io::Error),
+}
+
+impl Template {
+    /// Load the template `{dir}/{name}.txt`, returns `None` if it does not exist.
+    pub fn load(dir: &Path, name: &str) -> Result<Option<Self>, TemplateError> {
+        // the name comes from the mission, so it must not point outside of the template directory
+        // (e.g. via `..`, path separators or drive prefixes like `C:`); only plain names with an
+        // optional language suffix (e.g. `soviet.ru`) are allowed
+        let is_plain = |part: &str| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        };
+        let mut parts = name.splitn(3, '.');
+        let valid = parts.next().is_some_and(is_plain)
+            && parts.next().is_none_or(is_plain)
+            && parts.next().is_none();
+        if !valid {
+            return Err(TemplateError::InvalidName(name.to_string()));
+        }
+
+        let path = dir.join(format!("{}.txt", name));
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .map_err(|err| TemplateError::Read(path.display().to_string(), err))?;
+        content.parse().map(Some)
+    }
+
+    /// The built-in airfield template of `locale`.
+    pub fn default_airfield(locale: Locale) -> &'static Template {
+        static TEMPLATES: LazyLock<[Template; 6]> = LazyLock::new(|| {
+            Locale::ALL.map(|locale| parse_builtin(locale.phrases().airfield_template))
+        });
+        &TEMPLATES[locale as usize]
+    }
+
+    /// The built-in carrier template.
+    pub fn default_carrier() -> &'static Template {
+        static TEMPLATE: LazyLock<Template> =
+            LazyLock::new(|| parse_builtin(DEFAULT_CARRIER_TEMPLATE));
+        &TEMPLATE
+    }
+
+    /// The built-in weather station template of `locale`.
+    pub fn default_weather(locale: Locale) -> &'static Template {
+        static TEMPLATES: LazyLock<[Template; 6]> = LazyLock::new(|| {
+            Locale::ALL.map(|locale| parse_builtin(locale.phrases().weather_template))
+        });
+        &TEMPLATES[locale as usize]
+    }
+
+    /// The names of all placeholders used in the template.
+    pub fn placeholders(&self) -> impl Iterator<Item = &str> {
+        self.lines.iter().flatten().filter_map(|part| match part {
+            Part::Placeholder(name) => Some(name.as_str()),
+            Part::Text(_) => None,
+        })
+    }
+
+    /// Render the template with the given `values` and join the resulting sentences with
+    /// `separator`. Unknown placeholders are rendered empty. With `ssml` set, the text of the
+    /// template is XML-escaped, while the `values` have to be valid SSML already.
+    pub fn render(&self, values: &HashMap<&str, String>, separator: &str, ssml: bool) -> String {
+        let mut sentences = Vec::with_capacity(self.lines.len());
+        for line in &self.lines {
+            let mut sentence = String::new();
+            let mut has_placeholders = false;
+            let mut has_values = false;
+            for part in line {
+                match part {
+                    Part::Text(text) if ssml => sentence += &escape_xml(text),
+                    Part::Text(text) => sentence += text,
+                    Part::Placeholder(name) => {
+                        has_placeholders = true;
+                        match values.get(name.as_str()) {
+                            Some(value) => {
+                                has_values |= !value.is_empty();
+                                sentence += value;
+                            }
+                            None => log::warn!("Unknown template placeholder {{{}}}", name),
+                        }
+                    }
+                }
+            }
+
+            if !has_placeholders || has_values {
+                sentences.push(sentence.trim().to_string());
+            }
+        }
+        sentences.join(separator)
+    }
+}
+
+fn parse_builtin(source: &str) -> Template {
+    source.parse().expect("built-in templates are valid")
+}
+
+impl FromStr for Template {
+    type Err = TemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = Vec::new();
+        for (nr, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = Vec::new();
+            let mut text = String::new();
+            let mut chars = line.chars().peekable();
+            while let Some(c) = chars.next() {
+                match c {
+                    '{' if chars.peek() == Some(&'{') => {
+                        chars.next();
+                        text.push('{');
+                    }
+                    '}' if chars.peek() == Some(&'}') => {
+                        chars.next();
+                        text.push('}');
+                    }
+                    '{' => {
+                        let mut name = String::new();
+                        loop {
+                            match chars.next() {
+                                Some('}') => break,
+                                Some(c) => name.push(c),
+                                None => return Err(TemplateError::Unclosed(nr + 1)),
+                            }
+                        }
+                        if !text.is_empty() {
+                            parts.push(Part::Text(std::mem::take(&mut text)));
+                        }
+                        parts.push(Part::Placeholder(name.trim().to_string()));
+                    }
+                    '}' => return Err(TemplateError::UnexpectedClose(nr + 1)),
+                    c => text.push(c),
+                }
+            }
+            if !text.is_empty() {
+                parts.push(Part::Text(text));
+            }
+            lines.push(parts);
+        }
+
+        Ok(Template { lines })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template =
+            Template::from_str("# comment\nThis is {name}.\n\n{traffic}.\nQFE {qfe} {{mm}}.\nEnd.")
+                .unwrap();
+        assert_eq!(
+            template.placeholders().collect::<Vec<_>>(),
+            ["name", "traffic", "qfe"]
+        );
+
+        let mut values = HashMap::new();
+        values.insert("name", String::from("Kutaisi"));
+        values.insert("traffic", String::new());
+        values.insert("qfe", String::from("745"));
+        assert_eq!(
+            template.render(&values, " | ", false),
+            "This is Kutaisi. | QFE 745 {mm}. | End."
+        );
+    }
+
+    #[test]
+    fn test_render_ssml() {
+        let template = Template::from_str("R&D <range> {name}.").unwrap();
+        let mut values = HashMap::new();
+        values.insert("name", String::from("<break time=\"500ms\" />"));
+        assert_eq!(
+            template.render(&values, " | ", false),
+            "R&D <range> <break time=\"500ms\" />."
+        );
+        assert_eq!(
+            template.render(&values, " | ", true),
+            "R&amp;D &lt;range&gt; <break time=\"500ms\" />."
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            Template::from_str("This is {name"),
+            Err(TemplateError::Unclosed(1))
+        ));
+        assert!(matches!(
+            Template::from_str("Fine\nThis is name}"),
+            Err(TemplateError::UnexpectedClose(2))
+        ));
+    }
+
+    #[test]
+    fn test_default_templates() {
+        for template in [
+            DEFAULT_AIRFIELD_TEMPLATE,
+            DEFAULT_CARRIER_TEMPLATE,
+            DEFAULT_WEATHER_TEMPLATE,
+        ] {
+            assert!(Template::from_str(template).is_ok());
+        }
+
+        // the built-in templates of all languages parse
+        for locale in Locale::ALL {
+            assert!(
+                Template::default_airfield(locale)
+                    .placeholders()
+                    .any(|name| name == "name")
+            );
+            assert!(
+                Template::default_weather(locale)
+                    .placeholders()
+                    .any(|name| name == "name")
+            );
+        }
+        assert!(
+            Template::default_carrier()
+                .placeholders()
+                .any(|name| name == "name")
+        );
+    }
+
+    #[test]
+    fn test_load_invalid_name() {
+        let dir = Path::new("templates");
+        for name in [
+            "../secret",
+            "..",
+            "a/b",
+            "a\\b",
+            "C:\\x",
+            "C:foo",
+            "soviet.ru.txt",
+            ".de",
+            "",
+        ] {
+            assert!(matches!(
+                Template::load(dir, name),
+                Err(TemplateError::InvalidName(_))
+            ));
+        }
+        assert!(matches!(Template::load(dir, "Kutaisi.de"), Ok(None)));
+    }
+}
@@ -1,40 +1,24 @@
+use std::convert::Infallible;
 use std::fmt::Display;
+use std::ops::Deref;
 use std::str::FromStr;
 
-use audiopus::coder::Encoder;
-use audiopus::{Application, Channels, SampleRate};
+use futures::future::BoxFuture;
 use rusoto_core::Region;
 use rusoto_core::request::HttpClient;
 use rusoto_credential::StaticProvider;
-use rusoto_polly::{Polly, PollyClient, SynthesizeSpeechInput};
-use serde::{Deserialize, Serialize};
-use serde_json::json;
-
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
-pub enum VoiceKind {
-    // en-AU
-    Nicole,
-    Olivia,
-    Russell,
-    // en-GB
-    Amy,
-    Emma,
-    Brian,
-    // en-IN
-    Aditi,
-    Raveena,
-    // en-US
-    Ivy,
-    Joanna,
-    Kendra,
-    Kimberly,
-    Salli,
-    Joey,
-    Justin,
-    Kevin,
-    Matthew,
-    Geraint,
-}
+use rusoto_polly::{DescribeVoicesInput, Polly, PollyClient, SynthesizeSpeechInput};
+use serde::Serialize;
+
+use super::TextToSpeech;
+use super::catalog::{self, Gender, Provider, VoiceInfo};
+use super::prosody::{Prosody, signed, wrap_prosody};
+use crate::audio::{self, AudioFormat};
+
+/// The name of an Amazon Polly voice, e.g. `Brian`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize)]
+#[serde(transparent)]
+pub struct VoiceKind(String);
 
 #[derive(Clone)]
 pub struct AmazonWebServicesConfig {
@@ -47,10 +31,21 @@ pub struct AmazonWebServicesConfig {
 pub async fn text_to_speech(
     tts: &str,
     config: &AmazonWebServicesConfig,
-) -> Result<Vec<Vec<u8>>, anyhow::Error> {
+    prosody: Prosody,
+) -> Result<Vec<i16>, anyhow::Error> {
     let dispatcher = HttpClient::new()?;
     let creds = StaticProvider::new(config.key.clone(), config.secret.clone(), None, None);
 
+    // Polly expects both the rate and the pitch in percent
+    let mut attrs = Vec::new();
+    if let Some(rate) = prosody.rate {
+        attrs.push(("rate", format!("{}%", rate)));
+    }
+    if let Some(pitch) = prosody.pitch_percent() {
+        attrs.push(("pitch", format!("{}%", signed(pitch))));
+    }
+    let tts = wrap_prosody(tts, &attrs);
+
     //Build text_to_speech request
     let req = SynthesizeSpeechInput {
         engine: None, // TODO: allow usage of neural engine (only available for certain voices and regions!)
@@ -59,7 +54,7 @@ pub async fn text_to_speech(
         output_format: "pcm".to_string(),
         sample_rate: None, // defaults to 16,000
         speech_mark_types: None,
-        text: tts.to_string(),
+        text: tts,
         text_type: Some("ssml".to_string()),
         voice_id: config.voice.to_string(),
     };
@@ -70,49 +65,81 @@ pub async fn text_to_speech(
     let audio_stream = response
         .audio_stream
         .ok_or_else(|| anyhow!("Polly response did not contain an audio stream"))?;
-    let audio_stream = vector_i16(&audio_stream);
-
-    const MONO_20MS: usize = 16000 /* * 1 channel */ * 20 / 1000;
-    let enc = Encoder::new(SampleRate::Hz16000, Channels::Mono, Application::Voip)?;
-    let mut pos = 0;
-    let mut output = [0; 256];
-    let mut frames = Vec::new();
 
-    while pos + MONO_20MS < audio_stream.len() {
-        let len = enc.encode(&audio_stream[pos..(pos + MONO_20MS)], &mut output)?;
-        frames.push(output[..len].to_vec());
+    audio::prepare(
+        audio_stream.to_vec(),
+        AudioFormat::Pcm {
+            sample_rate: 16_000,
+            channels: 1,
+        },
+    )
+    .await
+}
 
-        pos += MONO_20MS;
+impl TextToSpeech for AmazonWebServicesConfig {
+    fn text_to_speech<'a>(
+        &'a self,
+        ssml: &'a str,
+        prosody: Prosody,
+    ) -> BoxFuture<'a, Result<Vec<i16>, anyhow::Error>> {
+        Box::pin(text_to_speech(ssml, self, prosody))
     }
-
-    Ok(frames)
 }
 
-fn vector_i16(byte_stream: &[u8]) -> Vec<i16> {
-    let len = byte_stream.len();
-    let mut res: Vec<i16> = Vec::new();
-    let mut index_pos = 0;
-    //hopefully this pushed the bits 8 bits at a time into a stream of u8
-    while index_pos < len {
-        let this_byte = byte_stream[index_pos];
-        let next_byte = byte_stream[index_pos + 1];
-        let these_converted = i16::from_le_bytes([this_byte, next_byte]);
-        res.push(these_converted);
-        index_pos += 2;
+pub async fn list_voices(
+    key: &str,
+    secret: &str,
+    region: &str,
+) -> Result<Vec<VoiceInfo>, anyhow::Error> {
+    let dispatcher = HttpClient::new()?;
+    let creds = StaticProvider::new(key.to_string(), secret.to_string(), None, None);
+    let region = Region::from_str(region)?;
+    let client = PollyClient::new_with(dispatcher, creds, region);
+
+    let mut voices = Vec::new();
+    let mut next_token = None;
+    loop {
+        let res = client
+            .describe_voices(DescribeVoicesInput {
+                next_token,
+                ..Default::default()
+            })
+            .await?;
+        voices.extend(res.voices.unwrap_or_default().into_iter().filter_map(|v| {
+            Some(VoiceInfo {
+                name: v.id?,
+                language: v.language_code?,
+                gender: v.gender.as_deref().and_then(Gender::parse),
+            })
+        }));
+
+        next_token = res.next_token;
+        if next_token.is_none() {
+            break;
+        }
     }
-    res
+
+    Ok(voices)
 }
 
 impl FromStr for VoiceKind {
-    type Err = serde_json::Error;
+    type Err = Infallible;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        serde_json::from_value(json!(s))
+        Ok(VoiceKind(catalog::resolve(Provider::AmazonWebServices, s)))
+    }
+}
+
+impl Deref for VoiceKind {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
 
 impl Display for VoiceKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        f.write_str(&self.0)
     }
 }
@@ -1,11 +1,16 @@
+use std::convert::Infallible;
 use std::fmt::Display;
-use std::io::Cursor;
+use std::ops::Deref;
 use std::str::FromStr;
 
-use ogg::reading::PacketReader;
+use futures::future::BoxFuture;
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+
+use super::TextToSpeech;
+use super::catalog::{self, Gender, Provider, VoiceInfo};
+use super::prosody::{Prosody, prosody_attrs, wrap_prosody};
+use crate::audio::{self, AudioFormat};
 
 #[derive(Clone)]
 pub struct AzureCognitiveServicesConfig {
@@ -14,22 +19,24 @@ pub struct AzureCognitiveServicesConfig {
     pub region: String,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
-pub enum VoiceKind {
-    #[serde(rename = "en-US-BenjaminRUS")]
-    Benjamin,
-    #[serde(rename = "en-US-AriaRUS")]
-    Aria,
-    #[serde(rename = "en-US-ZiraRUS")]
-    Zira,
-    #[serde(rename = "en-US-GuyRUS")]
-    Guy,
+/// The short name of an Azure voice, e.g. `en-US-AriaNeural`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize)]
+#[serde(transparent)]
+pub struct VoiceKind(String);
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct ListedVoice {
+    short_name: String,
+    locale: String,
+    gender: Option<String>,
 }
 
 pub async fn text_to_speech(
     tts: &str,
     config: &AzureCognitiveServicesConfig,
-) -> Result<Vec<Vec<u8>>, anyhow::Error> {
+    prosody: Prosody,
+) -> Result<Vec<i16>, anyhow::Error> {
     let client = reqwest::Client::new();
 
     //aquire token
@@ -57,11 +64,15 @@ pub async fn text_to_speech(
     );
 
     // update lang of root XML element (`<speak />`) and wrap text in an additional `<voice />` tag
-    let voice = config.voice.to_string();
-    let (lang, _) = voice.split_at(5);
+    let voice = &config.voice;
+    let lang = voice.language();
 
+    let tts = wrap_prosody(tts, &prosody_attrs(prosody));
+    let tts = tts.as_str();
     let tts = tts
-        .strip_prefix(r#"<speak version="1.0" xml:lang="en">"#)
+        .strip_prefix("<speak")
+        .and_then(|tts| tts.split_once('>'))
+        .map(|(_, tts)| tts)
         .unwrap_or(tts);
     let tts = tts.strip_suffix(r#"</speak>"#).unwrap_or(tts);
     let tts = format!(
@@ -86,26 +97,81 @@ pub async fn text_to_speech(
     }
 
     let bytes = res.bytes().await?;
-    let data = Cursor::new(bytes);
-    let mut frames = Vec::new();
-    let mut audio = PacketReader::new(data);
-    while let Some(pck) = audio.read_packet()? {
-        frames.push(pck.data.to_vec())
+    audio::prepare(bytes.to_vec(), AudioFormat::OggOpus).await
+}
+
+impl TextToSpeech for AzureCognitiveServicesConfig {
+    fn text_to_speech<'a>(
+        &'a self,
+        ssml: &'a str,
+        prosody: Prosody,
+    ) -> BoxFuture<'a, Result<Vec<i16>, anyhow::Error>> {
+        Box::pin(text_to_speech(ssml, self, prosody))
     }
+}
 
-    Ok(frames)
+pub async fn list_voices(key: &str, region: &str) -> Result<Vec<VoiceInfo>, anyhow::Error> {
+    let url = format!(
+        "https://{}.tts.speech.microsoft.com/cognitiveservices/voices/list",
+        region
+    );
+    let res = reqwest::Client::new()
+        .get(&url)
+        .header("Ocp-Apim-Subscription-Key", key)
+        .send()
+        .await?;
+    if res.status() != StatusCode::OK {
+        let err = res.text().await?;
+        return Err(anyhow!("Azure error: {}", err));
+    }
+
+    let voices: Vec<ListedVoice> = res.json().await?;
+    Ok(voices
+        .into_iter()
+        .map(|v| VoiceInfo {
+            name: v.short_name,
+            language: v.locale,
+            gender: v.gender.as_deref().and_then(Gender::parse),
+        })
+        .collect())
+}
+
+impl VoiceKind {
+    /// The language of the voice, e.g. `en-US` for `en-US-AriaNeural`.
+    pub fn language(&self) -> String {
+        if let Ok(info) = catalog::find(Provider::AzureCognitiveServices, &self.0) {
+            return info.language;
+        }
+
+        // the language is the prefix of the voice name
+        self.0
+            .rsplit_once('-')
+            .map(|(lang, _)| lang.to_string())
+            .unwrap_or_else(|| String::from("en-US"))
+    }
 }
 
 impl FromStr for VoiceKind {
-    type Err = serde_json::Error;
+    type Err = Infallible;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        serde_json::from_value(json!(s))
+        Ok(VoiceKind(catalog::resolve(
+            Provider::AzureCognitiveServices,
+            s,
+        )))
+    }
+}
+
+impl Deref for VoiceKind {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
 
 impl Display for VoiceKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(serde_json::to_string(self).unwrap().trim_matches('"'))
+        f.write_str(&self.0)
     }
 }
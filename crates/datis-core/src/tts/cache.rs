@@ -0,0 +1,423 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+
+use futures::lock::Mutex;
+use sha2::{Digest, Sha256};
+
+use super::TextToSpeechProvider;
+use super::prosody::Prosody;
+use crate::audio::RadioEffects;
+
+/// A persistent cache of synthesized Opus frames, keyed by a hash of the TTS provider, voice and
+/// SSML. Once the cache exceeds its max size, the least recently used entries are evicted.
+#[derive(Clone)]
+pub struct AudioCache(Arc<Mutex<AudioCacheInner>>);
+
+pub struct AudioCacheInner {
+    dir: PathBuf,
+    max_size: u64,
+    size: u64,
+    entries: HashMap<String, CacheEntry>,
+}
+
+struct CacheEntry {
+    size: u64,
+    last_used: SystemTime,
+}
+
+const EXTENSION: &str = "opus-frames";
+
+impl AudioCache {
+    /// Opens the cache in `dir` and indexes the entries that already exist.
+    pub fn new(dir: impl AsRef<Path>, max_size: u64) -> Self {
+        let dir = dir.as_ref().to_path_buf();
+        let mut entries = HashMap::new();
+        if let Ok(files) = fs::read_dir(&dir) {
+            for file in files.flatten() {
+                let path = file.path();
+                if path.extension().and_then(|e| e.to_str()) != Some(EXTENSION) {
+                    continue;
+                }
+                let (Some(key), Ok(meta)) =
+                    (path.file_stem().and_then(|s| s.to_str()), file.metadata())
+                else {
+                    continue;
+                };
+                entries.insert(
+                    key.to_string(),
+                    CacheEntry {
+                        size: meta.len(),
+                        last_used: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                    },
+                );
+            }
+        }
+
+        let size = entries.values().map(|e| e.size).sum();
+        log::debug!(
+            "Opened TTS cache at {} with {} entries ({} bytes)",
+            dir.display(),
+            entries.len(),
+            size
+        );
+
+        AudioCache(Arc::new(Mutex::new(AudioCacheInner {
+            dir,
+            max_size,
+            size,
+            entries,
+        })))
+    }
+
+    pub async fn get(
+        &self,
+        tts: &TextToSpeechProvider,
+        ssml: &str,
+        prosody: Prosody,
+        effects: Option<&RadioEffects>,
+    ) -> Option<Vec<Vec<u8>>> {
+        let key = cache_key(tts, ssml, prosody, effects);
+        let path = {
+            let inner = self.0.lock().await;
+            inner.entries.get(&key)?;
+            inner.path(&key)
+        };
+
+        // the file IO happens on a blocking thread without holding the lock
+        let now = SystemTime::now();
+        let result = tokio::task::spawn_blocking({
+            let key = key.clone();
+            move || {
+                let frames = read_frames(&path)?;
+                // persist the access time for the LRU order to survive restarts
+                if let Err(err) = File::options()
+                    .append(true)
+                    .open(&path)
+                    .and_then(|f| f.set_modified(now))
+                {
+                    log::debug!("Failed to update TTS cache entry {}: {}", key, err);
+                }
+                Ok::<_, io::Error>(frames)
+            }
+        })
+        .await
+        .map_err(io::Error::other)
+        .and_then(|result| result);
+
+        match result {
+            Ok(frames) => {
+                if let Some(entry) = self.0.lock().await.entries.get_mut(&key) {
+                    entry.last_used = now;
+                }
+                Some(frames)
+            }
+            Err(err) => {
+                log::warn!("Failed to read TTS cache entry {}: {}", key, err);
+                let removed = self.0.lock().await.remove(&key);
+                remove_files(removed).await;
+                None
+            }
+        }
+    }
+
+    pub async fn put(
+        &self,
+        tts: &TextToSpeechProvider,
+        ssml: &str,
+        prosody: Prosody,
+        effects: Option<&RadioEffects>,
+        frames: &[Vec<u8>],
+    ) -> Result<(), io::Error> {
+        let key = cache_key(tts, ssml, prosody, effects);
+        let (dir, path) = {
+            let inner = self.0.lock().await;
+            (inner.dir.clone(), inner.path(&key))
+        };
+
+        // the file IO happens on a blocking thread without holding the lock
+        let frames = frames.to_vec();
+        let size = tokio::task::spawn_blocking(move || {
+            fs::create_dir_all(&dir)?;
+            // write to a temporary file first to never read a partially written entry (unique
+            // per write, in case the same entry is written concurrently)
+            static TMP_NR: AtomicU64 = AtomicU64::new(0);
+            let tmp_path =
+                path.with_extension(format!("{}.tmp", TMP_NR.fetch_add(1, Ordering::Relaxed)));
+            let size = write_frames(&tmp_path, &frames)?;
+            fs::rename(&tmp_path, &path)?;
+            Ok::<_, io::Error>(size)
+        })
+        .await
+        .map_err(io::Error::other)??;
+
+        let evicted = {
+            let mut inner = self.0.lock().await;
+            inner.remove_entry(&key);
+            inner.size += size;
+            inner.entries.insert(
+                key,
+                CacheEntry {
+                    size,
+                    last_used: SystemTime::now(),
+                },
+            );
+            inner.evict()
+        };
+        remove_files(evicted).await;
+
+        Ok(())
+    }
+}
+
+impl AudioCacheInner {
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(key).with_extension(EXTENSION)
+    }
+
+    fn remove_entry(&mut self, key: &str) -> bool {
+        match self.entries.remove(key) {
+            Some(entry) => {
+                self.size -= entry.size;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove the entry from the index and return its file to be deleted.
+    fn remove(&mut self, key: &str) -> Vec<PathBuf> {
+        if self.remove_entry(key) {
+            vec![self.path(key)]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Remove the least recently used entries from the index until the cache fits into its max
+    /// size and return their files to be deleted.
+    fn evict(&mut self) -> Vec<PathBuf> {
+        let mut evicted = Vec::new();
+        while self.size > self.max_size {
+            let Some(key) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            log::debug!("Evicting TTS cache entry {}", key);
+            evicted.extend(self.remove(&key));
+        }
+        evicted
+    }
+}
+
+/// Delete the files of removed cache entries on a blocking thread.
+async fn remove_files(paths: Vec<PathBuf>) {
+    if paths.is_empty() {
+        return;
+    }
+
+    let _ = tokio::task::spawn_blocking(move || {
+        for path in paths {
+            if let Err(err) = fs::remove_file(&path) {
+                log::debug!(
+                    "Failed to delete TTS cache entry {}: {}",
+                    path.display(),
+                    err
+                );
+            }
+        }
+    })
+    .await;
+}
+
+fn cache_key(
+    tts: &TextToSpeechProvider,
+    ssml: &str,
+    prosody: Prosody,
+    effects: Option<&RadioEffects>,
+) -> String {
+    // the serialized provider contains both the provider and the voice, e.g. `AWS:Brian`
+    let tts = serde_json::to_string(tts).unwrap_or_default();
+    let mut hasher = Sha256::new();
+    hasher.update(tts.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(ssml.as_bytes());
+    // only hash non-default values to keep the keys of existing entries
+    if prosody != Prosody::default() {
+        hasher.update(b"\n");
+        hasher.update(
+            serde_json::to_string(&prosody)
+                .unwrap_or_default()
+                .as_bytes(),
+        );
+    }
+    if let Some(effects) = effects {
+        hasher.update(b"\n");
+        hasher.update(
+            serde_json::to_string(effects)
+                .unwrap_or_default()
+                .as_bytes(),
+        );
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Frames are stored one after another, each prefixed with its length (u16, little endian).
+fn write_frames(path: &Path, frames: &[Vec<u8>]) -> Result<u64, io::Error> {
+    let mut data = Vec::with_capacity(frames.iter().map(|f| f.len() + 2).sum());
+    for frame in frames {
+        let len = u16::try_from(frame.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Opus frame too large"))?;
+        data.extend_from_slice(&len.to_le_bytes());
+        data.extend_from_slice(frame);
+    }
+    File::create(path)?.write_all(&data)?;
+    Ok(data.len() as u64)
+}
+
+fn read_frames(path: &Path) -> Result<Vec<Vec<u8>>, io::Error> {
+    let mut data = Vec::new();
+    File::open(path)?.read_to_end(&mut data)?;
+
+    let mut frames = Vec::new();
+    let mut rest = data.as_slice();
+    while let [a, b, tail @ ..] = rest {
+        let len = u16::from_le_bytes([*a, *b]) as usize;
+        if tail.len() < len {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "truncated cache entry",
+            ));
+        }
+        frames.push(tail[..len].to_vec());
+        rest = &tail[len..];
+    }
+
+    Ok(frames)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("datis-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_cache_roundtrip() {
+        let dir = temp_dir("tts-cache-roundtrip");
+        let tts = TextToSpeechProvider::default();
+        let frames = vec![vec![1, 2, 3], vec![], vec![4]];
+
+        let cache = AudioCache::new(&dir, 1024);
+        assert_eq!(
+            cache
+                .get(&tts, "<speak>Hello</speak>", Prosody::default(), None)
+                .await,
+            None
+        );
+        cache
+            .put(
+                &tts,
+                "<speak>Hello</speak>",
+                Prosody::default(),
+                None,
+                &frames,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            cache
+                .get(&tts, "<speak>Hello</speak>", Prosody::default(), None)
+                .await,
+            Some(frames.clone())
+        );
+        assert_eq!(
+            cache
+                .get(&tts, "<speak>Bye</speak>", Prosody::default(), None)
+                .await,
+            None
+        );
+        let faster = Prosody {
+            rate: Some(110),
+            ..Default::default()
+        };
+        assert_eq!(
+            cache.get(&tts, "<speak>Hello</speak>", faster, None).await,
+            None
+        );
+
+        // entries survive restarts
+        let cache = AudioCache::new(&dir, 1024);
+        assert_eq!(
+            cache
+                .get(&tts, "<speak>Hello</speak>", Prosody::default(), None)
+                .await,
+            Some(frames)
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cache_lru_eviction() {
+        let dir = temp_dir("tts-cache-eviction");
+        let tts = TextToSpeechProvider::default();
+        let frame = vec![vec![0; 98]]; // 100 bytes per entry
+
+        let cache = AudioCache::new(&dir, 250);
+        cache
+            .put(&tts, "a", Prosody::default(), None, &frame)
+            .await
+            .unwrap();
+        cache
+            .put(&tts, "b", Prosody::default(), None, &frame)
+            .await
+            .unwrap();
+        // mark `a` as recently used
+        assert!(
+            cache
+                .get(&tts, "a", Prosody::default(), None)
+                .await
+                .is_some()
+        );
+        cache
+            .put(&tts, "c", Prosody::default(), None, &frame)
+            .await
+            .unwrap();
+
+        assert!(
+            cache
+                .get(&tts, "a", Prosody::default(), None)
+                .await
+                .is_some()
+        );
+        assert!(
+            cache
+                .get(&tts, "b", Prosody::default(), None)
+                .await
+                .is_none()
+        );
+        assert!(
+            cache
+                .get(&tts, "c", Prosody::default(), None)
+                .await
+                .is_some()
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
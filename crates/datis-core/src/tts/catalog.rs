@@ -0,0 +1,562 @@
+//! Catalog of the voices offered by the TTS providers.
+//!
+//! The catalog starts out with a built-in list of well-known voices and is updated with the voice
+//! lists fetched from the providers. The fetched lists are cached on disk (`voices.json`), so
+//! newly released voices can be used without a DATIS release.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use super::{aws, azure, gcloud, win};
+use crate::config::Config;
+
+const CACHE_FILE: &str = "voices.json";
+/// How long a cached voice list is used before it is fetched again.
+const MAX_CACHE_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// How long to wait before fetching the voice lists again after all providers failed.
+const RETRY_DELAY: Duration = Duration::from_secs(24 * 60 * 60);
+/// Marks the last refresh in which all providers failed.
+const FAILED_FILE: &str = "voices.failed";
+/// Max time to wait for a provider to respond with its voice list.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum Provider {
+    GoogleCloud,
+    AmazonWebServices,
+    AzureCognitiveServices,
+    Windows,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum Gender {
+    Female,
+    Male,
+    Neutral,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct VoiceInfo {
+    pub name: String,
+    /// The language of the voice, e.g. `en-US`.
+    pub language: String,
+    pub gender: Option<Gender>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub struct UnknownVoice {
+    pub name: String,
+    pub suggestions: Vec<String>,
+}
+
+static CATALOG: LazyLock<RwLock<HashMap<Provider, Vec<VoiceInfo>>>> = LazyLock::new(|| {
+    RwLock::new(
+        Provider::ALL
+            .into_iter()
+            .map(|provider| (provider, provider.fallback_voices()))
+            .collect(),
+    )
+});
+
+/// Retired voices that are replaced by a current voice of the same provider.
+const ALIASES: &[(Provider, &str, &str)] = &[
+    (
+        Provider::AzureCognitiveServices,
+        "en-US-AriaRUS",
+        "en-US-AriaNeural",
+    ),
+    (
+        Provider::AzureCognitiveServices,
+        "en-US-BenjaminRUS",
+        "en-US-GuyNeural",
+    ),
+    (
+        Provider::AzureCognitiveServices,
+        "en-US-GuyRUS",
+        "en-US-GuyNeural",
+    ),
+    (
+        Provider::AzureCognitiveServices,
+        "en-US-ZiraRUS",
+        "en-US-JennyNeural",
+    ),
+];
+
+impl Provider {
+    pub const ALL: [Provider; 4] = [
+        Provider::GoogleCloud,
+        Provider::AmazonWebServices,
+        Provider::AzureCognitiveServices,
+        Provider::Windows,
+    ];
+
+    /// The voices known without fetching the voice list from the provider.
+    fn fallback_voices(self) -> Vec<VoiceInfo> {
+        use Gender::*;
+
+        let voices: &[(&str, &str, Gender)] = match self {
+            Provider::GoogleCloud => &[
+                ("en-AU-Standard-A", "en-AU", Female),
+                ("en-AU-Standard-B", "en-AU", Male),
+                ("en-AU-Standard-C", "en-AU", Female),
+                ("en-AU-Standard-D", "en-AU", Male),
+                ("en-AU-Wavenet-A", "en-AU", Female),
+                ("en-AU-Wavenet-B", "en-AU", Male),
+                ("en-AU-Wavenet-C", "en-AU", Female),
+                ("en-AU-Wavenet-D", "en-AU", Male),
+                ("en-IN-Standard-A", "en-IN", Female),
+                ("en-IN-Standard-B", "en-IN", Male),
+                ("en-IN-Standard-C", "en-IN", Male),
+                ("en-IN-Standard-D", "en-IN", Female),
+                ("en-IN-Wavenet-A", "en-IN", Female),
+                ("en-IN-Wavenet-B", "en-IN", Male),
+                ("en-IN-Wavenet-C", "en-IN", Male),
+                ("en-IN-Wavenet-D", "en-IN", Female),
+                ("en-GB-Standard-A", "en-GB", Female),
+                ("en-GB-Standard-B", "en-GB", Male),
+                ("en-GB-Standard-C", "en-GB", Female),
+                ("en-GB-Standard-D", "en-GB", Male),
+                ("en-GB-Standard-F", "en-GB", Female),
+                ("en-GB-Wavenet-A", "en-GB", Female),
+                ("en-GB-Wavenet-B", "en-GB", Male),
+                ("en-GB-Wavenet-C", "en-GB", Female),
+                ("en-GB-Wavenet-D", "en-GB", Male),
+                ("en-GB-Wavenet-F", "en-GB", Female),
+                ("en-US-Standard-B", "en-US", Male),
+                ("en-US-Standard-C", "en-US", Female),
+                ("en-US-Standard-D", "en-US", Male),
+                ("en-US-Standard-E", "en-US", Female),
+                ("en-US-Standard-G", "en-US", Female),
+                ("en-US-Standard-H", "en-US", Female),
+                ("en-US-Standard-I", "en-US", Male),
+                ("en-US-Standard-J", "en-US", Male),
+                ("en-US-Wavenet-A", "en-US", Male),
+                ("en-US-Wavenet-B", "en-US", Male),
+                ("en-US-Wavenet-C", "en-US", Female),
+                ("en-US-Wavenet-D", "en-US", Male),
+                ("en-US-Wavenet-E", "en-US", Female),
+                ("en-US-Wavenet-F", "en-US", Female),
+                ("en-US-Wavenet-G", "en-US", Female),
+                ("en-US-Wavenet-H", "en-US", Female),
+                ("en-US-Wavenet-I", "en-US", Male),
+                ("en-US-Wavenet-J", "en-US", Male),
+                ("de-DE-Standard-A", "de-DE", Female),
+                ("de-DE-Standard-B", "de-DE", Male),
+                ("de-DE-Wavenet-A", "de-DE", Female),
+                ("de-DE-Wavenet-B", "de-DE", Male),
+                ("fr-FR-Standard-A", "fr-FR", Female),
+                ("fr-FR-Standard-B", "fr-FR", Male),
+                ("fr-FR-Wavenet-A", "fr-FR", Female),
+                ("fr-FR-Wavenet-B", "fr-FR", Male),
+                ("es-ES-Standard-A", "es-ES", Female),
+                ("es-ES-Standard-B", "es-ES", Male),
+                ("es-ES-Wavenet-A", "es-ES", Female),
+                ("es-ES-Wavenet-B", "es-ES", Male),
+                ("ru-RU-Standard-A", "ru-RU", Female),
+                ("ru-RU-Standard-B", "ru-RU", Male),
+                ("ru-RU-Wavenet-A", "ru-RU", Female),
+                ("ru-RU-Wavenet-B", "ru-RU", Male),
+                ("cmn-CN-Standard-A", "cmn-CN", Female),
+                ("cmn-CN-Standard-B", "cmn-CN", Male),
+                ("cmn-CN-Wavenet-A", "cmn-CN", Female),
+                ("cmn-CN-Wavenet-B", "cmn-CN", Male),
+            ],
+            Provider::AmazonWebServices => &[
+                ("Nicole", "en-AU", Female),
+                ("Olivia", "en-AU", Female),
+                ("Russell", "en-AU", Male),
+                ("Amy", "en-GB", Female),
+                ("Emma", "en-GB", Female),
+                ("Brian", "en-GB", Male),
+                ("Aditi", "en-IN", Female),
+                ("Raveena", "en-IN", Female),
+                ("Ivy", "en-US", Female),
+                ("Joanna", "en-US", Female),
+                ("Kendra", "en-US", Female),
+                ("Kimberly", "en-US", Female),
+                ("Salli", "en-US", Female),
+                ("Joey", "en-US", Male),
+                ("Justin", "en-US", Male),
+                ("Kevin", "en-US", Male),
+                ("Matthew", "en-US", Male),
+                ("Geraint", "en-GB-WLS", Male),
+                ("Marlene", "de-DE", Female),
+                ("Vicki", "de-DE", Female),
+                ("Hans", "de-DE", Male),
+                ("Conchita", "es-ES", Female),
+                ("Lucia", "es-ES", Female),
+                ("Enrique", "es-ES", Male),
+                ("Celine", "fr-FR", Female),
+                ("Lea", "fr-FR", Female),
+                ("Mathieu", "fr-FR", Male),
+                ("Tatyana", "ru-RU", Female),
+                ("Maxim", "ru-RU", Male),
+                ("Zhiyu", "cmn-CN", Female),
+            ],
+            Provider::AzureCognitiveServices => &[
+                ("en-US-AriaNeural", "en-US", Female),
+                ("en-US-GuyNeural", "en-US", Male),
+                ("en-US-JennyNeural", "en-US", Female),
+                ("en-US-DavisNeural", "en-US", Male),
+                ("en-GB-SoniaNeural", "en-GB", Female),
+                ("en-GB-RyanNeural", "en-GB", Male),
+                ("en-AU-NatashaNeural", "en-AU", Female),
+                ("en-AU-WilliamNeural", "en-AU", Male),
+                ("de-DE-KatjaNeural", "de-DE", Female),
+                ("de-DE-ConradNeural", "de-DE", Male),
+                ("es-ES-ElviraNeural", "es-ES", Female),
+                ("es-ES-AlvaroNeural", "es-ES", Male),
+                ("fr-FR-DeniseNeural", "fr-FR", Female),
+                ("fr-FR-HenriNeural", "fr-FR", Male),
+                ("ru-RU-SvetlanaNeural", "ru-RU", Female),
+                ("ru-RU-DmitryNeural", "ru-RU", Male),
+                ("zh-CN-XiaoxiaoNeural", "zh-CN", Female),
+                ("zh-CN-YunxiNeural", "zh-CN", Male),
+            ],
+            Provider::Windows => &[
+                ("Catherine", "en-AU", Female),
+                ("James", "en-AU", Male),
+                ("Linda", "en-CA", Female),
+                ("Richard", "en-CA", Male),
+                ("George", "en-GB", Male),
+                ("Hazel", "en-GB", Female),
+                ("Susan", "en-GB", Female),
+                ("Sean", "en-IE", Male),
+                ("Heera", "en-IN", Female),
+                ("Ravi", "en-IN", Male),
+                ("David", "en-US", Male),
+                ("Zira", "en-US", Female),
+                ("Mark", "en-US", Male),
+                ("Hedda", "de-DE", Female),
+                ("Katja", "de-DE", Female),
+                ("Stefan", "de-DE", Male),
+                ("Helena", "es-ES", Female),
+                ("Laura", "es-ES", Female),
+                ("Pablo", "es-ES", Male),
+                ("Hortense", "fr-FR", Female),
+                ("Julie", "fr-FR", Female),
+                ("Paul", "fr-FR", Male),
+                ("Irina", "ru-RU", Female),
+                ("Pavel", "ru-RU", Male),
+                ("Huihui", "zh-CN", Female),
+                ("Kangkang", "zh-CN", Male),
+                ("Yaoyao", "zh-CN", Female),
+            ],
+        };
+
+        voices
+            .iter()
+            .map(|(name, language, gender)| VoiceInfo {
+                name: name.to_string(),
+                language: language.to_string(),
+                gender: Some(*gender),
+            })
+            .collect()
+    }
+}
+
+/// Looks up the voice `name` (case-insensitive) of the given `provider`.
+pub fn find(provider: Provider, name: &str) -> Result<VoiceInfo, UnknownVoice> {
+    let catalog = CATALOG.read().unwrap();
+    let voices = catalog
+        .get(&provider)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    if let Some(voice) = voices.iter().find(|v| v.name.eq_ignore_ascii_case(name)) {
+        return Ok(voice.clone());
+    }
+
+    Err(UnknownVoice {
+        name: name.to_string(),
+        suggestions: suggest(voices, name),
+    })
+}
+
+/// The canonical name of the voice `name` of the given `provider`. Retired voices are replaced by
+/// their successor. Unknown voices are used as is (with a warning), as the catalog might just be
+/// outdated.
+pub fn resolve(provider: Provider, name: &str) -> String {
+    if let Some((_, alias, voice)) = ALIASES
+        .iter()
+        .find(|(p, alias, _)| *p == provider && alias.eq_ignore_ascii_case(name))
+    {
+        log::warn!(
+            "The voice `{}` is retired, using `{}` instead",
+            alias,
+            voice
+        );
+        return voice.to_string();
+    }
+
+    match find(provider, name) {
+        Ok(info) => info.name,
+        Err(err) => {
+            log::warn!("{} (of {:?}), using it anyway", err, provider);
+            name.to_string()
+        }
+    }
+}
+
+/// All known voices of the given `provider`.
+pub fn voices(provider: Provider) -> Vec<VoiceInfo> {
+    CATALOG
+        .read()
+        .unwrap()
+        .get(&provider)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// The names of up to three voices that are close to `name`, closest first.
+fn suggest(voices: &[VoiceInfo], name: &str) -> Vec<String> {
+    let name = name.to_lowercase();
+    let max_distance = (name.chars().count() / 3).max(2);
+    let mut candidates = voices
+        .iter()
+        .filter_map(|v| {
+            let distance = levenshtein(&v.name.to_lowercase(), &name);
+            (distance <= max_distance).then_some((distance, &v.name))
+        })
+        .collect::<Vec<_>>();
+    candidates.sort();
+    candidates
+        .into_iter()
+        .take(3)
+        .map(|(_, name)| name.clone())
+        .collect()
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev + usize::from(ca != *cb);
+            prev = row[j + 1];
+            row[j + 1] = substitution.min(prev + 1).min(row[j] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Loads the voice lists cached in `dir` and lists the voices installed on Windows. Providers
+/// without a list keep using the built-in list.
+pub fn load(dir: &Path) {
+    // The Windows voices depend on the machine and are therefore listed instead of cached
+    match win::list_voices() {
+        Ok(voices) if !voices.is_empty() => {
+            CATALOG.write().unwrap().insert(Provider::Windows, voices);
+        }
+        Ok(_) => {}
+        Err(err) => log::warn!("Failed to list the Windows voices: {}", err),
+    }
+
+    let path = dir.join(CACHE_FILE);
+    let cached = match fs::read(&path) {
+        Ok(data) => data,
+        Err(_) => return,
+    };
+    let cached: HashMap<Provider, Vec<VoiceInfo>> = match serde_json::from_slice(&cached) {
+        Ok(cached) => cached,
+        Err(err) => {
+            log::warn!("Ignoring invalid voice cache {}: {}", path.display(), err);
+            return;
+        }
+    };
+
+    let mut catalog = CATALOG.write().unwrap();
+    for (provider, voices) in cached {
+        if !voices.is_empty() {
+            catalog.insert(provider, voices);
+        }
+    }
+}
+
+/// Fetches the voice lists of all configured providers and caches them in `dir`. The cached lists
+/// are only fetched again once they are older than a week. Failing providers keep their current
+/// list. If all providers fail, the next attempt is made after a day.
+pub async fn refresh(dir: &Path, config: &Config) {
+    let path = dir.join(CACHE_FILE);
+    let is_fresh = fs::metadata(&path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age < MAX_CACHE_AGE);
+    let failed_path = dir.join(FAILED_FILE);
+    let failed_recently = fs::metadata(&failed_path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age < RETRY_DELAY);
+    if is_fresh || failed_recently {
+        return;
+    }
+
+    let mut fetched = HashMap::new();
+    if let Some(ref gcloud) = config.gcloud {
+        let provider = Provider::GoogleCloud;
+        if let Some(voices) = fetch(provider, gcloud::list_voices(&gcloud.key)).await {
+            fetched.insert(provider, voices);
+        }
+    }
+    if let Some(ref aws) = config.aws {
+        let provider = Provider::AmazonWebServices;
+        let list = aws::list_voices(&aws.key, &aws.secret, &aws.region);
+        if let Some(voices) = fetch(provider, list).await {
+            fetched.insert(provider, voices);
+        }
+    }
+    if let Some(ref azure) = config.azure {
+        let provider = Provider::AzureCognitiveServices;
+        let list = azure::list_voices(&azure.key, &azure.region);
+        if let Some(voices) = fetch(provider, list).await {
+            fetched.insert(provider, voices);
+        }
+    }
+    if fetched.is_empty() {
+        let attempted = config.gcloud.is_some() || config.aws.is_some() || config.azure.is_some();
+        if attempted {
+            // don't try again on every mission start if the providers are unreachable
+            if let Err(err) = fs::create_dir_all(dir).and_then(|_| fs::write(&failed_path, b"")) {
+                log::warn!("Failed to write {}: {}", failed_path.display(), err);
+            }
+        }
+        return;
+    }
+    let _ = fs::remove_file(&failed_path);
+
+    let cache = {
+        let mut catalog = CATALOG.write().unwrap();
+        catalog.extend(fetched);
+        catalog
+            .iter()
+            .filter(|(provider, _)| **provider != Provider::Windows)
+            .map(|(provider, voices)| (*provider, voices.clone()))
+            .collect::<HashMap<_, _>>()
+    };
+    if let Err(err) =
+        fs::create_dir_all(dir).and_then(|_| fs::write(&path, serde_json::to_vec_pretty(&cache)?))
+    {
+        log::warn!("Failed to cache voices in {}: {}", path.display(), err);
+    }
+}
+
+/// Same as [refresh], but runs on a separate thread for callers that are not running inside of an
+/// async runtime. The refreshed voice lists are used for voices parsed afterwards (e.g. on the
+/// next mission start).
+pub fn refresh_in_background(dir: PathBuf, config: Config) {
+    let result = std::thread::Builder::new()
+        .name("voice-catalog".to_string())
+        .spawn(move || {
+            match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(runtime) => runtime.block_on(refresh(&dir, &config)),
+                Err(err) => log::warn!("Failed to refresh the voice catalog: {}", err),
+            }
+        });
+    if let Err(err) = result {
+        log::warn!("Failed to refresh the voice catalog: {}", err);
+    }
+}
+
+async fn fetch(
+    provider: Provider,
+    list: impl Future<Output = Result<Vec<VoiceInfo>, anyhow::Error>>,
+) -> Option<Vec<VoiceInfo>> {
+    match tokio::time::timeout(FETCH_TIMEOUT, list).await {
+        Ok(Ok(voices)) if !voices.is_empty() => {
+            log::debug!("Fetched {} voices of {:?}", voices.len(), provider);
+            Some(voices)
+        }
+        Ok(Ok(_)) => None,
+        Ok(Err(err)) => {
+            log::warn!("Failed to fetch the voices of {:?}: {}", provider, err);
+            None
+        }
+        Err(_) => {
+            log::warn!("Timeout fetching the voices of {:?}", provider);
+            None
+        }
+    }
+}
+
+impl fmt::Display for UnknownVoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown voice `{}`", self.name)?;
+        if !self.suggestions.is_empty() {
+            write!(f, ", did you mean `{}`?", self.suggestions.join("`, `"))?;
+        }
+        Ok(())
+    }
+}
+
+impl Gender {
+    pub(crate) fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "female" => Some(Gender::Female),
+            "male" => Some(Gender::Male),
+            "neutral" => Some(Gender::Neutral),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(
+            find(Provider::GoogleCloud, "en-us-wavenet-a").unwrap(),
+            VoiceInfo {
+                name: "en-US-Wavenet-A".to_string(),
+                language: "en-US".to_string(),
+                gender: Some(Gender::Male),
+            }
+        );
+
+        let err = find(Provider::AmazonWebServices, "Brain").unwrap_err();
+        assert_eq!(err.suggestions, ["Brian"]);
+        assert_eq!(
+            err.to_string(),
+            "Unknown voice `Brain`, did you mean `Brian`?"
+        );
+
+        let err = find(Provider::AzureCognitiveServices, "foobar").unwrap_err();
+        assert_eq!(err.to_string(), "Unknown voice `foobar`");
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(resolve(Provider::AmazonWebServices, "matthew"), "Matthew");
+        assert_eq!(
+            resolve(Provider::AzureCognitiveServices, "en-us-ariarus"),
+            "en-US-AriaNeural"
+        );
+        // unknown voices are passed through as the catalog might be outdated
+        assert_eq!(
+            resolve(Provider::GoogleCloud, "en-US-Chirp3-HD-Puck"),
+            "en-US-Chirp3-HD-Puck"
+        );
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("brian", "brian"), 0);
+    }
+}
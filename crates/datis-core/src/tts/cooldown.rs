@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::TextToSpeechProvider;
+
+/// Keeps track of the TTS providers that recently failed, shared by all stations. Failures like an
+/// expired key or an exceeded quota affect all voices of a provider, so every voice of a provider
+/// on cooldown is skipped in favor of the next voice of a station's fallback chain until the
+/// cooldown expired.
+#[derive(Clone, Default)]
+pub struct TtsCooldowns(Arc<Mutex<HashMap<String, Instant>>>);
+
+impl TtsCooldowns {
+    /// Puts the provider of `tts` on cooldown for the given `duration`.
+    pub fn start(&self, tts: &TextToSpeechProvider, duration: Duration) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(tts.prefix().to_string(), Instant::now() + duration);
+    }
+
+    /// Whether the provider of `tts` is on cooldown.
+    pub fn is_active(&self, tts: &TextToSpeechProvider) -> bool {
+        let mut cooldowns = self.0.lock().unwrap();
+        match cooldowns.get(tts.prefix()) {
+            Some(until) if *until > Instant::now() => true,
+            Some(_) => {
+                cooldowns.remove(tts.prefix());
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use super::TtsCooldowns;
+    use crate::tts::TextToSpeechProvider;
+
+    #[test]
+    fn test_cooldown() {
+        let cooldowns = TtsCooldowns::default();
+        let tts = TextToSpeechProvider::default();
+        assert!(!cooldowns.is_active(&tts));
+
+        cooldowns.start(&tts, Duration::from_secs(60));
+        assert!(cooldowns.is_active(&tts));
+
+        cooldowns.start(&tts, Duration::ZERO);
+        assert!(!cooldowns.is_active(&tts));
+    }
+
+    #[test]
+    fn test_cooldown_per_provider() {
+        let cooldowns = TtsCooldowns::default();
+        cooldowns.start(
+            &TextToSpeechProvider::from_str("AWS:Matthew").unwrap(),
+            Duration::from_secs(60),
+        );
+        assert!(cooldowns.is_active(&TextToSpeechProvider::from_str("AWS:Brian").unwrap()));
+        assert!(
+            !cooldowns.is_active(&TextToSpeechProvider::from_str("GC:en-US-Wavenet-B").unwrap())
+        );
+    }
+}
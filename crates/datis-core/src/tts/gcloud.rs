@@ -1,11 +1,17 @@
+use std::convert::Infallible;
 use std::fmt::Display;
-use std::io::Cursor;
+use std::ops::Deref;
 use std::str::FromStr;
 
-use ogg::reading::PacketReader;
+use futures::future::BoxFuture;
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
-use serde_json::{Value, json};
+use serde_json::Value;
+
+use super::TextToSpeech;
+use super::catalog::{self, Gender, Provider, VoiceInfo};
+use super::prosody::Prosody;
+use crate::audio::{self, AudioFormat};
 
 #[derive(Clone)]
 pub struct GoogleCloudConfig {
@@ -19,6 +25,8 @@ struct AudioConfig<'a> {
     audio_encoding: &'a str,
     sample_rate_hertz: u32,
     speaking_rate: f32,
+    /// Pitch change in semitones.
+    pitch: f32,
 }
 
 #[derive(Serialize, Debug)]
@@ -31,7 +39,7 @@ struct Input<'a> {
 #[serde(rename_all = "camelCase")]
 struct Voice<'a> {
     language_code: &'a str,
-    name: VoiceKind,
+    name: &'a VoiceKind,
 }
 
 #[derive(Serialize, Debug)]
@@ -48,115 +56,51 @@ struct TextToSpeechResponse {
     audio_content: String,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
-pub enum VoiceKind {
-    #[serde(rename = "en-AU-Standard-A")]
-    EnAuStandardA,
-    #[serde(rename = "en-AU-Standard-B")]
-    EnAuStandardB,
-    #[serde(rename = "en-AU-Standard-C")]
-    EnAuStandardC,
-    #[serde(rename = "en-AU-Standard-D")]
-    EnAuStandardD,
-    #[serde(rename = "en-AU-Wavenet-A")]
-    EnAuWavenetA,
-    #[serde(rename = "en-AU-Wavenet-B")]
-    EnAuWavenetB,
-    #[serde(rename = "en-AU-Wavenet-C")]
-    EnAuWavenetC,
-    #[serde(rename = "en-AU-Wavenet-D")]
-    EnAuWavenetD,
-    #[serde(rename = "en-IN-Standard-A")]
-    EnInStandardA,
-    #[serde(rename = "en-IN-Standard-B")]
-    EnInStandardB,
-    #[serde(rename = "en-IN-Standard-C")]
-    EnInStandardC,
-    #[serde(rename = "en-IN-Standard-D")]
-    EnInStandardD,
-    #[serde(rename = "en-IN-Wavenet-A")]
-    EnInWavenetA,
-    #[serde(rename = "en-IN-Wavenet-B")]
-    EnInWavenetB,
-    #[serde(rename = "en-IN-Wavenet-C")]
-    EnInWavenetC,
-    #[serde(rename = "en-IN-Wavenet-D")]
-    EnInWavenetD,
-    #[serde(rename = "en-GB-Standard-A")]
-    EnGbStandardA,
-    #[serde(rename = "en-GB-Standard-B")]
-    EnGbStandardB,
-    #[serde(rename = "en-GB-Standard-C")]
-    EnGbStandardC,
-    #[serde(rename = "en-GB-Standard-D")]
-    EnGbStandardD,
-    #[serde(rename = "en-GB-Standard-F")]
-    EnGbStandardF,
-    #[serde(rename = "en-GB-Wavenet-A")]
-    EnGbWavenetA,
-    #[serde(rename = "en-GB-Wavenet-B")]
-    EnGbWavenetB,
-    #[serde(rename = "en-GB-Wavenet-C")]
-    EnGbWavenetC,
-    #[serde(rename = "en-GB-Wavenet-D")]
-    EnGbWavenetD,
-    #[serde(rename = "en-GB-Wavenet-F")]
-    EnGbWavenetF,
-    #[serde(rename = "en-US-Standard-B")]
-    EnUsStandardB,
-    #[serde(rename = "en-US-Standard-C")]
-    EnUsStandardC,
-    #[serde(rename = "en-US-Standard-D")]
-    EnUsStandardD,
-    #[serde(rename = "en-US-Standard-E")]
-    EnUsStandardE,
-    #[serde(rename = "en-US-Standard-G")]
-    EnUsStandardG,
-    #[serde(rename = "en-US-Standard-H")]
-    EnUsStandardH,
-    #[serde(rename = "en-US-Standard-I")]
-    EnUsStandardI,
-    #[serde(rename = "en-US-Standard-J")]
-    EnUsStandardJ,
-    #[serde(rename = "en-US-Wavenet-A")]
-    EnUsWavenetA,
-    #[serde(rename = "en-US-Wavenet-B")]
-    EnUsWavenetB,
-    #[serde(rename = "en-US-Wavenet-C")]
-    EnUsWavenetC,
-    #[serde(rename = "en-US-Wavenet-D")]
-    EnUsWavenetD,
-    #[serde(rename = "en-US-Wavenet-E")]
-    EnUsWavenetE,
-    #[serde(rename = "en-US-Wavenet-F")]
-    EnUsWavenetF,
-    #[serde(rename = "en-US-Wavenet-G")]
-    EnUsWavenetG,
-    #[serde(rename = "en-US-Wavenet-H")]
-    EnUsWavenetH,
-    #[serde(rename = "en-US-Wavenet-I")]
-    EnUsWavenetI,
-    #[serde(rename = "en-US-Wavenet-J")]
-    EnUsWavenetJ,
+/// The name of a Google Cloud voice, e.g. `en-US-Standard-C`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize)]
+#[serde(transparent)]
+pub struct VoiceKind(String);
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ListVoicesResponse {
+    #[serde(default)]
+    voices: Vec<ListedVoice>,
 }
 
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ListedVoice {
+    name: String,
+    language_codes: Vec<String>,
+    ssml_gender: Option<String>,
+}
+
+/// The speaking rate used for Google Cloud voices, which are a bit too fast for radio reports by
+/// default. `RATE` is relative to it, so `RATE 100` keeps it.
+const DEFAULT_SPEAKING_RATE: f32 = 0.9;
+
 pub async fn text_to_speech(
     text: &str,
     config: &GoogleCloudConfig,
-) -> Result<Vec<Vec<u8>>, anyhow::Error> {
+    prosody: Prosody,
+) -> Result<Vec<i16>, anyhow::Error> {
     use base64::Engine;
     use base64::prelude::BASE64_STANDARD;
 
+    let language_code = config.voice.language();
+
     let payload = TextToSpeechRequest {
         audio_config: AudioConfig {
             audio_encoding: "OGG_OPUS",
             sample_rate_hertz: 16_000,
-            speaking_rate: 0.9,
+            speaking_rate: DEFAULT_SPEAKING_RATE * prosody.rate_factor().unwrap_or(1.0),
+            pitch: prosody.pitch.unwrap_or(0) as f32,
         },
         input: Input { ssml: text },
         voice: Voice {
-            language_code: "en-US",
-            name: config.voice,
+            language_code: &language_code,
+            name: &config.voice,
         },
     };
 
@@ -173,28 +117,75 @@ pub async fn text_to_speech(
 
     let data: TextToSpeechResponse = res.json().await?;
     let data = BASE64_STANDARD.decode(&data.audio_content)?;
-    let data = Cursor::new(data);
+    audio::prepare(data, AudioFormat::OggOpus).await
+}
 
-    let mut frames = Vec::new();
+impl TextToSpeech for GoogleCloudConfig {
+    fn text_to_speech<'a>(
+        &'a self,
+        ssml: &'a str,
+        prosody: Prosody,
+    ) -> BoxFuture<'a, Result<Vec<i16>, anyhow::Error>> {
+        Box::pin(text_to_speech(ssml, self, prosody))
+    }
+}
 
-    let mut audio = PacketReader::new(data);
-    while let Some(pck) = audio.read_packet()? {
-        frames.push(pck.data.to_vec())
+pub async fn list_voices(key: &str) -> Result<Vec<VoiceInfo>, anyhow::Error> {
+    let url = format!("https://texttospeech.googleapis.com/v1/voices?key={}", key);
+    let res = reqwest::get(&url).await?;
+    if res.status() != StatusCode::OK {
+        let err: Value = res.json().await?;
+        return Err(anyhow!("Gcloud TTS error: {}", err));
     }
 
-    Ok(frames)
+    let data: ListVoicesResponse = res.json().await?;
+    Ok(data
+        .voices
+        .into_iter()
+        .filter_map(|v| {
+            Some(VoiceInfo {
+                language: v.language_codes.into_iter().next()?,
+                gender: v.ssml_gender.as_deref().and_then(Gender::parse),
+                name: v.name,
+            })
+        })
+        .collect())
+}
+
+impl VoiceKind {
+    /// The language of the voice, e.g. `en-US` for `en-US-Standard-C`.
+    pub fn language(&self) -> String {
+        if let Ok(info) = catalog::find(Provider::GoogleCloud, &self.0) {
+            return info.language;
+        }
+
+        // the language code is the prefix of the voice name
+        self.0
+            .match_indices('-')
+            .nth(1)
+            .map(|(i, _)| self.0[..i].to_string())
+            .unwrap_or_else(|| String::from("en-US"))
+    }
 }
 
 impl FromStr for VoiceKind {
-    type Err = serde_json::Error;
+    type Err = Infallible;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        serde_json::from_value(json!(s))
+        Ok(VoiceKind(catalog::resolve(Provider::GoogleCloud, s)))
+    }
+}
+
+impl Deref for VoiceKind {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
 
 impl Display for VoiceKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(serde_json::to_string(self).unwrap().trim_matches('"'))
+        f.write_str(&self.0)
     }
 }
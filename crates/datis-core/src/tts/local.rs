@@ -0,0 +1,194 @@
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use futures::future::BoxFuture;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use super::TextToSpeech;
+use super::prosody::Prosody;
+use crate::audio::{self, AudioFormat};
+
+/// A voice of one of the supported offline TTS engines.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum VoiceKind {
+    /// A Piper voice, given as the path to its `.onnx` voice model.
+    Piper { model: String },
+    /// An eSpeak NG voice (e.g. `en-us`), or eSpeak NG's default voice if `None`.
+    Espeak { voice: Option<String> },
+}
+
+#[derive(Clone)]
+pub struct LocalConfig {
+    pub voice: VoiceKind,
+    /// Path to the `piper` executable (defaults to `piper` from `PATH`).
+    pub piper: Option<PathBuf>,
+    /// Path to the `espeak-ng` executable (defaults to `espeak-ng` from `PATH`).
+    pub espeak: Option<PathBuf>,
+}
+
+/// eSpeak NG's default speaking rate in words per minute.
+const ESPEAK_DEFAULT_SPEED: u32 = 175;
+/// eSpeak NG's default pitch (on a scale from 0 to 99).
+const ESPEAK_DEFAULT_PITCH: i32 = 50;
+
+/// Piper falls back to this sample rate if it cannot be read from the voice model config.
+const PIPER_DEFAULT_SAMPLE_RATE: u32 = 22050;
+
+pub async fn text_to_speech(
+    ssml: &str,
+    config: &LocalConfig,
+    prosody: Prosody,
+) -> Result<Vec<i16>, anyhow::Error> {
+    match &config.voice {
+        VoiceKind::Piper { model } => {
+            // Piper does not understand SSML, so it is fed the plain text instead
+            let text = strip_ssml(ssml);
+            let mut cmd = Command::new(config.piper.as_deref().unwrap_or(Path::new("piper")));
+            cmd.arg("--model").arg(model).arg("--output_raw");
+            // Piper only supports the rate (as the inverse length scale of the phonemes)
+            if let Some(rate) = prosody.rate_factor() {
+                cmd.arg("--length_scale").arg((1.0 / rate).to_string());
+            }
+            let pcm = run(cmd, &text).await?;
+            let format = AudioFormat::Pcm {
+                sample_rate: piper_sample_rate(model).await,
+                channels: 1,
+            };
+            audio::prepare(pcm, format).await
+        }
+        VoiceKind::Espeak { voice } => {
+            let mut cmd = Command::new(config.espeak.as_deref().unwrap_or(Path::new("espeak-ng")));
+            // `-m` enables SSML markup
+            cmd.arg("-m").arg("--stdout");
+            if let Some(voice) = voice {
+                cmd.arg("-v").arg(voice);
+            }
+            if let Some(rate) = prosody.rate {
+                cmd.arg("-s")
+                    .arg((ESPEAK_DEFAULT_SPEED * rate / 100).to_string());
+            }
+            if let Some(pitch) = prosody.pitch {
+                // eSpeak's pitch ranges from 0 to 99, roughly 4 steps per semitone
+                cmd.arg("-p")
+                    .arg((ESPEAK_DEFAULT_PITCH + pitch * 4).clamp(0, 99).to_string());
+            }
+            let wav = run(cmd, ssml).await?;
+            audio::prepare(wav, AudioFormat::Wav).await
+        }
+    }
+}
+
+impl TextToSpeech for LocalConfig {
+    fn text_to_speech<'a>(
+        &'a self,
+        ssml: &'a str,
+        prosody: Prosody,
+    ) -> BoxFuture<'a, Result<Vec<i16>, anyhow::Error>> {
+        Box::pin(text_to_speech(ssml, self, prosody))
+    }
+}
+
+/// Run the TTS engine `cmd`, pass `input` via stdin and return everything it writes to stdout.
+async fn run(mut cmd: Command, input: &str) -> Result<Vec<u8>, anyhow::Error> {
+    let program = cmd.as_std().get_program().to_string_lossy().into_owned();
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|err| anyhow!("failed to start {}: {}", program, err))?;
+
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(input.as_bytes()).await?;
+    drop(stdin);
+
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "{} failed ({}): {}",
+            program,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(output.stdout)
+}
+
+/// Read the sample rate from the config (`{model}.json`) that comes with each Piper voice model.
+async fn piper_sample_rate(model: &str) -> u32 {
+    let config = match tokio::fs::read(format!("{}.json", model)).await {
+        Ok(config) => config,
+        Err(err) => {
+            log::warn!(
+                "Failed to read Piper voice model config for {}: {}",
+                model,
+                err
+            );
+            return PIPER_DEFAULT_SAMPLE_RATE;
+        }
+    };
+
+    serde_json::from_slice::<serde_json::Value>(&config)
+        .ok()
+        .and_then(|config| config["audio"]["sample_rate"].as_u64())
+        .map(|rate| rate as u32)
+        .unwrap_or(PIPER_DEFAULT_SAMPLE_RATE)
+}
+
+/// Remove all SSML tags and unescape the XML entities of the remaining text.
+fn strip_ssml(ssml: &str) -> String {
+    let mut text = String::with_capacity(ssml.len());
+    let mut in_tag = false;
+    for c in ssml.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+impl Display for VoiceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VoiceKind::Piper { model } => write!(f, "PIPER:{}", model),
+            VoiceKind::Espeak { voice: Some(voice) } => write!(f, "LOCAL:{}", voice),
+            VoiceKind::Espeak { voice: None } => f.write_str("LOCAL"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_strip_ssml() {
+        assert_eq!(
+            strip_ssml(
+                "<speak version=\"1.0\" xml:lang=\"en\">\nThis is Kutaisi.<break time=\"500ms\"/>\
+                 Runway 07 &amp; 25.\n</speak>"
+            ),
+            "This is Kutaisi. Runway 07 & 25."
+        );
+    }
+}
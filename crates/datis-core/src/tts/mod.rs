@@ -1,25 +1,177 @@
 pub mod aws;
 pub mod azure;
+pub mod cache;
+pub mod catalog;
+pub mod cooldown;
 pub mod gcloud;
+pub mod local;
+pub mod prosody;
+pub mod registry;
 pub mod win;
 
 use std::fmt;
 use std::str::FromStr;
+use std::sync::Arc;
 
-#[derive(PartialEq, Eq, Clone)]
+use futures::future::BoxFuture;
+
+use self::prosody::Prosody;
+use crate::config::{AwsConfig, AzureConfig, Config};
+
+#[derive(PartialEq, Eq, Hash, Clone)]
 pub enum TextToSpeechProvider {
-    GoogleCloud { voice: gcloud::VoiceKind },
-    AmazonWebServices { voice: aws::VoiceKind },
-    Windows { voice: Option<win::VoiceKind> },
-    AzureCognitiveServices { voice: azure::VoiceKind },
+    GoogleCloud {
+        voice: gcloud::VoiceKind,
+    },
+    AmazonWebServices {
+        voice: aws::VoiceKind,
+    },
+    Windows {
+        voice: Option<win::VoiceKind>,
+    },
+    AzureCognitiveServices {
+        voice: azure::VoiceKind,
+    },
+    Local {
+        voice: local::VoiceKind,
+    },
+    /// A voice of a provider registered via `registry::register`.
+    Custom {
+        prefix: String,
+        voice: String,
+    },
 }
 
-#[derive(Clone)]
-pub enum TextToSpeechConfig {
-    GoogleCloud(gcloud::GoogleCloudConfig),
-    AmazonWebServices(aws::AmazonWebServicesConfig),
-    Windows(win::WindowsConfig),
-    AzureCognitiveServices(azure::AzureCognitiveServicesConfig),
+/// A TTS engine, set up for a specific voice.
+pub trait TextToSpeech: Send + Sync {
+    /// Synthesizes the given SSML into 16kHz mono samples (see `audio::prepare`). The rate and
+    /// pitch of the `prosody` are applied by the engine if supported, its volume is applied to
+    /// the returned samples afterwards.
+    fn text_to_speech<'a>(
+        &'a self,
+        ssml: &'a str,
+        prosody: Prosody,
+    ) -> BoxFuture<'a, Result<Vec<i16>, anyhow::Error>>;
+}
+
+impl TextToSpeechProvider {
+    /// Logs a warning if the voice is not in the voice catalog of its provider.
+    pub fn warn_if_unknown(&self) {
+        let Some((provider, name)) = self.catalog_voice() else {
+            return;
+        };
+        if let Err(err) = catalog::find(provider, name) {
+            log::warn!("{} (of {:?})", err, provider);
+        }
+    }
+
+    /// The language of the voice (e.g. `en-US`), if known. Taken from the voice catalog, or from
+    /// the voice name for providers that prefix their voices with the language (e.g.
+    /// `de-DE-Wavenet-B`).
+    pub fn language(&self) -> Option<String> {
+        let (provider, name) = self.catalog_voice()?;
+        if let Ok(voice) = catalog::find(provider, name) {
+            return Some(voice.language);
+        }
+
+        match provider {
+            catalog::Provider::GoogleCloud | catalog::Provider::AzureCognitiveServices => {
+                let mut parts = name.splitn(3, '-');
+                let (language, region) = (parts.next()?, parts.next()?);
+                parts.next()?;
+                Some(format!("{}-{}", language, region))
+            }
+            _ => None,
+        }
+    }
+
+    /// The provider and name of the voice in the voice catalog.
+    fn catalog_voice(&self) -> Option<(catalog::Provider, &str)> {
+        Some(match self {
+            TextToSpeechProvider::GoogleCloud { voice } => {
+                (catalog::Provider::GoogleCloud, &**voice)
+            }
+            TextToSpeechProvider::AmazonWebServices { voice } => {
+                (catalog::Provider::AmazonWebServices, &**voice)
+            }
+            TextToSpeechProvider::AzureCognitiveServices { voice } => {
+                (catalog::Provider::AzureCognitiveServices, &**voice)
+            }
+            TextToSpeechProvider::Windows { voice: Some(voice) } => {
+                (catalog::Provider::Windows, &**voice)
+            }
+            _ => return None,
+        })
+    }
+
+    /// The prefix of the provider of the voice (e.g. `GC`), as used in voice names.
+    pub fn prefix(&self) -> &str {
+        match self {
+            TextToSpeechProvider::GoogleCloud { .. } => "GC",
+            TextToSpeechProvider::AmazonWebServices { .. } => "AWS",
+            TextToSpeechProvider::AzureCognitiveServices { .. } => "AZURE",
+            TextToSpeechProvider::Windows { .. } => "WIN",
+            TextToSpeechProvider::Local {
+                voice: local::VoiceKind::Piper { .. },
+            } => "PIPER",
+            TextToSpeechProvider::Local {
+                voice: local::VoiceKind::Espeak { .. },
+            } => "LOCAL",
+            TextToSpeechProvider::Custom { prefix, .. } => prefix,
+        }
+    }
+
+    /// Creates the TTS engine for the voice, using the credentials of the given `config`.
+    pub fn engine(&self, config: &Config) -> Result<Arc<dyn TextToSpeech>, String> {
+        Ok(match self.clone() {
+            TextToSpeechProvider::GoogleCloud { voice } => match config.gcloud {
+                Some(ref gcloud) => Arc::new(gcloud::GoogleCloudConfig {
+                    key: gcloud.key.clone(),
+                    voice,
+                }),
+                None => {
+                    return Err(format!(
+                        "Missing Google Cloud key for TTS provider {:?}",
+                        self
+                    ));
+                }
+            },
+            TextToSpeechProvider::AmazonWebServices { voice } => match config.aws {
+                Some(AwsConfig {
+                    ref key,
+                    ref secret,
+                    ref region,
+                }) => Arc::new(aws::AmazonWebServicesConfig {
+                    key: key.clone(),
+                    secret: secret.clone(),
+                    region: rusoto_core::Region::from_str(region)
+                        .map_err(|err| format!("Invalid AWS region {}: {}", region, err))?,
+                    voice,
+                }),
+                None => return Err("Missing AWS key, secret or region".to_string()),
+            },
+            TextToSpeechProvider::Windows { voice } => Arc::new(win::WindowsConfig { voice }),
+            TextToSpeechProvider::AzureCognitiveServices { voice } => match config.azure {
+                Some(AzureConfig {
+                    ref key,
+                    ref region,
+                }) => Arc::new(azure::AzureCognitiveServicesConfig {
+                    key: key.clone(),
+                    region: region.clone(),
+                    voice,
+                }),
+                None => return Err("Missing Azure key".to_string()),
+            },
+            TextToSpeechProvider::Local { voice } => Arc::new(local::LocalConfig {
+                voice,
+                piper: config.local.piper.clone(),
+                espeak: config.local.espeak.clone(),
+            }),
+            TextToSpeechProvider::Custom { prefix, voice } => {
+                registry::create(&prefix, &voice).map_err(|err| err.to_string())?
+            }
+        })
+    }
 }
 
 impl Default for TextToSpeechProvider {
@@ -45,6 +197,19 @@ impl fmt::Debug for TextToSpeechProvider {
                 "Windows built-in TTS (Voice: {:?})",
                 voice.as_ref().map(|v| &**v).unwrap_or_else(|| "Default")
             ),
+            TextToSpeechProvider::Local { voice } => match voice {
+                local::VoiceKind::Piper { model } => {
+                    write!(f, "Piper local TTS (Model: {})", model)
+                }
+                local::VoiceKind::Espeak { voice } => write!(
+                    f,
+                    "eSpeak NG local TTS (Voice: {})",
+                    voice.as_deref().unwrap_or("Default")
+                ),
+            },
+            TextToSpeechProvider::Custom { prefix, voice } => {
+                write!(f, "{} (Voice: {})", prefix, voice)
+            }
         }
     }
 }
@@ -59,38 +224,61 @@ impl FromStr for TextToSpeechProvider {
                 "GC" | "gc" => {
                     return Ok(TextToSpeechProvider::GoogleCloud {
                         voice: gcloud::VoiceKind::from_str(voice)
-                            .map_err(TextToSpeechProviderError::Voice)?,
+                            .unwrap_or_else(|never| match never {}),
                     });
                 }
                 "AWS" | "aws" => {
                     return Ok(TextToSpeechProvider::AmazonWebServices {
                         voice: aws::VoiceKind::from_str(voice)
-                            .map_err(TextToSpeechProviderError::Voice)?,
+                            .unwrap_or_else(|never| match never {}),
                     });
                 }
                 "AZURE" | "azure" => {
                     return Ok(TextToSpeechProvider::AzureCognitiveServices {
                         voice: azure::VoiceKind::from_str(voice)
-                            .map_err(TextToSpeechProviderError::Voice)?,
+                            .unwrap_or_else(|never| match never {}),
                     });
                 }
                 "WIN" | "win" => {
                     return Ok(TextToSpeechProvider::Windows {
                         voice: Some(
-                            win::VoiceKind::from_str(voice)
-                                .map_err(TextToSpeechProviderError::Voice)?,
+                            win::VoiceKind::from_str(voice).unwrap_or_else(|never| match never {}),
                         ),
                     });
                 }
+                "PIPER" | "piper" if !voice.is_empty() => {
+                    return Ok(TextToSpeechProvider::Local {
+                        voice: local::VoiceKind::Piper {
+                            model: voice.to_string(),
+                        },
+                    });
+                }
+                "LOCAL" | "local" if !voice.is_empty() => {
+                    return Ok(TextToSpeechProvider::Local {
+                        voice: local::VoiceKind::Espeak {
+                            voice: Some(voice.to_string()),
+                        },
+                    });
+                }
+                prefix if registry::is_registered(prefix) => {
+                    return Ok(TextToSpeechProvider::Custom {
+                        prefix: prefix.to_uppercase(),
+                        voice: voice.to_string(),
+                    });
+                }
                 _ => {}
             },
             [voice] if !voice.is_empty() => {
                 if voice == "WIN" || voice == "win" {
                     return Ok(TextToSpeechProvider::Windows { voice: None });
+                } else if voice == "LOCAL" || voice == "local" {
+                    return Ok(TextToSpeechProvider::Local {
+                        voice: local::VoiceKind::Espeak { voice: None },
+                    });
                 } else {
                     return Ok(TextToSpeechProvider::GoogleCloud {
                         voice: gcloud::VoiceKind::from_str(voice)
-                            .map_err(TextToSpeechProviderError::Voice)?,
+                            .unwrap_or_else(|never| match never {}),
                     });
                 }
             }
@@ -105,8 +293,6 @@ impl FromStr for TextToSpeechProvider {
 pub enum TextToSpeechProviderError {
     #[error("Invalid default voice `{0}`")]
     Provider(String),
-    #[error("Invalid default voice: `{0}`")]
-    Voice(serde_json::Error),
 }
 
 impl<'de> serde::Deserialize<'de> for TextToSpeechProvider {
@@ -141,6 +327,8 @@ impl serde::Serialize for TextToSpeechProvider {
                     "WIN".to_string()
                 }
             }
+            TextToSpeechProvider::Local { voice } => voice.to_string(),
+            TextToSpeechProvider::Custom { prefix, voice } => format!("{}:{}", prefix, voice),
         })
     }
 }
@@ -150,7 +338,7 @@ mod test {
     mod tts_provider_from_str {
         use std::str::FromStr;
 
-        use crate::tts::{TextToSpeechProvider, aws, azure, gcloud};
+        use crate::tts::{TextToSpeechProvider, aws, azure, gcloud, local};
 
         #[test]
         fn err_when_invalid() {
@@ -167,7 +355,7 @@ mod test {
             assert_eq!(
                 TextToSpeechProvider::from_str("en-US-Wavenet-A").unwrap(),
                 TextToSpeechProvider::GoogleCloud {
-                    voice: gcloud::VoiceKind::EnUsWavenetA
+                    voice: gcloud::VoiceKind::from_str("en-US-Wavenet-A").unwrap()
                 }
             )
         }
@@ -177,7 +365,7 @@ mod test {
             assert_eq!(
                 TextToSpeechProvider::from_str("GC:en-US-Wavenet-B").unwrap(),
                 TextToSpeechProvider::GoogleCloud {
-                    voice: gcloud::VoiceKind::EnUsWavenetB
+                    voice: gcloud::VoiceKind::from_str("en-US-Wavenet-B").unwrap()
                 }
             )
         }
@@ -187,7 +375,7 @@ mod test {
             assert_eq!(
                 TextToSpeechProvider::from_str("GC:en-GB-Standard-A").unwrap(),
                 TextToSpeechProvider::GoogleCloud {
-                    voice: gcloud::VoiceKind::EnGbStandardA
+                    voice: gcloud::VoiceKind::from_str("en-GB-Standard-A").unwrap()
                 }
             )
         }
@@ -197,7 +385,7 @@ mod test {
             assert_eq!(
                 TextToSpeechProvider::from_str("AWS:Brian").unwrap(),
                 TextToSpeechProvider::AmazonWebServices {
-                    voice: aws::VoiceKind::Brian
+                    voice: aws::VoiceKind::from_str("Brian").unwrap()
                 }
             )
         }
@@ -207,9 +395,74 @@ mod test {
             assert_eq!(
                 TextToSpeechProvider::from_str("AZURE:en-US-AriaRUS").unwrap(),
                 TextToSpeechProvider::AzureCognitiveServices {
-                    voice: azure::VoiceKind::Aria
+                    voice: azure::VoiceKind::from_str("en-US-AriaNeural").unwrap()
+                }
+            )
+        }
+
+        #[test]
+        fn voice_is_case_insensitive() {
+            assert_eq!(
+                TextToSpeechProvider::from_str("AWS:brian").unwrap(),
+                TextToSpeechProvider::AmazonWebServices {
+                    voice: aws::VoiceKind::from_str("Brian").unwrap()
+                }
+            )
+        }
+
+        #[test]
+        fn unknown_voice_is_passed_through() {
+            assert_eq!(
+                TextToSpeechProvider::from_str("GC:en-US-Chirp3-HD-Puck").unwrap(),
+                TextToSpeechProvider::GoogleCloud {
+                    voice: gcloud::VoiceKind::from_str("en-US-Chirp3-HD-Puck").unwrap()
+                }
+            )
+        }
+
+        #[test]
+        fn prefix_piper() {
+            assert_eq!(
+                TextToSpeechProvider::from_str("PIPER:C:\\voices\\en_US-ryan-medium.onnx").unwrap(),
+                TextToSpeechProvider::Local {
+                    voice: local::VoiceKind::Piper {
+                        model: "C:\\voices\\en_US-ryan-medium.onnx".to_string()
+                    }
                 }
             )
         }
+
+        #[test]
+        fn language() {
+            assert_eq!(
+                TextToSpeechProvider::from_str("GC:de-DE-Wavenet-B")
+                    .unwrap()
+                    .language()
+                    .as_deref(),
+                Some("de-DE")
+            );
+            assert_eq!(
+                TextToSpeechProvider::from_str("LOCAL").unwrap().language(),
+                None
+            );
+        }
+
+        #[test]
+        fn prefix_local() {
+            assert_eq!(
+                TextToSpeechProvider::from_str("LOCAL:en-us").unwrap(),
+                TextToSpeechProvider::Local {
+                    voice: local::VoiceKind::Espeak {
+                        voice: Some("en-us".to_string())
+                    }
+                }
+            );
+            assert_eq!(
+                TextToSpeechProvider::from_str("LOCAL").unwrap(),
+                TextToSpeechProvider::Local {
+                    voice: local::VoiceKind::Espeak { voice: None }
+                }
+            );
+        }
     }
 }
@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+
+/// Adjustments of the speaking rate, pitch and volume of a voice. Unset values keep the default
+/// of the respective TTS provider.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub struct Prosody {
+    /// Speaking rate in percent of the normal rate, e.g. `110` for a 10% brisker pace.
+    pub rate: Option<u32>,
+    /// Pitch change in semitones.
+    pub pitch: Option<i32>,
+    /// Volume change in dB, applied to the normalized audio.
+    pub volume: Option<i32>,
+}
+
+impl Prosody {
+    /// Uses the values of `fallback` for all values not set.
+    pub fn or(self, fallback: Prosody) -> Prosody {
+        Prosody {
+            rate: self.rate.or(fallback.rate),
+            pitch: self.pitch.or(fallback.pitch),
+            volume: self.volume.or(fallback.volume),
+        }
+    }
+
+    /// The speaking rate as a factor of the normal rate.
+    pub fn rate_factor(&self) -> Option<f32> {
+        self.rate.map(|rate| rate as f32 / 100.0)
+    }
+
+    /// The pitch change relative to the normal pitch in percent.
+    pub fn pitch_percent(&self) -> Option<i32> {
+        self.pitch
+            .map(|st| ((2f64.powf(st as f64 / 12.0) - 1.0) * 100.0).round() as i32)
+    }
+}
+
+/// Wraps the content of the `<speak>` element of `ssml` in a `<prosody>` element with the given
+/// `attrs`. Returns `ssml` unchanged if there are no attributes.
+pub fn wrap_prosody(ssml: &str, attrs: &[(&str, String)]) -> String {
+    if attrs.is_empty() {
+        return ssml.to_string();
+    }
+
+    let attrs = attrs
+        .iter()
+        .map(|(name, value)| format!(r#" {}="{}""#, name, value))
+        .collect::<String>();
+    let (start, rest) = match ssml
+        .strip_prefix("<speak")
+        .and_then(|rest| rest.split_once('>'))
+    {
+        Some((attrs, rest)) => (format!("<speak{}>", attrs), rest),
+        None => return format!("<prosody{}>{}</prosody>", attrs, ssml),
+    };
+    let body = rest.strip_suffix("</speak>").unwrap_or(rest);
+    format!("{}<prosody{}>{}</prosody></speak>", start, attrs, body)
+}
+
+/// The `<prosody>` attributes as defined by SSML 1.0, i.e. the rate as a factor of the normal rate
+/// and the pitch in semitones.
+pub fn prosody_attrs(prosody: Prosody) -> Vec<(&'static str, String)> {
+    let mut attrs = Vec::new();
+    if let Some(rate) = prosody.rate_factor() {
+        attrs.push(("rate", rate.to_string()));
+    }
+    if let Some(pitch) = prosody.pitch {
+        attrs.push(("pitch", format!("{}st", signed(pitch))));
+    }
+    attrs
+}
+
+/// Formats `value` with an explicit sign, e.g. `+2` or `-2`.
+pub fn signed(value: i32) -> String {
+    format!("{:+}", value)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_or() {
+        let station = Prosody {
+            rate: Some(110),
+            pitch: None,
+            volume: None,
+        };
+        let global = Prosody {
+            rate: Some(90),
+            pitch: Some(-2),
+            volume: None,
+        };
+        assert_eq!(
+            station.or(global),
+            Prosody {
+                rate: Some(110),
+                pitch: Some(-2),
+                volume: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_pitch_percent() {
+        let prosody = |pitch| Prosody {
+            pitch: Some(pitch),
+            ..Default::default()
+        };
+        assert_eq!(prosody(0).pitch_percent(), Some(0));
+        assert_eq!(prosody(12).pitch_percent(), Some(100));
+        assert_eq!(prosody(-2).pitch_percent(), Some(-11));
+    }
+
+    #[test]
+    fn test_wrap_prosody() {
+        assert_eq!(
+            wrap_prosody(
+                r#"<speak version="1.0" xml:lang="en">Hello</speak>"#,
+                &[("rate", "110%".to_string()), ("pitch", signed(-2) + "st")]
+            ),
+            r#"<speak version="1.0" xml:lang="en"><prosody rate="110%" pitch="-2st">Hello</prosody></speak>"#
+        );
+        assert_eq!(
+            wrap_prosody("<speak>Hello</speak>", &[]),
+            "<speak>Hello</speak>"
+        );
+    }
+}
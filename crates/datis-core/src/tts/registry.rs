@@ -0,0 +1,136 @@
+//! Registry of custom TTS providers.
+//!
+//! Embedders of `datis-core` can register their own TTS engines under a prefix. Voices of the
+//! form `{PREFIX}:{VOICE}` are then synthesized by the engine the registered factory creates for
+//! the voice.
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
+
+use super::TextToSpeech;
+
+/// Creates the TTS engine for a voice of a custom provider.
+pub trait TextToSpeechFactory: Send + Sync {
+    fn create(&self, voice: &str) -> Result<Arc<dyn TextToSpeech>, anyhow::Error>;
+}
+
+impl<F> TextToSpeechFactory for F
+where
+    F: Fn(&str) -> Result<Arc<dyn TextToSpeech>, anyhow::Error> + Send + Sync,
+{
+    fn create(&self, voice: &str) -> Result<Arc<dyn TextToSpeech>, anyhow::Error> {
+        self(voice)
+    }
+}
+
+type Factories = HashMap<String, Arc<dyn TextToSpeechFactory>>;
+
+static REGISTRY: LazyLock<RwLock<Factories>> = LazyLock::new(Default::default);
+
+/// The prefixes of the built-in providers, which cannot be registered.
+const BUILT_IN: [&str; 6] = ["GC", "AWS", "AZURE", "WIN", "PIPER", "LOCAL"];
+
+#[derive(Debug, thiserror::Error)]
+#[error("TTS provider prefix `{0}` is already in use")]
+pub struct PrefixInUse(String);
+
+/// Registers a custom TTS provider for voices of the form `{prefix}:{voice}` (the prefix is
+/// case-insensitive). Must be called before the voices are parsed, e.g. before extracting the
+/// stations of a mission.
+pub fn register(
+    prefix: &str,
+    factory: impl TextToSpeechFactory + 'static,
+) -> Result<(), PrefixInUse> {
+    let prefix = prefix.to_uppercase();
+    if BUILT_IN.contains(&prefix.as_str()) {
+        return Err(PrefixInUse(prefix));
+    }
+
+    let mut registry = REGISTRY.write().unwrap();
+    if registry.contains_key(&prefix) {
+        return Err(PrefixInUse(prefix));
+    }
+    registry.insert(prefix, Arc::new(factory));
+    Ok(())
+}
+
+/// Removes the custom TTS provider registered for `prefix`.
+pub fn unregister(prefix: &str) {
+    REGISTRY.write().unwrap().remove(&prefix.to_uppercase());
+}
+
+pub fn is_registered(prefix: &str) -> bool {
+    REGISTRY
+        .read()
+        .unwrap()
+        .contains_key(&prefix.to_uppercase())
+}
+
+/// Creates the engine of the custom provider registered for `prefix` for the given `voice`.
+pub fn create(prefix: &str, voice: &str) -> Result<Arc<dyn TextToSpeech>, anyhow::Error> {
+    let factory = REGISTRY
+        .read()
+        .unwrap()
+        .get(&prefix.to_uppercase())
+        .cloned()
+        .ok_or_else(|| anyhow!("No TTS provider registered for `{}`", prefix))?;
+    factory.create(voice)
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use futures::future::BoxFuture;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::config::Config;
+    use crate::tts::TextToSpeechProvider;
+    use crate::tts::prosody::Prosody;
+
+    struct Silence(usize);
+
+    impl TextToSpeech for Silence {
+        fn text_to_speech<'a>(
+            &'a self,
+            _ssml: &'a str,
+            _prosody: Prosody,
+        ) -> BoxFuture<'a, Result<Vec<i16>, anyhow::Error>> {
+            Box::pin(async move { Ok(vec![0; self.0]) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_provider() {
+        assert!(TextToSpeechProvider::from_str("SILENCE:10").is_err());
+
+        let factory = |voice: &str| -> Result<Arc<dyn TextToSpeech>, anyhow::Error> {
+            Ok(Arc::new(Silence(voice.parse()?)))
+        };
+        register("silence", factory).unwrap();
+        assert!(register("SILENCE", factory).is_err());
+        assert!(register("gc", factory).is_err());
+
+        let tts = TextToSpeechProvider::from_str("silence:10").unwrap();
+        assert_eq!(
+            tts,
+            TextToSpeechProvider::Custom {
+                prefix: "SILENCE".to_string(),
+                voice: "10".to_string()
+            }
+        );
+        let engine = tts.engine(&Config::default()).unwrap();
+        assert_eq!(
+            engine
+                .text_to_speech("<speak />", Prosody::default())
+                .await
+                .unwrap(),
+            [0; 10]
+        );
+
+        unregister("silence");
+        assert!(TextToSpeechProvider::from_str("SILENCE:10").is_err());
+    }
+}
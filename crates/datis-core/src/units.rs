@@ -0,0 +1,60 @@
+//! Unit conventions reports can be given in.
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum UnitProfile {
+    /// Winds in knots, visibility in nautical miles, altimeter in inHg with the QNH in hPa and
+    /// the QFE in inHg and hPa in the remarks.
+    #[default]
+    Mixed,
+    /// Winds in knots, visibility in statute miles, altimeter and QFE in inHg.
+    Faa,
+    /// Winds in knots, visibility in kilometers (meters below 5km), QNH and QFE in hPa.
+    Icao,
+    /// Winds in km/h, visibility in kilometers (meters below 5km), QFE and QNH in mmHg, altitudes
+    /// in meters.
+    Soviet,
+}
+
+impl UnitProfile {
+    /// Whether altitudes (ceiling, winds aloft and density altitude) are given in meters instead
+    /// of feet.
+    pub fn metric_altitudes(self) -> bool {
+        self == UnitProfile::Soviet
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UnitProfile::Mixed => "mixed",
+            UnitProfile::Faa => "faa",
+            UnitProfile::Icao => "icao",
+            UnitProfile::Soviet => "soviet",
+        }
+    }
+}
+
+impl FromStr for UnitProfile {
+    type Err = UnitProfileError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "MIXED" => Ok(UnitProfile::Mixed),
+            "FAA" => Ok(UnitProfile::Faa),
+            "ICAO" => Ok(UnitProfile::Icao),
+            "SOVIET" | "RU" => Ok(UnitProfile::Soviet),
+            _ => Err(UnitProfileError(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for UnitProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Unsupported unit profile `{0}`")]
+pub struct UnitProfileError(String);
@@ -10,6 +10,13 @@ pub fn round_hundreds(n: i32) -> i32 {
     (n / 100) * 100
 }
 
+/// Escape `s` for the use as text content of an XML (SSML) document.
+pub fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
 static PHONETIC_NUMBERS: &[&str] = &["ZERO", "1", "2", "3", "4", "5", "6", "7", "8", "NINER"];
 
 pub fn pronounce_number<S>(n: S, pronounce: bool) -> String
@@ -0,0 +1,255 @@
+use std::fmt::Write;
+
+use time::PrimitiveDateTime;
+use uom::si::i32::Length;
+use uom::si::length::{foot, meter};
+use uom::si::pressure::{hectopascal, inch_of_mercury};
+use uom::si::thermodynamic_temperature::degree_celsius;
+use uom::si::velocity::knot;
+
+use super::{CloudCoverage, WeatherCondition, WeatherInfo, round_wind_dir};
+
+/// Render an ICAO METAR for the given station `ident` (usually the ICAO code of the airfield),
+/// observation `time` (UTC) and station altitude `alt` (MSL).
+///
+/// Example: `METAR UGKO 011200Z 33005KT 9999 SCT030 22/15 Q1015 RMK A2997 QFE1015`
+pub fn generate_metar(
+    ident: &str,
+    time: PrimitiveDateTime,
+    weather: &WeatherInfo,
+    alt: Length,
+) -> String {
+    let mut metar = format!(
+        "METAR {} {:02}{:02}{:02}Z",
+        ident.to_uppercase(),
+        time.day(),
+        time.hour(),
+        time.minute()
+    );
+
+    write!(metar, " {}", wind_group(weather)).unwrap();
+    write!(metar, " {}", visibility_group(weather.get_visibility(alt))).unwrap();
+
+    if let Some(group) = present_weather_group(&weather.get_weather_conditions(alt)) {
+        write!(metar, " {}", group).unwrap();
+    }
+
+    let clouds = cloud_groups(weather, alt);
+    if clouds.is_empty() {
+        metar += " NSC";
+    } else {
+        for group in clouds {
+            write!(metar, " {}", group).unwrap();
+        }
+    }
+
+    write!(
+        metar,
+        " {}/{}",
+        temperature_group(weather.temperature.get::<degree_celsius>()),
+        temperature_group(weather.get_dew_point(alt).get::<degree_celsius>())
+    )
+    .unwrap();
+
+    write!(
+        metar,
+        " Q{:04} RMK A{:04} QFE{:04}",
+        weather.get_qnh(alt).get::<hectopascal>().round(),
+        (weather.get_qnh(alt).get::<inch_of_mercury>() * 100.0).round(),
+        weather.get_qfe().get::<hectopascal>().round(),
+    )
+    .unwrap();
+
+    metar
+}
+
+fn wind_group(weather: &WeatherInfo) -> String {
+    let speed = weather.wind_speed.get::<knot>().round();
+    if speed < 1.0 {
+        return "00000KT".to_string();
+    }
+
+    // light winds are reported as variable
+    if speed < 3.0 {
+        return format!("VRB{:02}KT", speed);
+    }
+
+    let mut group = format!("{:03}{:02}", round_wind_dir(weather.wind_dir), speed);
+    if let Some(gust) = weather.get_wind_gust() {
+        write!(group, "G{:02}", gust.get::<knot>().round()).unwrap();
+    }
+    group += "KT";
+
+    if let Some((from, to)) = weather.get_wind_variability() {
+        write!(
+            group,
+            " {:03}V{:03}",
+            round_wind_dir(from),
+            round_wind_dir(to)
+        )
+        .unwrap();
+    }
+
+    group
+}
+
+fn visibility_group(visibility: Option<Length>) -> String {
+    let visibility = match visibility {
+        Some(visibility) => visibility.get::<meter>(),
+        None => return "9999".to_string(),
+    };
+
+    let visibility = if visibility < 800 {
+        (visibility / 50) * 50
+    } else if visibility < 5_000 {
+        (visibility / 100) * 100
+    } else {
+        (visibility / 1_000) * 1_000
+    };
+
+    format!("{:04}", visibility.min(9_999))
+}
+
+fn present_weather_group(conditions: &[WeatherCondition]) -> Option<String> {
+    let thunderstorm = conditions
+        .iter()
+        .any(|c| matches!(c, WeatherCondition::Thunderstorm));
+    let rain = conditions.iter().find(|c| {
+        matches!(
+            c,
+            WeatherCondition::SlightRain | WeatherCondition::Rain | WeatherCondition::HeavyRain
+        )
+    });
+
+    let mut groups = Vec::new();
+    match (thunderstorm, rain) {
+        // a thunderstorm with rain is combined into a single group, e.g. `+TSRA`
+        (true, Some(rain)) => groups.push(rain.to_metar().replace("RA", "TSRA")),
+        (true, None) => groups.push(WeatherCondition::Thunderstorm.to_metar().to_string()),
+        (false, Some(rain)) => groups.push(rain.to_metar().to_string()),
+        (false, None) => {}
+    }
+
+    for condition in conditions {
+        if matches!(
+            condition,
+            WeatherCondition::Fog | WeatherCondition::DustStorm
+        ) {
+            groups.push(condition.to_metar().to_string());
+        }
+    }
+
+    if groups.is_empty() {
+        None
+    } else {
+        Some(groups.join(" "))
+    }
+}
+
+fn cloud_groups(weather: &WeatherInfo, alt: Length) -> Vec<String> {
+    weather
+        .get_cloud_layers()
+        .into_iter()
+        .filter(|layer| !matches!(layer.coverage, CloudCoverage::Clear))
+        // layers below the station are not reported
+        .filter(|layer| layer.altitude_max > alt)
+        .map(|layer| {
+            // cloud bases are reported in hundreds of feet above the station
+            let base = (layer.altitude_min - alt).get::<foot>().max(0) / 100;
+            format!("{}{:03}", layer.coverage.to_metar(), base.min(999))
+        })
+        .collect()
+}
+
+fn temperature_group(celsius: f64) -> String {
+    let celsius = celsius.round();
+    if celsius < 0.0 {
+        format!("M{:02}", -celsius)
+    } else {
+        format!("{:02}", celsius)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use time::{Date, Month, Time};
+    use uom::num::Zero;
+    use uom::si::angle::degree;
+    use uom::si::f64::{Angle, Pressure, ThermodynamicTemperature as Temperature, Velocity};
+    use uom::si::pressure::pascal;
+
+    use super::*;
+    use crate::weather::{Clouds, OldClouds};
+
+    fn date_time(day: u8, hour: u8, minute: u8) -> PrimitiveDateTime {
+        PrimitiveDateTime::new(
+            Date::from_calendar_date(2021, Month::June, day).unwrap(),
+            Time::from_hms(hour, minute, 0).unwrap(),
+        )
+    }
+
+    fn weather() -> WeatherInfo {
+        WeatherInfo {
+            clouds: None,
+            wind_speed: Velocity::new::<knot>(5.0),
+            wind_dir: Angle::new::<degree>(330.0),
+            temperature: Temperature::new::<degree_celsius>(22.0),
+            pressure_sealevel: Pressure::new::<pascal>(101_500.0),
+            pressure_groundlevel: Pressure::new::<pascal>(101_500.0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_metar() {
+        assert_eq!(
+            generate_metar("UGKO", date_time(1, 12, 0), &weather(), Length::zero()),
+            "METAR UGKO 011200Z 33005KT 9999 NSC 22/08 Q1015 RMK A2997 QFE1015"
+        );
+    }
+
+    #[test]
+    fn test_metar_with_clouds_and_precipitation() {
+        let weather = WeatherInfo {
+            clouds: Some(Clouds::Old(OldClouds {
+                base: Length::new::<meter>(2_000),
+                density: 7,
+                thickness: Length::new::<meter>(800),
+                iprecptns: 2,
+            })),
+            wind_speed: Velocity::new::<knot>(2.0),
+            temperature: Temperature::new::<degree_celsius>(-3.4),
+            ..weather()
+        };
+
+        assert_eq!(
+            generate_metar("ugko", date_time(21, 7, 35), &weather, Length::zero()),
+            "METAR UGKO 210735Z VRB02KT 1200 TSRA BKN063 M03/M04 Q1015 RMK A2997 QFE1015"
+        );
+    }
+
+    #[test]
+    fn test_wind_group() {
+        let mut weather = weather();
+        weather.wind_dir = Angle::new::<degree>(2.0);
+        assert_eq!(wind_group(&weather), "36005KT");
+
+        weather.wind_speed = Velocity::new::<knot>(0.3);
+        assert_eq!(wind_group(&weather), "00000KT");
+
+        weather.wind_speed = Velocity::new::<knot>(15.0);
+        weather.turbulence = Velocity::new::<knot>(12.0);
+        assert_eq!(wind_group(&weather), "36015G27KT 320V040");
+    }
+
+    #[test]
+    fn test_visibility_group() {
+        assert_eq!(visibility_group(None), "9999");
+        assert_eq!(visibility_group(Some(Length::zero())), "0000");
+        assert_eq!(visibility_group(Some(Length::new::<meter>(730))), "0700");
+        assert_eq!(visibility_group(Some(Length::new::<meter>(1_234))), "1200");
+        assert_eq!(visibility_group(Some(Length::new::<meter>(7_400))), "7000");
+        assert_eq!(visibility_group(Some(Length::new::<meter>(12_000))), "9999");
+    }
+}
@@ -0,0 +1,709 @@
+pub mod metar;
+
+use serde::Deserialize;
+use uom::num::Zero;
+use uom::num_traits::Pow;
+use uom::si::angle::{degree, radian};
+use uom::si::f64::{
+    Angle, Length as LengthF64, Pressure, ThermodynamicTemperature as Temperature, Velocity,
+};
+use uom::si::i32::Length;
+use uom::si::length::{foot, meter};
+use uom::si::pressure::{millibar, pascal};
+use uom::si::thermodynamic_temperature::degree_celsius;
+use uom::si::velocity::knot;
+
+use crate::station::Position;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct WeatherInfo {
+    pub clouds: Option<Clouds>,
+    pub wind_speed: Velocity,
+    /// The direction the wind is coming from
+    pub wind_dir: Angle,
+    /// The ground turbulence of the mission, which causes the wind to gust and vary in direction.
+    /// DCS only provides a single mission-wide value (no per-altitude or per-location turbulence),
+    /// so it is only used for the surface wind.
+    pub turbulence: Velocity,
+    pub temperature: Temperature,
+    pub pressure_sealevel: Pressure,
+    pub pressure_groundlevel: Pressure,
+    /// This basically determines how heigh the fog is, from 0 to `fog_thickness`.
+    pub fog_thickness: Length,
+    pub fog_visibility: Length,
+    pub dust_density: i32,
+    pub position: Position,
+    /// The wind at higher altitudes (only retrieved for weather stations that report it).
+    pub winds_aloft: Vec<WindAloft>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct WindAloft {
+    /// The altitude (MSL) the wind has been sampled at.
+    pub alt: LengthF64,
+    pub wind_speed: Velocity,
+    /// The direction the wind is coming from
+    pub wind_dir: Angle,
+    pub temperature: Temperature,
+}
+
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Clouds {
+    New(NewClouds),
+    Old(OldClouds),
+}
+
+#[derive(Debug, PartialEq, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewClouds {
+    #[serde(deserialize_with = "crate::de::from_meter")]
+    pub base: Length,
+    pub preset: CloudPreset,
+}
+
+#[derive(Debug, PartialEq, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudPreset {
+    pub precipitation_power: f64,
+    pub layers: Vec<NewCloudLayer>,
+}
+
+#[derive(Debug, PartialEq, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewCloudLayer {
+    #[serde(deserialize_with = "crate::de::from_meter")]
+    altitude_min: Length,
+    #[serde(deserialize_with = "crate::de::from_meter")]
+    altitude_max: Length,
+    coverage: f32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Deserialize)]
+pub struct OldClouds {
+    #[serde(deserialize_with = "crate::de::from_meter")]
+    pub base: Length,
+    pub density: u32,
+    #[serde(deserialize_with = "crate::de::from_meter")]
+    pub thickness: Length,
+    pub iprecptns: u32,
+}
+
+impl WeatherInfo {
+    /// Get the speed of the wind gusts, if they exceed the mean wind speed by at least 10 knots
+    /// and the wind is not calm.
+    ///
+    /// DCS does not expose the actual gusts, so this is an approximation: the ground turbulence is
+    /// taken as the peak deviation from the mean wind speed, i.e. the gusts are the mean wind speed
+    /// plus the turbulence. Whether gusts are reported thus mostly depends on the turbulence.
+    pub fn get_wind_gust(&self) -> Option<Velocity> {
+        if self.wind_speed.get::<knot>() < VARIABLE_WIND_MIN_KT
+            || self.turbulence.get::<knot>() < GUST_THRESHOLD_KT
+        {
+            return None;
+        }
+
+        Some(self.wind_speed + self.turbulence)
+    }
+
+    /// Get the range the wind direction varies in (from, to), if it varies by at least 60° and
+    /// the wind is not calm.
+    pub fn get_wind_variability(&self) -> Option<(Angle, Angle)> {
+        if self.wind_speed.get::<knot>() < VARIABLE_WIND_MIN_KT || self.turbulence.is_zero() {
+            return None;
+        }
+
+        // the gusts can blow perpendicular to the mean wind
+        let deviation = self
+            .turbulence
+            .get::<knot>()
+            .atan2(self.wind_speed.get::<knot>());
+        let deviation = Angle::new::<radian>(deviation);
+        if deviation.get::<degree>() * 2.0 < VARIABLE_WIND_MIN_DEG {
+            return None;
+        }
+
+        Some((
+            normalize_angle(self.wind_dir - deviation),
+            normalize_angle(self.wind_dir + deviation),
+        ))
+    }
+
+    /// Get the headwind (negative for a tailwind) and crosswind (positive if coming from the
+    /// right) components of the wind for the given runway heading.
+    pub fn get_wind_components(&self, heading: Angle) -> (Velocity, Velocity) {
+        let angle = (self.wind_dir - heading).get::<radian>();
+        (self.wind_speed * angle.cos(), self.wind_speed * angle.sin())
+    }
+
+    /// Get QNH correct for the current temperature (as far as possible in DCS)
+    pub fn get_qnh(&self, alt: Length) -> Pressure {
+        let pressure_altitude = pressure_altitude(self.get_qfe(), self.pressure_sealevel);
+        let alt_diff = f64::from(alt.get::<foot>()) - pressure_altitude;
+
+        // this corrects the pressure for the current temperature
+        let correction = Pressure::new::<pascal>(alt_diff / 27.0 * 100.0);
+
+        self.pressure_sealevel + correction
+    }
+
+    /// Get QFE
+    pub fn get_qfe(&self) -> Pressure {
+        self.pressure_groundlevel
+    }
+
+    /// Get the pressure altitude, i.e. the altitude in the standard atmosphere at which the
+    /// pressure of the station (QFE) is found.
+    pub fn get_pressure_altitude(&self) -> Length {
+        let standard_pressure = Pressure::new::<millibar>(STANDARD_PRESSURE_MB);
+        Length::new::<foot>(pressure_altitude(self.get_qfe(), standard_pressure).round() as i32)
+    }
+
+    /// Get the transition level (as flight level) for the given transition altitude, i.e. the
+    /// lowest flight level in steps of 500ft that keeps a transition layer of at least 1000ft
+    /// above the transition altitude at the current QNH.
+    pub fn get_transition_level(&self, alt: Length, transition_alt: LengthF64) -> u32 {
+        let standard_pressure = Pressure::new::<millibar>(STANDARD_PRESSURE_MB);
+        let transition_alt =
+            transition_alt.get::<foot>() + pressure_altitude(self.get_qnh(alt), standard_pressure);
+        ((transition_alt + 1_000.0) / 500.0).ceil() as u32 * 5
+    }
+
+    /// Get the density altitude, i.e. the pressure altitude corrected for the deviation of the
+    /// temperature from the standard atmosphere.
+    pub fn get_density_altitude(&self) -> Length {
+        let pressure_altitude = f64::from(self.get_pressure_altitude().get::<foot>());
+        // the standard atmosphere cools down by 1.98°C per 1000ft
+        let isa_temperature = 15.0 - 1.98 * pressure_altitude / 1_000.0;
+        let deviation = self.temperature.get::<degree_celsius>() - isa_temperature;
+        Length::new::<foot>((pressure_altitude + 118.8 * deviation).round() as i32)
+    }
+
+    /// Estimate the dew point at the given altitude. DCS does not simulate humidity, so it is
+    /// derived from the fog, precipitation and clouds instead.
+    pub fn get_dew_point(&self, alt: Length) -> Temperature {
+        let temperature = self.temperature.get::<degree_celsius>();
+        let dew_point = match self.get_humidity_state(alt) {
+            HumidityState::Saturated => temperature,
+            HumidityState::Spread(spread) => temperature - spread,
+            HumidityState::Relative(humidity) => dew_point(temperature, humidity),
+        };
+        Temperature::new::<degree_celsius>(dew_point.min(temperature))
+    }
+
+    /// Get the relative humidity (in percent) at the given altitude, see `get_dew_point`.
+    pub fn get_relative_humidity(&self, alt: Length) -> f64 {
+        let temperature = self.temperature.get::<degree_celsius>();
+        let dew_point = self.get_dew_point(alt).get::<degree_celsius>();
+        // see https://en.wikipedia.org/wiki/Dew_point#Calculating_the_dew_point
+        100.0 * (magnus(dew_point) - magnus(temperature)).exp()
+    }
+
+    fn get_humidity_state(&self, alt: Length) -> HumidityState {
+        if self.get_fog_visibility(alt).is_some() {
+            return HumidityState::Saturated;
+        }
+
+        let conditions = self.get_weather_conditions(alt);
+        if conditions
+            .iter()
+            .any(|c| matches!(c, WeatherCondition::DustStorm))
+        {
+            return HumidityState::Relative(DUST_STORM_HUMIDITY);
+        }
+        if !conditions.is_empty() {
+            return HumidityState::Spread(PRECIPITATION_SPREAD);
+        }
+
+        for layer in self.get_cloud_layers() {
+            if matches!(layer.coverage, CloudCoverage::Clear) || layer.altitude_max < alt {
+                continue;
+            }
+            if layer.altitude_min <= alt {
+                return HumidityState::Saturated;
+            }
+            // the base of (cumulus) clouds forms where the rising air cools down to its dew
+            // point, which is roughly every 400ft of temperature/dew point spread
+            let base = f64::from((layer.altitude_min - alt).get::<foot>());
+            return HumidityState::Spread((base / 400.0).min(MAX_SPREAD));
+        }
+
+        HumidityState::Relative(CLEAR_SKY_HUMIDITY)
+    }
+
+    pub fn get_visibility(&self, alt: Length) -> Option<Length> {
+        let clouds_vis = self.clouds.as_ref().and_then(|c| c.get_visibility(alt));
+        let dust_vis = self.get_dust_storm_visibility(alt);
+        let fog_vis = self.get_fog_visibility(alt);
+        let vis = vec![clouds_vis, dust_vis, fog_vis]
+            .into_iter()
+            .flatten()
+            .min();
+        // Visibility below 50m is considered as ZERO
+        vis.map(|v| {
+            if v < Length::new::<meter>(50) {
+                Length::zero()
+            } else {
+                v
+            }
+        })
+    }
+
+    fn get_dust_storm_visibility(&self, alt: Length) -> Option<Length> {
+        // The dust will only be between 0 and 50 meters altitude
+        if self.dust_density == 0 || alt > Length::new::<meter>(50) {
+            return None;
+        }
+
+        // The multiplier of 4 was derived by manual testing the resulting visibility.
+        Some(Length::new::<meter>(self.dust_density * 4))
+    }
+
+    fn get_fog_visibility(&self, alt: Length) -> Option<Length> {
+        if self.fog_visibility.is_zero() || alt > self.fog_thickness {
+            return None;
+        }
+
+        Some(self.fog_visibility)
+    }
+
+    pub fn get_ceiling(&self, alt: Length) -> Option<Ceiling> {
+        for layer in self.get_cloud_layers() {
+            if (layer.altitude_min..=layer.altitude_max).contains(&alt) {
+                return None;
+            }
+
+            if layer.altitude_min > alt
+                && matches!(
+                    layer.coverage,
+                    CloudCoverage::Broken | CloudCoverage::Overcast
+                )
+            {
+                return Some(Ceiling {
+                    alt: layer.altitude_min,
+                    coverage: layer.coverage,
+                });
+            }
+        }
+
+        None
+    }
+
+    pub fn get_cloud_layers(&self) -> Vec<CloudLayer> {
+        self.clouds
+            .as_ref()
+            .map(|c| c.get_cloud_layers())
+            .unwrap_or_default()
+    }
+
+    pub fn get_weather_conditions(&self, alt: Length) -> Vec<WeatherCondition> {
+        let mut kind = self
+            .clouds
+            .as_ref()
+            .map(|c| c.get_weather_conditions())
+            .unwrap_or_default();
+        if self.get_dust_storm_visibility(alt).is_some() {
+            kind.push(WeatherCondition::DustStorm);
+        }
+        if self.get_fog_visibility(alt).is_some() {
+            kind.push(WeatherCondition::Fog);
+        }
+        kind
+    }
+}
+
+/// Gusts are only reported if they exceed the mean wind speed by this amount.
+const GUST_THRESHOLD_KT: f64 = 10.0;
+/// Gusts and variable wind directions are only reported for wind speeds of at least this amount.
+const VARIABLE_WIND_MIN_KT: f64 = 3.0;
+/// Variable wind directions are only reported if they vary by at least this amount.
+const VARIABLE_WIND_MIN_DEG: f64 = 60.0;
+const STANDARD_PRESSURE_MB: f64 = 1013.25;
+/// Spread between temperature and dew point while it is raining.
+const PRECIPITATION_SPREAD: f64 = 1.0;
+/// Max. spread between temperature and dew point derived from the cloud base.
+const MAX_SPREAD: f64 = 25.0;
+/// Relative humidity (in percent) assumed for a clear sky.
+const CLEAR_SKY_HUMIDITY: f64 = 40.0;
+/// Relative humidity (in percent) assumed during dust storms.
+const DUST_STORM_HUMIDITY: f64 = 15.0;
+
+enum HumidityState {
+    Saturated,
+    /// Spread between temperature and dew point in °C.
+    Spread(f64),
+    /// Relative humidity in percent.
+    Relative(f64),
+}
+
+/// Round a wind direction to steps of ten degrees, with north being 360 instead of 000.
+pub fn round_wind_dir(dir: Angle) -> i32 {
+    let dir = (dir.get::<degree>() / 10.0).round() as i32 * 10;
+    if dir <= 0 { dir + 360 } else { dir }
+}
+
+fn normalize_angle(angle: Angle) -> Angle {
+    Angle::new::<degree>(angle.get::<degree>().rem_euclid(360.0))
+}
+
+/// Altitude (in ft) in the standard atmosphere at which `pressure` is found, given the
+/// `reference` pressure at zero altitude (see https://en.wikipedia.org/wiki/Pressure_altitude).
+fn pressure_altitude(pressure: Pressure, reference: Pressure) -> f64 {
+    14_5366.45 * (1.0 - (pressure.get::<millibar>() / reference.get::<millibar>()).pow(0.190284))
+}
+
+// Magnus formula constants (see https://en.wikipedia.org/wiki/Dew_point)
+const MAGNUS_B: f64 = 17.625;
+const MAGNUS_C: f64 = 243.04;
+
+fn magnus(celsius: f64) -> f64 {
+    MAGNUS_B * celsius / (MAGNUS_C + celsius)
+}
+
+/// Dew point (in °C) for the given temperature (in °C) and relative humidity (in percent).
+fn dew_point(celsius: f64, humidity: f64) -> f64 {
+    let gamma = (humidity / 100.0).ln() + magnus(celsius);
+    MAGNUS_C * gamma / (MAGNUS_B - gamma)
+}
+
+pub struct Ceiling {
+    pub alt: Length,
+    pub coverage: CloudCoverage,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum CloudCoverage {
+    Clear,
+    Few,
+    Scattered,
+    Broken,
+    Overcast,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum WeatherCondition {
+    SlightRain,
+    Rain,
+    HeavyRain,
+    Thunderstorm,
+    Fog,
+    DustStorm,
+}
+
+pub struct CloudLayer {
+    coverage: CloudCoverage,
+    altitude_min: Length,
+    altitude_max: Length,
+}
+
+impl Clouds {
+    pub fn get_cloud_layers(&self) -> Vec<CloudLayer> {
+        match self {
+            Clouds::New(clouds) => clouds.get_cloud_layers(),
+            Clouds::Old(clouds) => clouds.get_cloud_layers(),
+        }
+    }
+
+    pub fn get_weather_conditions(&self) -> Vec<WeatherCondition> {
+        match self {
+            Clouds::New(clouds) => clouds.get_weather_conditions(),
+            Clouds::Old(clouds) => clouds.get_weather_conditions(),
+        }
+    }
+
+    pub fn get_visibility(&self, alt: Length) -> Option<Length> {
+        for layer in self.get_cloud_layers() {
+            if alt >= layer.altitude_min
+                && alt <= layer.altitude_max
+                && matches!(
+                    layer.coverage,
+                    CloudCoverage::Scattered | CloudCoverage::Broken | CloudCoverage::Overcast
+                )
+            {
+                return Some(Length::zero());
+            }
+        }
+
+        match self {
+            Clouds::New(clouds) => clouds.get_visibility(),
+            Clouds::Old(clouds) => clouds.get_visibility(),
+        }
+    }
+}
+
+impl NewClouds {
+    pub fn get_cloud_layers(&self) -> Vec<CloudLayer> {
+        let diff = match self.preset.layers.first() {
+            Some(first) => self.base - first.altitude_min,
+            None => return Vec::new(),
+        };
+
+        self.preset
+            .layers
+            .iter()
+            .map(|layer| CloudLayer {
+                coverage: match layer.coverage {
+                    x if (0.0..0.3).contains(&x) => CloudCoverage::Clear,
+                    x if (0.3..0.5).contains(&x) => CloudCoverage::Few,
+                    x if (0.5..0.6).contains(&x) => CloudCoverage::Scattered,
+                    x if (0.6..0.9).contains(&x) => CloudCoverage::Broken,
+                    x if (0.9..f32::MAX).contains(&x) => CloudCoverage::Overcast,
+                    _ => CloudCoverage::Clear, // unreachable
+                },
+                altitude_min: layer.altitude_min + diff,
+                altitude_max: layer.altitude_max + diff,
+            })
+            .collect()
+    }
+
+    pub fn get_weather_conditions(&self) -> Vec<WeatherCondition> {
+        if self.preset.precipitation_power <= 0.0 {
+            return vec![];
+        }
+
+        match self.preset.precipitation_power {
+            x if (0.0..0.5).contains(&x) => vec![WeatherCondition::SlightRain],
+            x if (0.5..0.8).contains(&x) => vec![WeatherCondition::Rain],
+            x if (0.8..f64::MAX).contains(&x) => vec![WeatherCondition::HeavyRain],
+            _ => vec![], // unreachable
+        }
+    }
+
+    pub fn get_visibility(&self) -> Option<Length> {
+        if self.preset.precipitation_power <= 0.0 {
+            return None;
+        }
+
+        match self.preset.precipitation_power {
+            x if (0.0..0.3).contains(&x) => Some(Length::new::<meter>(5_000)),
+            x if (0.3..0.5).contains(&x) => Some(Length::new::<meter>(4_000)),
+            x if (0.5..0.6).contains(&x) => Some(Length::new::<meter>(3_000)),
+            x if (0.6..0.7).contains(&x) => Some(Length::new::<meter>(2_500)),
+            x if (0.7..0.8).contains(&x) => Some(Length::new::<meter>(2_000)),
+            x if (0.8..0.9).contains(&x) => Some(Length::new::<meter>(1_500)),
+            x if (0.9..0.97).contains(&x) => Some(Length::new::<meter>(1_000)),
+            x if (1.0..f64::MAX).contains(&x) => Some(Length::new::<meter>(700)),
+            _ => None, // unreachable
+        }
+    }
+}
+
+impl OldClouds {
+    pub fn get_cloud_layers(&self) -> Vec<CloudLayer> {
+        vec![CloudLayer {
+            coverage: match self.density {
+                x if (0..2).contains(&x) => CloudCoverage::Clear,
+                x if (2..4).contains(&x) => CloudCoverage::Few,
+                x if (4..6).contains(&x) => CloudCoverage::Scattered,
+                x if (6..9).contains(&x) => CloudCoverage::Broken,
+                x if (9..u32::MAX).contains(&x) => CloudCoverage::Overcast,
+                _ => CloudCoverage::Clear, // unreachable
+            },
+            altitude_min: if self.base < Length::new::<foot>(1_000) {
+                Length::zero()
+            } else {
+                self.base - Length::new::<foot>(200)
+            },
+            altitude_max: self.base + self.thickness - Length::new::<foot>(200),
+        }]
+    }
+
+    pub fn get_weather_conditions(&self) -> Vec<WeatherCondition> {
+        match self.iprecptns {
+            1 => vec![WeatherCondition::Rain],
+            2 => vec![WeatherCondition::Rain, WeatherCondition::Thunderstorm],
+            _ => vec![],
+        }
+    }
+
+    pub fn get_visibility(&self) -> Option<Length> {
+        match self.iprecptns {
+            1 => Some(Length::new::<meter>(7_400)),
+            2 => Some(Length::new::<meter>(1_200)),
+            _ => None,
+        }
+    }
+}
+
+impl CloudCoverage {
+    pub fn to_metar(self) -> &'static str {
+        match self {
+            CloudCoverage::Clear => "CLR",
+            CloudCoverage::Few => "FEW",
+            CloudCoverage::Scattered => "SCT",
+            CloudCoverage::Broken => "BKN",
+            CloudCoverage::Overcast => "OVC",
+        }
+    }
+}
+
+impl std::fmt::Display for CloudCoverage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CloudCoverage::Clear => "Clear",
+            CloudCoverage::Few => "Few",
+            CloudCoverage::Scattered => "Scattered",
+            CloudCoverage::Broken => "Broken",
+            CloudCoverage::Overcast => "Overcast",
+        })
+    }
+}
+
+impl WeatherCondition {
+    pub fn to_metar(self) -> &'static str {
+        match self {
+            WeatherCondition::SlightRain => "-RA",
+            WeatherCondition::Rain => "RA",
+            WeatherCondition::HeavyRain => "+RA",
+            WeatherCondition::Thunderstorm => "TS",
+            WeatherCondition::Fog => "FG",
+            WeatherCondition::DustStorm => "DS",
+        }
+    }
+}
+
+impl std::fmt::Display for WeatherCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            WeatherCondition::SlightRain => "Slight Rain",
+            WeatherCondition::Rain => "Rain",
+            WeatherCondition::HeavyRain => "Heavy Rain",
+            WeatherCondition::Thunderstorm => "Thunderstorm",
+            WeatherCondition::Fog => "Fog",
+            WeatherCondition::DustStorm => "Dust Storm",
+        })
+    }
+}
+
+impl Default for Clouds {
+    fn default() -> Self {
+        Clouds::Old(OldClouds::default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn weather(temperature: f64, qfe: f64) -> WeatherInfo {
+        WeatherInfo {
+            temperature: Temperature::new::<degree_celsius>(temperature),
+            pressure_sealevel: Pressure::new::<millibar>(1013.25),
+            pressure_groundlevel: Pressure::new::<millibar>(qfe),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pressure_and_density_altitude() {
+        let weather = weather(15.0, 1013.25);
+        assert_eq!(weather.get_pressure_altitude(), Length::zero());
+        assert_eq!(weather.get_density_altitude(), Length::zero());
+
+        // hot and high
+        let weather = self::weather(35.0, 843.0);
+        assert_eq!(weather.get_pressure_altitude().get::<foot>(), 5_000);
+        assert_eq!(weather.get_density_altitude().get::<foot>(), 8_549);
+    }
+
+    #[test]
+    fn test_transition_level() {
+        let ta = LengthF64::new::<foot>(6_000.0);
+        assert_eq!(
+            weather(15.0, 1013.25).get_transition_level(Length::zero(), ta),
+            70
+        );
+        assert_eq!(
+            weather(15.0, 1035.0).get_transition_level(Length::zero(), ta),
+            65
+        );
+        assert_eq!(
+            weather(15.0, 990.0).get_transition_level(Length::zero(), ta),
+            80
+        );
+        assert_eq!(
+            weather(15.0, 960.0).get_transition_level(Length::zero(), ta),
+            90
+        );
+    }
+
+    #[test]
+    fn test_wind_gust_and_variability() {
+        let mut weather = WeatherInfo {
+            wind_speed: Velocity::new::<knot>(15.0),
+            wind_dir: Angle::new::<degree>(10.0),
+            turbulence: Velocity::new::<knot>(5.0),
+            ..Default::default()
+        };
+        assert_eq!(weather.get_wind_gust(), None);
+        assert!(weather.get_wind_variability().is_none());
+
+        // gusts just below the threshold are not reported
+        weather.turbulence = Velocity::new::<knot>(9.9);
+        assert_eq!(weather.get_wind_gust(), None);
+
+        weather.turbulence = Velocity::new::<knot>(12.0);
+        assert_eq!(
+            weather.get_wind_gust().map(|v| v.get::<knot>().round()),
+            Some(27.0)
+        );
+        let (from, to) = weather.get_wind_variability().unwrap();
+        assert_eq!(round_wind_dir(from), 330);
+        assert_eq!(round_wind_dir(to), 50);
+
+        // no gusts or variable direction for calm winds
+        weather.wind_speed = Velocity::new::<knot>(1.0);
+        assert_eq!(weather.get_wind_gust(), None);
+        assert!(weather.get_wind_variability().is_none());
+    }
+
+    #[test]
+    fn test_wind_components() {
+        let weather = WeatherInfo {
+            wind_speed: Velocity::new::<knot>(20.0),
+            wind_dir: Angle::new::<degree>(70.0),
+            ..Default::default()
+        };
+
+        let components = |heading: f64| {
+            let (head, cross) = weather.get_wind_components(Angle::new::<degree>(heading));
+            (head.get::<knot>().round(), cross.get::<knot>().round())
+        };
+        assert_eq!(components(40.0), (17.0, 10.0));
+        assert_eq!(components(100.0), (17.0, -10.0));
+        assert_eq!(components(220.0), (-17.0, -10.0));
+    }
+
+    #[test]
+    fn test_dew_point() {
+        let mut weather = weather(20.0, 1013.25);
+        assert_eq!(weather.get_relative_humidity(Length::zero()).round(), 40.0);
+
+        weather.clouds = Some(Clouds::Old(OldClouds {
+            base: Length::new::<foot>(4_200),
+            density: 5,
+            thickness: Length::new::<foot>(1_000),
+            iprecptns: 0,
+        }));
+        // clouds 4000ft above the station
+        assert_eq!(
+            weather
+                .get_dew_point(Length::zero())
+                .get::<degree_celsius>()
+                .round(),
+            10.0
+        );
+
+        weather.fog_visibility = Length::new::<meter>(800);
+        weather.fog_thickness = Length::new::<meter>(100);
+        assert_eq!(
+            weather
+                .get_dew_point(Length::zero())
+                .get::<degree_celsius>(),
+            20.0
+        );
+        assert_eq!(weather.get_relative_humidity(Length::zero()).round(), 100.0);
+    }
+}
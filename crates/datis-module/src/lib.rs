@@ -2,6 +2,7 @@ mod config;
 mod mission;
 
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
 
@@ -9,6 +10,11 @@ use config::read_config;
 use datis_core::Datis;
 use datis_core::config::Config;
 use datis_core::ipc::MissionRpc;
+use datis_core::station::{
+    Coalition, Custom, MissionInterface, Position, Station, Transmitter, all_coalitions,
+};
+use datis_core::tts::prosody::Prosody;
+use datis_core::tts::{TextToSpeechProvider, catalog};
 use mlua::prelude::*;
 use mlua::{Function, Value};
 use once_cell::sync::Lazy;
@@ -56,6 +62,13 @@ pub fn init(lua: &Lua) -> Result<(Config, PathBuf), mlua::Error> {
     )
     .map_err(|err| to_lua_err("initializing logging", err))?;
 
+    // voices are checked against the catalog while parsing the mission, those of the config have
+    // already been parsed before logging was set up
+    catalog::load(&write_dir.join("DATIS").join("Cache"));
+    for tts in std::iter::once(&config.default_voice).chain(&config.fallback_voices) {
+        tts.warn_if_unknown();
+    }
+
     Ok((config, write_dir))
 }
 
@@ -70,12 +83,17 @@ fn start(lua: &Lua, (): ()) -> LuaResult<()> {
     log::info!("Starting DATIS version {} ...", env!("CARGO_PKG_VERSION"));
     log::info!("Using SRS Server port: {}", config.srs_port);
 
-    let info = mission::extract(lua, &config.default_voice)
+    catalog::refresh_in_background(write_dir.join("DATIS").join("Cache"), config.clone());
+
+    let template_dir = write_dir.join("DATIS").join("Templates");
+    let info = mission::extract(lua, &config.default_voice, &template_dir)
         .map_err(|err| to_lua_err("extracting mission information", err))?;
 
     let mut datis = Datis::new(info.stations, config)
         .map_err(|err| to_lua_err("creating DATIS instance", err))?;
     datis.enable_exporter(write_dir.join("Logs"));
+    datis.enable_tts_cache(write_dir.join("DATIS").join("Cache"));
+    datis.enable_lexicon(write_dir.join("DATIS").join("Lexicon.txt"));
 
     let mut d = DATIS.write().unwrap();
     *d = Some((datis, info.ipc));
@@ -162,6 +180,182 @@ fn try_next(lua: &Lua, callback: Function) -> LuaResult<bool> {
     Ok(false)
 }
 
+/// `datis.addBroadcast(freq, message[, voice[, options]])`
+///
+/// Adds a custom broadcast station at `freq` (in MHz) and returns its name. The supported
+/// `options` are `name`, `coalition` (`"blue"`, `"red"` or `"all"`), and either `unit`, the name of
+/// the unit to broadcast from, or `position`, a fixed position (`{ x, y, z }` as returned by
+/// `getPoint()` in mission scripts) to broadcast from.
+fn add_broadcast(
+    lua: &Lua,
+    (freq, message, voice, options): (f64, String, Option<String>, Option<LuaTable>),
+) -> LuaResult<String> {
+    let mut guard = DATIS.write().unwrap();
+    let (datis, ipc) = guard.as_mut().ok_or(Error::NotRunning)?;
+
+    let tts = match voice {
+        Some(voice) => TextToSpeechProvider::from_str(&voice)
+            .map_err(|err| to_lua_err("parsing broadcast voice", err))?,
+        None => datis.config().default_voice.clone(),
+    };
+
+    let (name, coalition, unit, position) = match options {
+        Some(options) => (
+            options.get::<Option<String>>("name")?,
+            options.get::<Option<String>>("coalition")?,
+            options.get::<Option<String>>("unit")?,
+            options.get::<Option<LuaTable>>("position")?,
+        ),
+        None => (None, None, None, None),
+    };
+    let name = name.unwrap_or_else(|| {
+        (1..)
+            .map(|i| format!("Broadcast {}", i))
+            .find(|name| datis.station(name).is_none())
+            .unwrap()
+    });
+    let coalitions = match coalition.as_deref().map(str::to_lowercase).as_deref() {
+        None | Some("blue") => vec![Coalition::Blue],
+        Some("red") => vec![Coalition::Red],
+        Some("all") | Some("neutral") => all_coalitions(),
+        Some(other) => {
+            return Err(to_lua_err(
+                "adding broadcast",
+                Error::Coalition(other.into()),
+            ));
+        }
+    };
+
+    let (position, unit_id, unit_name) = match (unit, position) {
+        (Some(unit), _) => {
+            let unit_id = find_unit_id(lua, &unit)?
+                .ok_or_else(|| to_lua_err("adding broadcast", Error::UnitNotFound(unit.clone())))?;
+            (None, unit_id, unit)
+        }
+        // mission scripts use `{ x, y, z }` with `y` being the altitude
+        (None, Some(position)) => (
+            Some(Position {
+                x: position.get("x")?,
+                y: position.get("z")?,
+                alt: position.get::<Option<f64>>("y")?.unwrap_or_default(),
+            }),
+            0,
+            String::new(),
+        ),
+        (None, None) => return Err(to_lua_err("adding broadcast", Error::NoPosition)),
+    };
+
+    let station = Station {
+        name: name.clone(),
+        freq: (freq * 1_000_000.0).round() as u64,
+        tts,
+        fallback_tts: Vec::new(),
+        prosody: Prosody::default(),
+        transmitter: Transmitter::Custom(Custom {
+            position,
+            unit_id,
+            unit_name,
+            message,
+        }),
+        ipc: MissionInterface::Ipc(ipc.clone()),
+        coalitions,
+        template: None,
+        radio_effects: None,
+    };
+    datis
+        .add_station(station)
+        .map_err(|err| to_lua_err("adding broadcast", err))?;
+
+    Ok(name)
+}
+
+/// Look up the id of the unit or static object with the given `name` in the mission scripting
+/// environment.
+fn find_unit_id(lua: &Lua, name: &str) -> LuaResult<Option<u32>> {
+    let net: LuaTable = lua.globals().get("net")?;
+    let dostring_in: Function = net.get("dostring_in")?;
+    let result: String = dostring_in.call((
+        "server",
+        format!(
+            r#"
+            local unit = Unit.getByName([==[{name}]==]) or StaticObject.getByName([==[{name}]==])
+            if unit == nil then
+              return ""
+            else
+              return tostring(unit:getID())
+            end
+            "#
+        ),
+    ))?;
+    Ok(result.parse().ok())
+}
+
+/// `datis.setActiveRunway(airfield[, runway])`, resets to the wind-based runway if `nil`.
+fn set_active_runway(_: &Lua, (name, runway): (String, Option<String>)) -> LuaResult<()> {
+    let mut guard = DATIS.write().unwrap();
+    let (datis, _) = guard.as_mut().ok_or(Error::NotRunning)?;
+    datis
+        .set_active_runway(&name, runway)
+        .map_err(|err| to_lua_err("setting active runway", err))
+}
+
+/// `datis.setRunwayClosed(airfield, runway[, closed])`, `closed` defaults to `true`.
+fn set_runway_closed(
+    _: &Lua,
+    (name, runway, closed): (String, String, Option<bool>),
+) -> LuaResult<()> {
+    let mut guard = DATIS.write().unwrap();
+    let (datis, _) = guard.as_mut().ok_or(Error::NotRunning)?;
+    datis
+        .set_runway_closed(&name, &runway, closed.unwrap_or(true))
+        .map_err(|err| to_lua_err("setting runway closed", err))
+}
+
+/// `datis.setInfoLetter(airfield[, letter])`, resets to the rotating letter if `nil`.
+fn set_info_letter(_: &Lua, (name, letter): (String, Option<String>)) -> LuaResult<()> {
+    let mut guard = DATIS.write().unwrap();
+    let (datis, _) = guard.as_mut().ok_or(Error::NotRunning)?;
+    let letter = match letter {
+        Some(letter) => {
+            let mut chars = letter.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii_alphabetic() => Some(c),
+                _ => {
+                    return Err(to_lua_err(
+                        "setting information letter",
+                        Error::InfoLetter(letter),
+                    ));
+                }
+            }
+        }
+        None => None,
+    };
+    datis
+        .set_info_letter(&name, letter)
+        .map_err(|err| to_lua_err("setting information letter", err))
+}
+
+/// `datis.removeStation(name)`
+fn remove_station(_: &Lua, name: String) -> LuaResult<()> {
+    let mut guard = DATIS.write().unwrap();
+    let (datis, _) = guard.as_mut().ok_or(Error::NotRunning)?;
+    datis
+        .remove_station(&name)
+        .map_err(|err| to_lua_err("removing station", err))?;
+    Ok(())
+}
+
+/// `datis.getReport(name)`, returns the latest report of the station (same structure as in
+/// `atis-reports.json`) or `nil` if there is none yet.
+fn get_report(lua: &Lua, name: String) -> LuaResult<Value> {
+    let guard = DATIS.read().unwrap();
+    let (datis, _) = guard.as_ref().ok_or(Error::NotRunning)?;
+    match datis.status().get(&name).and_then(|status| status.report) {
+        Some(report) => lua.to_value(&report),
+        None => Ok(Value::Nil),
+    }
+}
+
 #[mlua::lua_module]
 pub fn datis(lua: &Lua) -> LuaResult<LuaTable> {
     let exports = lua.create_table()?;
@@ -170,6 +364,12 @@ pub fn datis(lua: &Lua) -> LuaResult<LuaTable> {
     exports.set("pause", lua.create_function(pause)?)?;
     exports.set("resume", lua.create_function(resume)?)?;
     exports.set("try_next", lua.create_function(try_next)?)?;
+    exports.set("addBroadcast", lua.create_function(add_broadcast)?)?;
+    exports.set("setActiveRunway", lua.create_function(set_active_runway)?)?;
+    exports.set("setRunwayClosed", lua.create_function(set_runway_closed)?)?;
+    exports.set("setInfoLetter", lua.create_function(set_info_letter)?)?;
+    exports.set("removeStation", lua.create_function(remove_station)?)?;
+    exports.set("getReport", lua.create_function(get_report)?)?;
     Ok(exports)
 }
 
@@ -179,6 +379,22 @@ pub enum Error {
     DeserializeParams(#[source] mlua::Error),
     #[error("Failed to serialize params: {0}")]
     SerializeParams(#[source] mlua::Error),
+    #[error("DATIS is not running")]
+    NotRunning,
+    #[error("Invalid coalition `{0}`, expected blue, red or all")]
+    Coalition(String),
+    #[error("Unit `{0}` not found")]
+    UnitNotFound(String),
+    #[error("Either a `unit` or a `position` is required")]
+    NoPosition,
+    #[error("Invalid information letter `{0}`, expected A to Z")]
+    InfoLetter(String),
+}
+
+impl From<Error> for mlua::Error {
+    fn from(err: Error) -> Self {
+        mlua::Error::ExternalError(Arc::new(err))
+    }
 }
 
 fn to_lua_err(context: &str, err: impl std::error::Error + Send + Sync + 'static) -> mlua::Error {
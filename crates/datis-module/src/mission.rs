@@ -1,9 +1,14 @@
 use std::collections::HashMap;
+use std::path::Path;
 
 use datis_core::extract::*;
 use datis_core::ipc::*;
+use datis_core::locale::Locale;
 use datis_core::station::*;
+use datis_core::template::Template;
 use datis_core::tts::TextToSpeechProvider;
+use datis_core::tts::prosody::Prosody;
+use datis_core::units::UnitProfile;
 use mlua::ObjectLike as _;
 use mlua::prelude::{Lua, LuaTable};
 use rand::Rng;
@@ -13,7 +18,11 @@ pub struct Info {
     pub ipc: MissionRpc,
 }
 
-pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info, mlua::Error> {
+pub fn extract(
+    lua: &Lua,
+    default_voice: &TextToSpeechProvider,
+    template_dir: &Path,
+) -> Result<Info, mlua::Error> {
     // extract frequencies from mission briefing, which is retrieved from
     // `DCS.getMissionDescription()`
     let station_configs_from_description = {
@@ -22,6 +31,15 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
         extract_station_config_from_mission_description(&mission_description)
     };
 
+    // read the map (e.g. `Caucasus`) to derive defaults like the transition altitude
+    let theatre: String = {
+        let current_mission: LuaTable = lua.globals().get("_current_mission")?;
+        let mission: LuaTable = current_mission.get("mission")?;
+        mission
+            .get::<Option<String>>("theatre")?
+            .unwrap_or_default()
+    };
+
     // Create a random generator for creating the information letter offset.
     let mut rng = rand::rng();
 
@@ -44,14 +62,13 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
                 (x, y)
             };
 
-            let mut runways: Vec<String> = Vec::new();
+            let mut runways: Vec<Runway> = Vec::new();
             if let Some(rwys) = airdrome.get::<Option<LuaTable>>("runways")? {
                 for pair in rwys.pairs::<usize, LuaTable>() {
                     let (_, rwy) = pair?;
                     let start: String = rwy.get("start")?;
                     let end: String = rwy.get("end")?;
-                    runways.push(start);
-                    runways.push(end);
+                    runways.push(Runway::new(start, end));
                 }
             }
 
@@ -59,6 +76,7 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
                 display_name.clone(),
                 Airfield {
                     name: display_name,
+                    icao: None,
                     position: Position { x, y, alt: 0.0 },
                     runways,
                     traffic_freq: None,
@@ -67,6 +85,14 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
                     active_rwy_override: None,
                     no_hpa: false,
                     no_qfe: false,
+                    dew_point: false,
+                    density_alt: false,
+                    transition_alt: None,
+                    runway_rules: RunwayRules::default(),
+                    locale: Locale::default(),
+                    units: UnitProfile::default(),
+                    crosswind_limit: None,
+                    tailwind_limit: None,
                 },
             );
         }
@@ -82,8 +108,13 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
 
         let mut mission_units = Vec::new();
 
-        for key in &["blue", "red", "neutrals"] {
-            let coalition = match coalitions.get::<Option<LuaTable>>(*key)? {
+        for (key, unit_coalitions) in [
+            ("blue", vec![Coalition::Blue]),
+            ("red", vec![Coalition::Red]),
+            // neutral units broadcast to all coalitions
+            ("neutrals", all_coalitions()),
+        ] {
+            let coalition = match coalitions.get::<Option<LuaTable>>(key)? {
                 Some(coalition) => coalition,
                 None => continue,
             };
@@ -113,6 +144,7 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
                                             y,
                                             alt: alt.unwrap_or(0.0),
                                             is_static: key == "static",
+                                            coalitions: unit_coalitions.clone(),
                                         });
                                     }
                                 }
@@ -179,13 +211,37 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
                 airfield.active_rwy_override = config.active_rwy_override;
                 airfield.no_hpa = config.no_hpa;
                 airfield.no_qfe = config.no_qfe;
+                airfield.dew_point = config.dew_point;
+                airfield.density_alt = config.density_alt;
+                airfield.transition_alt = config.transition_level.then(|| {
+                    config
+                        .transition_alt
+                        .unwrap_or_else(|| default_transition_alt(&theatre))
+                });
+                airfield.runway_rules = config.runway_rules;
+                airfield.locale = config.locale;
+                airfield.units = config.units;
+                airfield.crosswind_limit = config.crosswind_limit;
+                airfield.tailwind_limit = config.tailwind_limit;
+                airfield.icao = config.icao;
 
                 Station {
                     name,
                     freq: config.atis,
                     tts: config.tts.unwrap_or_else(|| default_voice.clone()),
+                    fallback_tts: config.fallback_tts,
+                    prosody: config.prosody,
                     transmitter: Transmitter::Airfield(airfield),
                     ipc: MissionInterface::Ipc(ipc.clone()),
+                    // there is no owning unit for stations defined in the mission situation
+                    coalitions: config.coalition.unwrap_or_else(|| vec![Coalition::Blue]),
+                    template: load_template(
+                        template_dir,
+                        config.template.as_deref(),
+                        "airfield",
+                        config.locale,
+                    ),
+                    radio_effects: config.radio_effects,
                 }
             })
         })
@@ -201,6 +257,19 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
                 airfield.active_rwy_override = config.active_rwy_override;
                 airfield.no_hpa = config.no_hpa;
                 airfield.no_qfe = config.no_qfe;
+                airfield.dew_point = config.dew_point;
+                airfield.density_alt = config.density_alt;
+                airfield.transition_alt = config.transition_level.then(|| {
+                    config
+                        .transition_alt
+                        .unwrap_or_else(|| default_transition_alt(&theatre))
+                });
+                airfield.runway_rules = config.runway_rules;
+                airfield.locale = config.locale;
+                airfield.units = config.units;
+                airfield.crosswind_limit = config.crosswind_limit;
+                airfield.tailwind_limit = config.tailwind_limit;
+                airfield.icao = config.icao;
                 airfield.position.x = mission_unit.x;
                 airfield.position.y = mission_unit.y;
                 airfield.position.alt = mission_unit.alt;
@@ -209,8 +278,20 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
                     name: config.name,
                     freq: config.atis,
                     tts: config.tts.unwrap_or_else(|| default_voice.clone()),
+                    fallback_tts: config.fallback_tts,
+                    prosody: config.prosody,
                     transmitter: Transmitter::Airfield(airfield),
                     ipc: MissionInterface::Ipc(ipc.clone()),
+                    coalitions: config
+                        .coalition
+                        .unwrap_or_else(|| mission_unit.coalitions.clone()),
+                    template: load_template(
+                        template_dir,
+                        config.template.as_deref(),
+                        "airfield",
+                        config.locale,
+                    ),
+                    radio_effects: config.radio_effects,
                 }
             })
         })
@@ -222,10 +303,11 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
         log::info!("ATIS Stations:");
         for station in &stations {
             log::info!(
-                "  - {} (Freq: {}, Voice: {:?})",
+                "  - {} (Freq: {}, Voice: {:?}, Coalitions: {:?})",
                 station.name,
                 station.freq,
-                station.tts
+                station.tts,
+                station.coalitions
             );
         }
     }
@@ -237,12 +319,22 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
                 name: config.name.clone(),
                 freq: config.atis,
                 tts: config.tts.unwrap_or_else(|| default_voice.clone()),
+                fallback_tts: config.fallback_tts,
+                prosody: config.prosody,
                 transmitter: Transmitter::Carrier(Carrier {
                     name: config.name,
                     unit_id: mission_unit.id,
                     unit_name: mission_unit.name.clone(),
                 }),
                 ipc: MissionInterface::Ipc(ipc.clone()),
+                coalitions: mission_unit.coalitions.clone(),
+                template: load_template(
+                    template_dir,
+                    config.template.as_deref(),
+                    "carrier",
+                    Locale::En,
+                ),
+                radio_effects: None,
             })
         })
         .collect::<Vec<_>>();
@@ -253,10 +345,11 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
         log::info!("Carrier Stations:");
         for station in &carriers {
             log::info!(
-                "  - {} (Freq: {}, Voice: {:?})",
+                "  - {} (Freq: {}, Voice: {:?}, Coalitions: {:?})",
                 station.name,
                 station.freq,
-                station.tts
+                station.tts,
+                station.coalitions
             );
         }
     }
@@ -268,6 +361,8 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
                 name: mission_unit.name.clone(),
                 freq: config.freq,
                 tts: config.tts.unwrap_or_else(|| default_voice.clone()),
+                fallback_tts: Vec::new(),
+                prosody: Prosody::default(),
                 transmitter: Transmitter::Custom(Custom {
                     position: if mission_unit.is_static {
                         Some(Position {
@@ -283,6 +378,9 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
                     message: config.message,
                 }),
                 ipc: MissionInterface::Ipc(ipc.clone()),
+                coalitions: mission_unit.coalitions.clone(),
+                template: None,
+                radio_effects: None,
             })
         })
         .collect::<Vec<_>>();
@@ -293,10 +391,11 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
         log::info!("Broadcast Stations:");
         for station in &broadcasts {
             log::info!(
-                "  - {} (Freq: {}, Voice: {:?})",
+                "  - {} (Freq: {}, Voice: {:?}, Coalitions: {:?})",
                 station.name,
                 station.freq,
-                station.tts
+                station.tts,
+                station.coalitions
             );
         }
     }
@@ -308,6 +407,8 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
                 name: mission_unit.name.clone(),
                 freq: config.freq,
                 tts: config.tts.unwrap_or_else(|| default_voice.clone()),
+                fallback_tts: config.fallback_tts,
+                prosody: config.prosody,
                 transmitter: Transmitter::Weather(WeatherTransmitter {
                     position: if mission_unit.is_static {
                         Some(Position {
@@ -319,12 +420,24 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
                         None
                     },
                     name: config.name,
+                    icao: config.icao,
                     unit_id: mission_unit.id,
                     unit_name: mission_unit.name.clone(),
                     info_ltr_offset: rng.random_range(0..25),
                     info_ltr_override: None,
+                    winds_aloft: config.winds_aloft,
+                    locale: config.locale,
+                    units: config.units,
                 }),
                 ipc: MissionInterface::Ipc(ipc.clone()),
+                coalitions: mission_unit.coalitions.clone(),
+                template: load_template(
+                    template_dir,
+                    config.template.as_deref(),
+                    "weather",
+                    config.locale,
+                ),
+                radio_effects: None,
             })
         })
         .collect::<Vec<_>>();
@@ -335,10 +448,11 @@ pub fn extract(lua: &Lua, default_voice: &TextToSpeechProvider) -> Result<Info,
         log::info!("Weather Stations:");
         for station in &weather_stations {
             log::info!(
-                "  - {} (Freq: {}, Voice: {:?})",
+                "  - {} (Freq: {}, Voice: {:?}, Coalitions: {:?})",
                 station.name,
                 station.freq,
-                station.tts
+                station.tts,
+                station.coalitions
             );
         }
     }
@@ -358,4 +472,40 @@ struct MissionUnit {
     y: f64,
     alt: f64,
     is_static: bool,
+    coalitions: Vec<Coalition>,
+}
+
+/// Load the template `name` of a station or, if not set, the template `default` (e.g. `airfield`)
+/// overriding the built-in one of the transmitter. A template for the language of the station
+/// (e.g. `airfield.de.txt`) takes precedence. Overrides without a language only apply to English
+/// stations, to not replace the built-in templates of other languages with English wording. Falls
+/// back to the built-in template if no template can be loaded.
+fn load_template(
+    template_dir: &Path,
+    name: Option<&str>,
+    default: &str,
+    locale: Locale,
+) -> Option<Template> {
+    let template = name.unwrap_or(default);
+    let localized = format!("{}.{}", template, locale.ssml_lang());
+    let mut candidates = vec![localized.as_str()];
+    if name.is_some() || locale == Locale::En {
+        candidates.push(template);
+    }
+
+    for candidate in candidates {
+        match Template::load(template_dir, candidate) {
+            Ok(Some(template)) => return Some(template),
+            Ok(None) => {}
+            Err(err) => {
+                log::error!("Failed to load template {}: {}", candidate, err);
+                return None;
+            }
+        }
+    }
+
+    if let Some(name) = name {
+        log::warn!("Template {} not found in {}", name, template_dir.display());
+    }
+    None
 }
@@ -2,4 +2,4 @@
 mod tts;
 
 #[cfg(target_os = "windows")]
-pub use tts::tts;
+pub use tts::{Voice, tts, voices};
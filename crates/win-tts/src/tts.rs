@@ -1,9 +1,40 @@
 use thiserror::Error;
 use tokio::task;
-use windows::Media::SpeechSynthesis::SpeechSynthesizer;
+use windows::Media::SpeechSynthesis::{SpeechSynthesizer, VoiceGender};
 use windows::Storage::Streams::DataReader;
 use windows::core::HSTRING;
 
+/// A voice installed on the machine.
+#[derive(Debug, Clone)]
+pub struct Voice {
+    /// The display name of the voice, e.g. `Microsoft Zira`.
+    pub name: String,
+    /// The language of the voice, e.g. `en-US`.
+    pub language: String,
+    /// Either `female` or `male`.
+    pub gender: String,
+}
+
+/// Lists all voices installed on the machine.
+pub fn voices() -> Result<Vec<Voice>, Error> {
+    let all_voices = SpeechSynthesizer::AllVoices()?;
+    let len = all_voices.Size()? as usize;
+    let mut voices = Vec::with_capacity(len);
+    for i in 0..len {
+        let v = all_voices.GetAt(i as u32)?;
+        voices.push(Voice {
+            name: v.DisplayName()?.to_string(),
+            language: v.Language()?.to_string(),
+            gender: if v.Gender()? == VoiceGender::Female {
+                String::from("female")
+            } else {
+                String::from("male")
+            },
+        });
+    }
+    Ok(voices)
+}
+
 pub async fn tts(ssml: impl Into<String>, voice: Option<&str>) -> Result<Vec<u8>, Error> {
     let ssml = ssml.into();
     let voice = voice.map(String::from);
@@ -36,17 +67,19 @@ async fn tts_local(mut ssml: String, voice: Option<String>) -> Result<Vec<u8>, E
     // Note, there does not seem to be a way to explicitly set 16000kHz, 16 audio bits per
     // sample and mono channel.
 
+    // the language of the report, e.g. `de` for `<speak version="1.0" xml:lang="de">`
+    let report_lang = ssml
+        .split_once("xml:lang=\"")
+        .and_then(|(_, rest)| rest.split_once('"'))
+        .map(|(lang, _)| lang.to_string())
+        .unwrap_or_else(|| String::from("en"));
+
     let mut voice_info = None;
     if let Some(ref voice) = voice {
         let all_voices = SpeechSynthesizer::AllVoices()?;
         let len = all_voices.Size()? as usize;
         for i in 0..len {
             let v = all_voices.GetAt(i as u32)?;
-            let lang = v.Language()?.to_string();
-            if !lang.starts_with("en-") {
-                continue;
-            }
-
             let name = v.DisplayName()?.to_string();
             if name.ends_with(voice) {
                 voice_info = Some(v);
@@ -54,13 +87,13 @@ async fn tts_local(mut ssml: String, voice: Option<String>) -> Result<Vec<u8>, E
             }
         }
     } else {
-        // default to the first english voice in the list
+        // default to the first voice in the list that speaks the language of the report
         let all_voices = SpeechSynthesizer::AllVoices()?;
         let len = all_voices.Size()? as usize;
         for i in 0..len {
             let v = all_voices.GetAt(i as u32)?;
             let lang = v.Language()?.to_string();
-            if lang.starts_with("en-") {
+            if lang.starts_with(&format!("{}-", report_lang)) {
                 let name = v.DisplayName()?.to_string();
                 log::debug!("Using WIN voice: {}", name);
                 voice_info = Some(v);
@@ -69,7 +102,10 @@ async fn tts_local(mut ssml: String, voice: Option<String>) -> Result<Vec<u8>, E
         }
 
         if voice_info.is_none() {
-            log::warn!("Could not find any english Windows TTS voice");
+            log::warn!(
+                "Could not find any Windows TTS voice for the language `{}`",
+                report_lang
+            );
         }
     }
 
@@ -82,10 +118,6 @@ async fn tts_local(mut ssml: String, voice: Option<String>) -> Result<Vec<u8>, E
         for i in 0..len {
             let v = all_voices.GetAt(i as u32)?;
             let lang = v.Language()?.to_string();
-            if !lang.starts_with("en-") {
-                continue;
-            }
-
             let name = v.DisplayName()?.to_string();
             log::info!("- {} ({})", name, lang);
         }
@@ -94,7 +126,11 @@ async fn tts_local(mut ssml: String, voice: Option<String>) -> Result<Vec<u8>, E
     let synth = SpeechSynthesizer::new()?;
     if let Some(info) = voice_info {
         let lang = info.Language()?.to_string();
-        ssml = ssml.replacen("xml:lang=\"en\"", &format!("xml:lang=\"{}\"", lang), 1);
+        ssml = ssml.replacen(
+            &format!("xml:lang=\"{}\"", report_lang),
+            &format!("xml:lang=\"{}\"", lang),
+            1,
+        );
         synth.SetVoice(&info)?;
     }
 
@@ -3,6 +3,56 @@ package.cpath = package.cpath..";"..lfs.writedir().."Mods\\services\\DATIS\\bin\
 local datis = nil
 local isStarted = false
 
+-- The scripting API for mission scripts. Mission scripts run in a separate Lua environment, so
+-- their calls are queued there and applied by the hook about once a second (see
+-- `datis_handleMissionCalls`).
+local missionApi = [[
+  DATIS = {
+    calls = {},
+    broadcasts = 0,
+  }
+
+  local function queue(call)
+    table.insert(DATIS.calls, call)
+  end
+
+  function DATIS.addBroadcast(freq, message, voice, options)
+    local opts = {}
+    for k, v in pairs(options or {}) do
+      opts[k] = v
+    end
+    -- the name is generated here already to be able to return it right away
+    if opts.name == nil then
+      DATIS.broadcasts = DATIS.broadcasts + 1
+      opts.name = "Mission Broadcast " .. DATIS.broadcasts
+    end
+    queue({ method = "addBroadcast", freq = freq, message = message, voice = voice, options = opts })
+    return opts.name
+  end
+
+  function DATIS.setActiveRunway(airfield, runway)
+    queue({ method = "setActiveRunway", airfield = airfield, runway = runway })
+  end
+
+  function DATIS.setRunwayClosed(airfield, runway, closed)
+    queue({ method = "setRunwayClosed", airfield = airfield, runway = runway, closed = closed })
+  end
+
+  function DATIS.setInfoLetter(airfield, letter)
+    queue({ method = "setInfoLetter", airfield = airfield, letter = letter })
+  end
+
+  function DATIS.removeStation(name)
+    queue({ method = "removeStation", name = name })
+  end
+
+  function DATIS.takeCalls()
+    local calls = DATIS.calls
+    DATIS.calls = {}
+    return net.lua2json(calls)
+  end
+]]
+
 function datis_start()
   if not DCS.isServer() then
     log.write("[DATIS]", log.WARNING, "Starting DATIS skipped for not being the Server ...")
@@ -20,6 +70,11 @@ function datis_start()
   end
   datis.start()
 
+  -- expose the scripting API (e.g. `DATIS.addBroadcast`) to other hook scripts and to mission
+  -- scripts
+  DATIS = datis
+  net.dostring_in("server", missionApi)
+
   log.write("[DATIS]", log.INFO, "Started")
 
   if DCS.getPause() then
@@ -33,6 +88,8 @@ function datis_stop()
   if datis ~= nil then
     datis.stop()
     datis = nil
+    DATIS = nil
+    net.dostring_in("server", "DATIS = nil")
   end
 end
 
@@ -59,6 +116,45 @@ function datis_simulation_frame()
     if not ok then
       log.write("[DATIS]", log.ERROR, "Next error: " .. tostring(err))
     end
+
+    ok, err = pcall(datis_handleMissionCalls)
+    if not ok then
+      log.write("[DATIS]", log.ERROR, "Mission call error: " .. tostring(err))
+    end
+  end
+end
+
+function datis_handleMissionCalls()
+  if datis == nil then
+    return
+  end
+
+  local result = net.dostring_in("server", "return DATIS ~= nil and DATIS.takeCalls() or ''")
+  if result == nil or result == "" then
+    return
+  end
+
+  for _, call in ipairs(net.json2lua(result)) do
+    local ok, err = pcall(datis_handleMissionCall, call)
+    if not ok then
+      log.write("[DATIS]", log.ERROR, "Mission call " .. tostring(call.method) .. " failed: " .. tostring(err))
+    end
+  end
+end
+
+function datis_handleMissionCall(call)
+  if call.method == "addBroadcast" then
+    datis.addBroadcast(call.freq, call.message, call.voice, call.options)
+  elseif call.method == "setActiveRunway" then
+    datis.setActiveRunway(call.airfield, call.runway)
+  elseif call.method == "setRunwayClosed" then
+    datis.setRunwayClosed(call.airfield, call.runway, call.closed)
+  elseif call.method == "setInfoLetter" then
+    datis.setInfoLetter(call.airfield, call.letter)
+  elseif call.method == "removeStation" then
+    datis.removeStation(call.name)
+  else
+    error("unknown method " .. tostring(call.method))
   end
 end
 
@@ -90,6 +186,8 @@ function datis_handleRequest(method, params)
       result = {
         windSpeed = wind.v,
         windDir = wind.a,
+        -- the ground turbulence is stored in 0.1 m/s
+        turbulence = (weather.groundTurbulence or 0) / 10,
         temp = temp,
         pressure = pressure,
         fogThickness = weather.fog.thickness,